@@ -0,0 +1,147 @@
+use std::{
+    cell::Cell,
+    io::{self, Read, Write},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+const DATA: u16 = 0;
+const STATUS: u16 = 1;
+const COMMAND: u16 = 2;
+const CONTROL: u16 = 3;
+
+const STATUS_RX_FULL: u8 = 0x08;
+const STATUS_TX_EMPTY: u8 = 0x10;
+const STATUS_IRQ: u8 = 0x80;
+
+const COMMAND_DTR: u8 = 0x01;
+const COMMAND_RX_IRQ_DISABLED: u8 = 0x02;
+
+/// A 6551 ACIA whose transmitter writes to stdout and whose receiver reads from stdin
+///
+/// Only the parts of the ACIA that programs commonly touch are emulated. Data is sent and received
+/// immediately regardless of the baud rate, so the transmitter is always empty. The receiver
+/// interrupt is asserted while a byte is waiting, unless it's disabled in the command register.
+///
+/// Line feeds read from stdin are converted to carriage returns, which is what monitors and BASIC
+/// interpreters expect at the end of a line. Carriage returns written are printed as new lines and
+/// line feeds are dropped, so both CR and CR LF line endings print correctly.
+pub struct Acia {
+    input: Receiver<u8>,
+    rx_data: Cell<Option<u8>>,
+    input_closed: Cell<bool>,
+    bytes_written: usize,
+    command: u8,
+    control: u8,
+}
+
+impl Acia {
+    pub fn new() -> Self {
+        let (sender, input) = mpsc::channel();
+
+        // Reading stdin blocks, so it's done on its own thread and the emulator polls the channel
+        thread::spawn(move || {
+            for byte in io::stdin().lock().bytes() {
+                let byte = match byte {
+                    Ok(b'\n') => b'\r',
+                    Ok(byte) => byte,
+                    Err(_) => break,
+                };
+                if sender.send(byte).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Acia {
+            input,
+            rx_data: Cell::new(None),
+            input_closed: Cell::new(false),
+            bytes_written: 0,
+            command: 0,
+            control: 0,
+        }
+    }
+
+    /// Whether stdin has been closed and every byte read from it has been received
+    pub fn input_closed(&self) -> bool {
+        self.input_closed.get() && self.rx_data.get().is_none()
+    }
+
+    /// How many bytes the program has sent to stdout
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    pub fn irq_asserted(&self) -> bool {
+        self.poll_input();
+        self.rx_irq_asserted()
+    }
+
+    pub fn read(&self, register: u16) -> u8 {
+        self.poll_input();
+        match register {
+            DATA => self.rx_data.take().unwrap_or(0),
+            _ => self.peek(register),
+        }
+    }
+
+    pub fn peek(&self, register: u16) -> u8 {
+        match register {
+            DATA => self.rx_data.get().unwrap_or(0),
+            STATUS => {
+                let mut status = STATUS_TX_EMPTY;
+                if self.rx_data.get().is_some() {
+                    status |= STATUS_RX_FULL;
+                }
+                if self.rx_irq_asserted() {
+                    status |= STATUS_IRQ;
+                }
+                status
+            }
+            COMMAND => self.command,
+            CONTROL => self.control,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn write(&mut self, register: u16, value: u8) {
+        match register {
+            DATA => {
+                let mut stdout = io::stdout();
+                let result = match value {
+                    b'\r' => stdout.write_all(b"\n"),
+                    b'\n' => Ok(()),
+                    _ => stdout.write_all(&[value]),
+                };
+                result
+                    .and_then(|_| stdout.flush())
+                    .expect("Unable to write to stdout");
+                self.bytes_written += 1;
+            }
+            // Writing the status register is a programmed reset, which clears the lower five bits
+            // of the command register
+            STATUS => self.command &= 0xe0,
+            COMMAND => self.command = value,
+            CONTROL => self.control = value,
+            _ => unreachable!(),
+        }
+    }
+
+    fn rx_irq_asserted(&self) -> bool {
+        self.rx_data.get().is_some()
+            && self.command & COMMAND_DTR != 0
+            && self.command & COMMAND_RX_IRQ_DISABLED == 0
+    }
+
+    fn poll_input(&self) {
+        if self.rx_data.get().is_some() {
+            return;
+        }
+        match self.input.try_recv() {
+            Ok(byte) => self.rx_data.set(Some(byte)),
+            Err(TryRecvError::Empty) => (),
+            Err(TryRecvError::Disconnected) => self.input_closed.set(true),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod spec_tests;
+
+use std::fmt;
+
+const DATA_RECORD: u8 = 0x00;
+const EOF_RECORD: u8 = 0x01;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum LoadError {
+    TooLarge { addr: u16, len: usize },
+    InvalidRecord { line: usize },
+    BadChecksum { line: usize },
+    UnsupportedRecord { line: usize, record_type: u8 },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::TooLarge { addr, len } => write!(
+                f,
+                "{} bytes loaded at ${:04X} extend past the end of memory",
+                len, addr
+            ),
+            LoadError::InvalidRecord { line } => write!(f, "Invalid record on line {}", line),
+            LoadError::BadChecksum { line } => write!(f, "Bad checksum on line {}", line),
+            LoadError::UnsupportedRecord { line, record_type } => write!(
+                f,
+                "Unsupported record type {:02X} on line {}",
+                record_type, line
+            ),
+        }
+    }
+}
+
+/// Whether `contents` looks like Intel HEX rather than a raw binary
+pub fn is_intel_hex(contents: &[u8]) -> bool {
+    contents.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b':')
+}
+
+/// Copies a raw binary into memory at `addr`
+pub fn load_binary(memory: &mut [u8], addr: u16, contents: &[u8]) -> Result<(), LoadError> {
+    let start = addr as usize;
+    if start + contents.len() > memory.len() {
+        return Err(LoadError::TooLarge {
+            addr,
+            len: contents.len(),
+        });
+    }
+    memory[start..start + contents.len()].copy_from_slice(contents);
+    Ok(())
+}
+
+/// Copies the data records of an Intel HEX file into memory
+///
+/// Only data and end of file records are supported, since extended addresses aren't needed for a
+/// 16-bit address space.
+pub fn load_intel_hex(memory: &mut [u8], contents: &str) -> Result<(), LoadError> {
+    for (i, line) in contents.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let bytes = parse_record(line).ok_or(LoadError::InvalidRecord { line: line_number })?;
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(LoadError::InvalidRecord { line: line_number });
+        }
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return Err(LoadError::BadChecksum { line: line_number });
+        }
+
+        let addr = u16::from(bytes[1]) << 8 | u16::from(bytes[2]);
+        let data = &bytes[4..bytes.len() - 1];
+        match bytes[3] {
+            DATA_RECORD => load_binary(memory, addr, data)?,
+            EOF_RECORD => break,
+            record_type => {
+                return Err(LoadError::UnsupportedRecord {
+                    line: line_number,
+                    record_type,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+// Decodes the hex digits following the colon that starts a record
+fn parse_record(line: &str) -> Option<Vec<u8>> {
+    let digits = line.strip_prefix(':')?;
+    if digits.len() % 2 != 0 || !digits.is_ascii() {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn intel_hex() {
+    let mut memory = [0; 0x10000];
+    let contents = ":0300300002337A1E\n\n:02FFFE003412BB\n:00000001FF\n";
+    assert_eq!(Ok(()), load_intel_hex(&mut memory, contents));
+    assert_eq!([0x02, 0x33, 0x7a], memory[0x30..0x33]);
+    assert_eq!([0x34, 0x12], memory[0xfffe..]);
+}
+
+#[test]
+fn intel_hex_stops_at_eof_record() {
+    let mut memory = [0; 0x10000];
+    let contents = ":00000001FF\n:0300300002337A1E\nnot a record\n";
+    assert_eq!(Ok(()), load_intel_hex(&mut memory, contents));
+    assert_eq!([0, 0, 0], memory[0x30..0x33]);
+}
+
+#[test]
+fn intel_hex_bad_checksum() {
+    let mut memory = [0; 0x10000];
+    let contents = ":02FFFE003412BB\n:0300300002337A1F\n";
+    assert_eq!(
+        Err(LoadError::BadChecksum { line: 2 }),
+        load_intel_hex(&mut memory, contents)
+    );
+}
+
+#[test]
+fn intel_hex_short_record() {
+    let mut memory = [0; 0x10000];
+
+    // The byte count says there are 3 data bytes but there are only 2
+    assert_eq!(
+        Err(LoadError::InvalidRecord { line: 1 }),
+        load_intel_hex(&mut memory, ":030030000233FF\n")
+    );
+
+    // Too short to have an address, record type and checksum
+    assert_eq!(
+        Err(LoadError::InvalidRecord { line: 1 }),
+        load_intel_hex(&mut memory, ":0000\n")
+    );
+}
+
+#[test]
+fn intel_hex_unsupported_record() {
+    let mut memory = [0; 0x10000];
+    assert_eq!(
+        Err(LoadError::UnsupportedRecord {
+            line: 1,
+            record_type: 0x04
+        }),
+        load_intel_hex(&mut memory, ":020000040000FA\n")
+    );
+}
+
+#[test]
+fn parse_records() {
+    assert_eq!(
+        Some(vec![0x00, 0x00, 0x00, 0x01, 0xff]),
+        parse_record(":00000001FF")
+    );
+    assert_eq!(None, parse_record("00000001FF"));
+    assert_eq!(None, parse_record(":00000001F"));
+    assert_eq!(None, parse_record(":0000000GFF"));
+}
@@ -0,0 +1,216 @@
+//! A single-board computer built around the 6502, with 64K of RAM and a 6551 ACIA connected to the
+//! terminal
+//!
+//!     cargo run --example sbc -- [--addr ADDR] [--start ADDR] [--acia ADDR] FILE
+//!
+//! `FILE` is an Intel HEX file or a raw binary. Raw binaries are loaded at `--addr`, or so that
+//! they end at $FFFF if it isn't given, which suits ROM images that include the vectors. The CPU is
+//! reset through the reset vector unless `--start` is given. The ACIA's four registers are mapped
+//! at `--acia`, which defaults to $8800 as on the Symon simulator, so builds of EhBASIC or Wozmon
+//! for it run unchanged. Once stdin is closed and all of it has been read, the machine keeps running
+//! until the program has gone a second without writing anything, so piped input is processed to
+//! the end.
+
+mod acia;
+mod loader;
+
+use crate::acia::Acia;
+use cpu6502::cpu::{Cpu, Interconnect, Interrupt, Nmos6502};
+use std::{
+    env, fs, process, thread,
+    time::{Duration, Instant},
+};
+
+const MEMORY_SIZE: usize = 0x10000;
+const DEFAULT_ACIA_ADDR: u16 = 0x8800;
+const CLOCK_HZ: u64 = 1_000_000;
+
+// How many cycles are run between checks of whether the emulator is ahead of real time
+const THROTTLE_CYCLES: usize = 10_000;
+
+// How long the program can go without writing anything once stdin is closed before the machine
+// stops, a second at 1MHz
+const EXIT_IDLE_CYCLES: usize = 1_000_000;
+
+struct Sbc {
+    memory: Box<[u8]>,
+    acia: Acia,
+    acia_addr: u16,
+    elapsed_cycles: usize,
+}
+
+impl Sbc {
+    fn acia_register(&self, address: u16) -> Option<u16> {
+        let register = address.wrapping_sub(self.acia_addr);
+        if register < 4 {
+            Some(register)
+        } else {
+            None
+        }
+    }
+}
+
+impl Interconnect for Sbc {
+    fn read(&self, address: u16) -> u8 {
+        match self.acia_register(address) {
+            Some(register) => self.acia.read(register),
+            None => self.memory[address as usize],
+        }
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        match self.acia_register(address) {
+            Some(register) => self.acia.write(register, value),
+            None => self.memory[address as usize] = value,
+        }
+    }
+
+    fn tick(&mut self) -> Interrupt {
+        self.elapsed_cycles += 1;
+        Interrupt::None
+    }
+
+    fn elapsed_cycles(&self) -> usize {
+        self.elapsed_cycles
+    }
+
+    fn irq_asserted(&self) -> bool {
+        self.acia.irq_asserted()
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        match self.acia_register(address) {
+            Some(register) => self.acia.peek(register),
+            None => self.memory[address as usize],
+        }
+    }
+}
+
+struct Options {
+    file: String,
+    addr: Option<u16>,
+    start: Option<u16>,
+    acia_addr: u16,
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        eprintln!("Usage: sbc [--addr ADDR] [--start ADDR] [--acia ADDR] FILE");
+        process::exit(2);
+    });
+
+    let contents = fs::read(&options.file).unwrap_or_else(|e| {
+        eprintln!("Unable to read {}: {}", options.file, e);
+        process::exit(1);
+    });
+
+    let mut memory = vec![0; MEMORY_SIZE].into_boxed_slice();
+    let loaded = if loader::is_intel_hex(&contents) {
+        loader::load_intel_hex(&mut memory, &String::from_utf8_lossy(&contents))
+    } else {
+        let addr = options
+            .addr
+            .unwrap_or_else(|| (MEMORY_SIZE - contents.len().min(MEMORY_SIZE)) as u16);
+        loader::load_binary(&mut memory, addr, &contents)
+    };
+    if let Err(e) = loaded {
+        eprintln!("Unable to load {}: {}", options.file, e);
+        process::exit(1);
+    }
+
+    let sbc = Sbc {
+        memory,
+        acia: Acia::new(),
+        acia_addr: options.acia_addr,
+        elapsed_cycles: 0,
+    };
+    let mut cpu = Cpu::with_variant(sbc, 0, Nmos6502);
+    cpu.power_on();
+    if let Some(start) = options.start {
+        let mut state = cpu.state();
+        state.set_pc(start);
+        cpu.set_state(state);
+    }
+
+    run(&mut cpu);
+}
+
+fn run(cpu: &mut Cpu<Sbc, Nmos6502>) {
+    let start_time = Instant::now();
+
+    // The cycle count and bytes written when the program last wrote something after stdin closed
+    let mut last_output = None;
+    loop {
+        let acia = &cpu.interconnect.acia;
+        if acia.input_closed() {
+            let cycles = cpu.interconnect.elapsed_cycles();
+            match last_output {
+                Some((since, written)) if written == acia.bytes_written() => {
+                    if cycles - since >= EXIT_IDLE_CYCLES {
+                        break;
+                    }
+                }
+                _ => last_output = Some((cycles, acia.bytes_written())),
+            }
+        }
+
+        let throttle_at = cpu.interconnect.elapsed_cycles() + THROTTLE_CYCLES;
+        while cpu.interconnect.elapsed_cycles() < throttle_at {
+            if let Err(fault) = cpu.try_step() {
+                let state = cpu.state();
+                eprintln!("Emulation stopped: {}", fault);
+                eprintln!(
+                    "PC:{:04X} A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+                    state.pc(),
+                    state.acc(),
+                    state.x(),
+                    state.y(),
+                    state.status(),
+                    state.sp(),
+                    cpu.interconnect.elapsed_cycles()
+                );
+                process::exit(1);
+            }
+        }
+
+        let emulated_time =
+            Duration::from_micros(cpu.interconnect.elapsed_cycles() as u64 * 1_000_000 / CLOCK_HZ);
+        if let Some(ahead) = emulated_time.checked_sub(start_time.elapsed()) {
+            thread::sleep(ahead);
+        }
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut file = None;
+    let mut addr = None;
+    let mut start = None;
+    let mut acia_addr = DEFAULT_ACIA_ADDR;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--addr" => addr = Some(parse_addr(args.next())?),
+            "--start" => start = Some(parse_addr(args.next())?),
+            "--acia" => acia_addr = parse_addr(args.next())?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if file.is_none() => file = Some(arg),
+            _ => return Err("Only one file can be loaded".to_owned()),
+        }
+    }
+
+    Ok(Options {
+        file: file.ok_or("No file given")?,
+        addr,
+        start,
+        acia_addr,
+    })
+}
+
+// Parses an address written in hex, with an optional `$` or `0x` prefix
+fn parse_addr(arg: Option<String>) -> Result<u16, String> {
+    let arg = arg.ok_or("Missing address")?;
+    let digits = arg.trim_start_matches('$').trim_start_matches("0x");
+    u16::from_str_radix(digits, 16).map_err(|_| format!("Invalid address {}", arg))
+}
@@ -1,4 +1,4 @@
-use std::num::Wrapping;
+use core::num::Wrapping;
 
 pub fn lo_hi(val: u16) -> (u8, u8) {
     let low_byte = (val & 0xff) as u8;
@@ -22,6 +22,6 @@ pub fn wrapping_dec(val: u8) -> u8 {
     wrapping_subtract(val, 1)
 }
 
-fn wrapping_subtract(lhs: u8, rhs: u8) -> u8 {
+pub fn wrapping_subtract(lhs: u8, rhs: u8) -> u8 {
     (Wrapping(lhs) - Wrapping(rhs)).0
 }
@@ -0,0 +1,110 @@
+use crate::cpu::{Interconnect, Interrupt};
+use core::{cell::RefCell, mem};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BusOperation {
+    Read,
+    Write,
+}
+
+/// A single bus access, including the dummy accesses made on cycles where the CPU is busy
+/// internally
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BusCycle {
+    /// The value of `Interconnect::elapsed_cycles` when the access was made
+    pub cycle: usize,
+    pub address: u16,
+    pub value: u8,
+    pub operation: BusOperation,
+}
+
+/// An `Interconnect` that records every read and write made through it
+///
+/// This is meant for checking the CPU against per-cycle reference data. Since the 6502 accesses
+/// the bus exactly once per cycle, a correct CPU produces one entry for each tick. Peeks aren't
+/// recorded, and neither are cycles where the CPU is halted, since the bus belongs to whatever
+/// halted it.
+pub struct BusLog<I: Interconnect> {
+    inner: I,
+    cycles: RefCell<Vec<BusCycle>>,
+}
+
+impl<I: Interconnect> BusLog<I> {
+    pub fn new(inner: I) -> Self {
+        BusLog {
+            inner,
+            cycles: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    /// Returns the accesses recorded so far and clears the log
+    pub fn take_cycles(&mut self) -> Vec<BusCycle> {
+        mem::take(self.cycles.get_mut())
+    }
+
+    fn record(&self, address: u16, value: u8, operation: BusOperation) {
+        self.cycles.borrow_mut().push(BusCycle {
+            cycle: self.inner.elapsed_cycles(),
+            address,
+            value,
+            operation,
+        });
+    }
+}
+
+impl<I: Interconnect> Interconnect for BusLog<I> {
+    fn read(&self, address: u16) -> u8 {
+        let value = self.inner.read(address);
+        self.record(address, value, BusOperation::Read);
+        value
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.inner.write(address, value);
+        self.record(address, value, BusOperation::Write);
+    }
+
+    fn tick(&mut self) -> Interrupt {
+        self.inner.tick()
+    }
+
+    fn elapsed_cycles(&self) -> usize {
+        self.inner.elapsed_cycles()
+    }
+
+    fn irq_asserted(&self) -> bool {
+        self.inner.irq_asserted()
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        self.inner.peek(address)
+    }
+
+    fn halt_requested(&self) -> bool {
+        self.inner.halt_requested()
+    }
+
+    fn halted_cycle(&mut self, address: u16) {
+        self.inner.halted_cycle(address)
+    }
+
+    fn power_on(&mut self) {
+        self.inner.power_on()
+    }
+
+    fn soft_reset(&mut self) {
+        self.inner.soft_reset()
+    }
+}
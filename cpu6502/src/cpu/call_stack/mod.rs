@@ -0,0 +1,214 @@
+#[cfg(all(test, feature = "std"))]
+mod spec_tests;
+
+use crate::cpu::{Cpu, Interconnect, Variant};
+
+// Each frame holds at least a two byte return address, so more than this many can't be on the
+// stack at once
+const MAX_DEPTH: usize = 128;
+
+const JSR: u8 = 0x20;
+const BRK: u8 = 0x00;
+const RTS: u8 = 0x60;
+const RTI: u8 = 0x40;
+
+/// How a frame on the call stack was entered
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FrameKind {
+    Subroutine,
+    Break,
+    Nmi,
+    Irq,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CallFrame {
+    pub kind: FrameKind,
+    /// The address of the `JSR` or `BRK`, or of the instruction an interrupt was taken before
+    pub caller: u16,
+    pub target: u16,
+    /// The stack pointer before the return address was pushed
+    pub sp: u8,
+    /// The elapsed cycles when the call started
+    pub cycle: usize,
+}
+
+impl CallFrame {
+    /// Where a matching `RTS` or `RTI` returns to
+    pub fn return_address(&self) -> u16 {
+        match self.kind {
+            FrameKind::Subroutine => self.caller.wrapping_add(3),
+            FrameKind::Break => self.caller.wrapping_add(2),
+            FrameKind::Nmi | FrameKind::Irq => self.caller,
+        }
+    }
+}
+
+/// Stack manipulation that doesn't follow the call and return discipline
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StackTrick {
+    /// An `RTS` or `RTI` returned to an address that no call pushed, as jump tables using `RTS` do
+    UnmatchedReturn { pc: u16, target: u16 },
+    /// The return address of a frame was replaced before it returned
+    ModifiedReturn { pc: u16, expected: u16, target: u16 },
+    /// Frames were dropped without returning, by pulling their return addresses or moving the
+    /// stack pointer
+    DiscardedFrames { pc: u16, count: usize },
+}
+
+/// A shadow of the hardware stack that tracks calls and interrupts as the CPU executes them
+///
+/// A frame is left as soon as the stack pointer rises back to where it was before the call, so
+/// the call stack stays in step with the hardware stack even when code manipulates it directly.
+pub struct CallStack {
+    frames: [CallFrame; MAX_DEPTH],
+    depth: usize,
+    last_trick: Option<StackTrick>,
+}
+
+impl Default for CallStack {
+    fn default() -> Self {
+        let empty = CallFrame {
+            kind: FrameKind::Subroutine,
+            caller: 0,
+            target: 0,
+            sp: 0,
+            cycle: 0,
+        };
+        CallStack {
+            frames: [empty; MAX_DEPTH],
+            depth: 0,
+            last_trick: None,
+        }
+    }
+}
+
+impl CallStack {
+    /// The frames on the call stack, outermost first
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames[..self.depth]
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The innermost frame
+    pub fn current(&self) -> Option<&CallFrame> {
+        self.frames().last()
+    }
+
+    /// The most recent stack trick, which is cleared when the CPU is reset
+    pub fn last_trick(&self) -> Option<StackTrick> {
+        self.last_trick
+    }
+
+    fn push(&mut self, frame: CallFrame) {
+        if self.depth == MAX_DEPTH {
+            // Only reachable when the stack pointer wraps, in which case the oldest frame is lost
+            self.frames.copy_within(1.., 0);
+            self.depth -= 1;
+        }
+        self.frames[self.depth] = frame;
+        self.depth += 1;
+    }
+
+    // Leaves the frames whose return addresses are no longer on the stack, returning the
+    // outermost of them and how many there were
+    fn unwind(&mut self, sp: u8) -> Option<(CallFrame, usize)> {
+        let mut left = None;
+        let mut count = 0;
+        while let Some(&frame) = self.current() {
+            // The stack pointer wraps, so the depth below the frame is taken as a signed distance
+            if frame.sp.wrapping_sub(sp) as i8 > 0 {
+                break;
+            }
+            self.depth -= 1;
+            left = Some(frame);
+            count += 1;
+        }
+        left.map(|frame| (frame, count))
+    }
+}
+
+impl<I: Interconnect, V: Variant> Cpu<I, V> {
+    pub fn call_stack(&self) -> &CallStack {
+        &self.call_stack
+    }
+}
+
+/// Updates the call stack after the instruction at `pc` executed, given the stack pointer and
+/// elapsed cycles from before it
+pub fn track_instruction<I: Interconnect, V: Variant>(
+    cpu: &mut Cpu<I, V>,
+    opcode: u8,
+    pc: u16,
+    sp: u8,
+    cycle: usize,
+) {
+    let target = cpu.registers.pc;
+    let call_stack = &mut cpu.call_stack;
+    let kind = match opcode {
+        JSR => Some(FrameKind::Subroutine),
+        BRK => Some(FrameKind::Break),
+        _ => None,
+    };
+    if let Some(kind) = kind {
+        call_stack.push(CallFrame {
+            kind,
+            caller: pc,
+            target,
+            sp,
+            cycle,
+        });
+    }
+
+    let is_return = opcode == RTS || opcode == RTI;
+    let trick = match call_stack.unwind(cpu.registers.sp) {
+        None if is_return => Some(StackTrick::UnmatchedReturn { pc, target }),
+        None => None,
+        Some((frame, count)) => {
+            if is_return && frame.return_address() != target {
+                Some(StackTrick::ModifiedReturn {
+                    pc,
+                    expected: frame.return_address(),
+                    target,
+                })
+            } else if !is_return || count > 1 {
+                let returned = if is_return { 1 } else { 0 };
+                Some(StackTrick::DiscardedFrames {
+                    pc,
+                    count: count - returned,
+                })
+            } else {
+                None
+            }
+        }
+    };
+    if trick.is_some() {
+        call_stack.last_trick = trick;
+    }
+}
+
+/// Pushes a frame for an interrupt taken before the instruction at `pc`
+pub fn track_interrupt<I: Interconnect, V: Variant>(
+    cpu: &mut Cpu<I, V>,
+    kind: FrameKind,
+    pc: u16,
+    sp: u8,
+    cycle: usize,
+) {
+    let target = cpu.registers.pc;
+    cpu.call_stack.push(CallFrame {
+        kind,
+        caller: pc,
+        target,
+        sp,
+        cycle,
+    });
+}
+
+pub fn clear<I: Interconnect, V: Variant>(cpu: &mut Cpu<I, V>) {
+    cpu.call_stack.depth = 0;
+    cpu.call_stack.last_trick = None;
+}
@@ -0,0 +1,195 @@
+use crate::cpu::{
+    test_fixture::TestCpu, CallFrame, FrameKind, Interrupt, StackTrick, BREAK_VECTOR,
+};
+
+const IRQ_SOURCE_TEST: u8 = 0x1;
+
+fn new_fixture(program: &[(u16, &[u8])]) -> TestCpu {
+    let mut cpu = TestCpu::new_test();
+    for &(addr, bytes) in program {
+        cpu.interconnect.store_many(addr, bytes);
+    }
+    cpu.registers.sp = 0xfd;
+    cpu
+}
+
+fn run(cpu: &mut TestCpu, steps: usize) {
+    for _ in 0..steps {
+        cpu.step();
+    }
+}
+
+#[test]
+fn nested_calls() {
+    // JSR $0300, where $0300 does JSR $0400, RTS and $0400 does NOP, RTS
+    let mut cpu = new_fixture(&[
+        (0x200, &[0x20, 0x00, 0x03]),
+        (0x300, &[0x20, 0x00, 0x04, 0x60]),
+        (0x400, &[0xea, 0x60]),
+    ]);
+    run(&mut cpu, 2);
+
+    assert_eq!(
+        &[
+            CallFrame {
+                kind: FrameKind::Subroutine,
+                caller: 0x200,
+                target: 0x300,
+                sp: 0xfd,
+                cycle: 0,
+            },
+            CallFrame {
+                kind: FrameKind::Subroutine,
+                caller: 0x300,
+                target: 0x400,
+                sp: 0xfb,
+                cycle: 6,
+            },
+        ],
+        cpu.call_stack().frames()
+    );
+    assert_eq!(0x303, cpu.call_stack().current().unwrap().return_address());
+
+    run(&mut cpu, 2);
+    assert_eq!(1, cpu.call_stack().depth());
+    assert_eq!(0x300, cpu.call_stack().current().unwrap().target);
+
+    run(&mut cpu, 1);
+    assert_eq!(0x203, cpu.registers.pc);
+    assert_eq!(0, cpu.call_stack().depth());
+    assert_eq!(None, cpu.call_stack().last_trick());
+}
+
+#[test]
+fn call_across_stack_wrap() {
+    // JSR $0300, where $0300 does JSR $0400, RTS and $0400 does RTS, with the return address of the
+    // first call pushed at $0101 and $0100 so the stack pointer wraps to $FF
+    let mut cpu = new_fixture(&[
+        (0x200, &[0x20, 0x00, 0x03]),
+        (0x300, &[0x20, 0x00, 0x04, 0x60]),
+        (0x400, &[0x60]),
+    ]);
+    cpu.registers.sp = 0x01;
+    run(&mut cpu, 2);
+    assert_eq!(0xfd, cpu.registers.sp);
+    assert_eq!(2, cpu.call_stack().depth());
+
+    run(&mut cpu, 1);
+    assert_eq!(1, cpu.call_stack().depth());
+    assert_eq!(0x300, cpu.call_stack().current().unwrap().target);
+
+    run(&mut cpu, 1);
+    assert_eq!(0x203, cpu.registers.pc);
+    assert_eq!(0, cpu.call_stack().depth());
+    assert_eq!(None, cpu.call_stack().last_trick());
+}
+
+#[test]
+fn rts_jump_table() {
+    // JSR $0300, where $0300 pushes $03FF and returns to jump to $0400
+    let mut cpu = new_fixture(&[
+        (0x200, &[0x20, 0x00, 0x03]),
+        (0x300, &[0xa9, 0x03, 0x48, 0xa9, 0xff, 0x48, 0x60]),
+    ]);
+    run(&mut cpu, 6);
+
+    assert_eq!(0x400, cpu.registers.pc);
+    assert_eq!(1, cpu.call_stack().depth());
+    assert_eq!(
+        Some(StackTrick::UnmatchedReturn {
+            pc: 0x306,
+            target: 0x400,
+        }),
+        cpu.call_stack().last_trick()
+    );
+}
+
+#[test]
+fn discarded_frames() {
+    // JSR $0300, where $0300 pulls the return address off the stack
+    let mut cpu = new_fixture(&[(0x200, &[0x20, 0x00, 0x03]), (0x300, &[0x68, 0x68])]);
+    run(&mut cpu, 2);
+    assert_eq!(1, cpu.call_stack().depth());
+    assert_eq!(None, cpu.call_stack().last_trick());
+
+    run(&mut cpu, 1);
+    assert_eq!(0, cpu.call_stack().depth());
+    assert_eq!(
+        Some(StackTrick::DiscardedFrames {
+            pc: 0x301,
+            count: 1,
+        }),
+        cpu.call_stack().last_trick()
+    );
+}
+
+#[test]
+fn modified_return() {
+    // JSR $0300, where $0300 does TSX, INC $0101,X to skip a byte on return, then RTS
+    let mut cpu = new_fixture(&[
+        (0x200, &[0x20, 0x00, 0x03]),
+        (0x300, &[0xba, 0xfe, 0x01, 0x01, 0x60]),
+    ]);
+    run(&mut cpu, 4);
+
+    assert_eq!(0x204, cpu.registers.pc);
+    assert_eq!(0, cpu.call_stack().depth());
+    assert_eq!(
+        Some(StackTrick::ModifiedReturn {
+            pc: 0x304,
+            expected: 0x203,
+            target: 0x204,
+        }),
+        cpu.call_stack().last_trick()
+    );
+}
+
+#[test]
+fn break_and_interrupts() {
+    // BRK, with the handler at $0400 doing NOP, RTI
+    let mut cpu = new_fixture(&[
+        (0x200, &[0x00, 0xea, 0xea, 0xea]),
+        (0x400, &[0xea, 0x40]),
+        (BREAK_VECTOR, &[0x00, 0x04]),
+    ]);
+    run(&mut cpu, 1);
+    let frame = *cpu.call_stack().current().unwrap();
+    assert_eq!(FrameKind::Break, frame.kind);
+    assert_eq!(0x400, frame.target);
+    assert_eq!(0x202, frame.return_address());
+
+    run(&mut cpu, 2);
+    assert_eq!(0x202, cpu.registers.pc);
+    assert_eq!(0, cpu.call_stack().depth());
+
+    cpu.registers.set_interrupt_disable_flag(false);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    while cpu.step() != Interrupt::Irq {}
+    cpu.interconnect.irq_line.release(IRQ_SOURCE_TEST);
+
+    let frame = *cpu.call_stack().current().unwrap();
+    assert_eq!(FrameKind::Irq, frame.kind);
+    assert_eq!(0x400, frame.target);
+    assert_eq!(0xfd, frame.sp);
+
+    run(&mut cpu, 2);
+    assert_eq!(frame.return_address(), cpu.registers.pc);
+    assert_eq!(0, cpu.call_stack().depth());
+    assert_eq!(None, cpu.call_stack().last_trick());
+}
+
+#[test]
+fn reset_clears() {
+    // JSR $0300, where $0300 discards the return address and calls itself
+    let mut cpu = new_fixture(&[
+        (0x200, &[0x20, 0x00, 0x03]),
+        (0x300, &[0x68, 0x68, 0x20, 0x00, 0x03]),
+    ]);
+    run(&mut cpu, 4);
+    assert_eq!(1, cpu.call_stack().depth());
+    assert_eq!(true, cpu.call_stack().last_trick().is_some());
+
+    cpu.soft_reset();
+    assert_eq!(0, cpu.call_stack().depth());
+    assert_eq!(None, cpu.call_stack().last_trick());
+}
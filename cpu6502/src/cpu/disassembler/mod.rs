@@ -0,0 +1,293 @@
+#[cfg(all(test, feature = "std"))]
+mod spec_tests;
+
+use crate::cpu::{opcodes, CpuState, Interconnect};
+use core::fmt;
+
+macro_rules! mnemonics {
+    ($($variant:ident => $name:expr,)*) => {
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+        pub enum Mnemonic {
+            $($variant,)*
+        }
+
+        impl Mnemonic {
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(Mnemonic::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+mnemonics! {
+    Adc => "ADC",
+    And => "AND",
+    Asl => "ASL",
+    Bcc => "BCC",
+    Bcs => "BCS",
+    Beq => "BEQ",
+    Bit => "BIT",
+    Bmi => "BMI",
+    Bne => "BNE",
+    Bpl => "BPL",
+    Brk => "BRK",
+    Bvc => "BVC",
+    Bvs => "BVS",
+    Clc => "CLC",
+    Cld => "CLD",
+    Cli => "CLI",
+    Clv => "CLV",
+    Cmp => "CMP",
+    Cpx => "CPX",
+    Cpy => "CPY",
+    Dec => "DEC",
+    Dex => "DEX",
+    Dey => "DEY",
+    Eor => "EOR",
+    Inc => "INC",
+    Inx => "INX",
+    Iny => "INY",
+    Jmp => "JMP",
+    Jsr => "JSR",
+    Lda => "LDA",
+    Ldx => "LDX",
+    Ldy => "LDY",
+    Lsr => "LSR",
+    Nop => "NOP",
+    Ora => "ORA",
+    Pha => "PHA",
+    Php => "PHP",
+    Pla => "PLA",
+    Plp => "PLP",
+    Rol => "ROL",
+    Ror => "ROR",
+    Rti => "RTI",
+    Rts => "RTS",
+    Sbc => "SBC",
+    Sec => "SEC",
+    Sed => "SED",
+    Sei => "SEI",
+    Sta => "STA",
+    Stx => "STX",
+    Sty => "STY",
+    Tax => "TAX",
+    Tay => "TAY",
+    Tsx => "TSX",
+    Txa => "TXA",
+    Txs => "TXS",
+    Tya => "TYA",
+    Alr => "ALR",
+    Anc => "ANC",
+    Ane => "ANE",
+    Arr => "ARR",
+    Axs => "AXS",
+    Dcp => "DCP",
+    Isc => "ISC",
+    Kil => "KIL",
+    Las => "LAS",
+    Lax => "LAX",
+    Lxa => "LXA",
+    Rla => "RLA",
+    Rra => "RRA",
+    Sax => "SAX",
+    Sha => "SHA",
+    Shx => "SHX",
+    Shy => "SHY",
+    Slo => "SLO",
+    Sre => "SRE",
+    Tas => "TAS",
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AddressingMode {
+    Implied,
+    Accumulator,
+    Immediate,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Indirect,
+    IndexedIndirect,
+    IndirectIndexed,
+    Relative,
+}
+
+impl AddressingMode {
+    /// The number of operand bytes following the opcode
+    pub fn operand_len(self) -> u8 {
+        match self {
+            AddressingMode::Implied | AddressingMode::Accumulator => 0,
+            AddressingMode::Immediate
+            | AddressingMode::ZeroPage
+            | AddressingMode::ZeroPageX
+            | AddressingMode::ZeroPageY
+            | AddressingMode::IndexedIndirect
+            | AddressingMode::IndirectIndexed
+            | AddressingMode::Relative => 1,
+            AddressingMode::Absolute
+            | AddressingMode::AbsoluteX
+            | AddressingMode::AbsoluteY
+            | AddressingMode::Indirect => 2,
+        }
+    }
+}
+
+/// A single decoded instruction
+///
+/// Formatting an instruction with `Display` produces standard assembly syntax, such as
+/// `LDA ($44),Y`. Relative branches are shown with their target address.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Instruction {
+    address: u16,
+    bytes: [u8; 3],
+    mnemonic: Mnemonic,
+    mode: AddressingMode,
+    cycles: u8,
+    official: bool,
+}
+
+impl Instruction {
+    pub fn address(&self) -> u16 {
+        self.address
+    }
+
+    pub fn opcode(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    pub fn mnemonic(&self) -> Mnemonic {
+        self.mnemonic
+    }
+
+    pub fn mode(&self) -> AddressingMode {
+        self.mode
+    }
+
+    /// The opcode followed by its operand bytes
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.length() as usize]
+    }
+
+    pub fn operand(&self) -> &[u8] {
+        &self.bytes[1..self.length() as usize]
+    }
+
+    /// The instruction length in bytes, including the opcode
+    pub fn length(&self) -> u8 {
+        1 + self.mode.operand_len()
+    }
+
+    /// The number of cycles taken when no page boundary is crossed and no branch is taken
+    pub fn cycles(&self) -> u8 {
+        self.cycles
+    }
+
+    /// Whether the opcode is part of the documented instruction set
+    pub fn is_official(&self) -> bool {
+        self.official
+    }
+
+    /// The address of the memory operand when executed with `state`, if the instruction has one
+    ///
+    /// For an indirect `JMP` this is the address of the pointer. Jumps and branches to a literal
+    /// address have no memory operand.
+    pub fn effective_address<I: Interconnect>(&self, bus: &I, state: &CpuState) -> Option<u16> {
+        let read16_zp = |addr: u8| {
+            u16::from(bus.peek(u16::from(addr)))
+                | u16::from(bus.peek(u16::from(addr.wrapping_add(1)))) << 8
+        };
+
+        match self.mode {
+            AddressingMode::Implied
+            | AddressingMode::Accumulator
+            | AddressingMode::Immediate
+            | AddressingMode::Relative => None,
+            AddressingMode::ZeroPage => Some(u16::from(self.operand8())),
+            AddressingMode::ZeroPageX => Some(u16::from(self.operand8().wrapping_add(state.x()))),
+            AddressingMode::ZeroPageY => Some(u16::from(self.operand8().wrapping_add(state.y()))),
+            AddressingMode::Absolute => match self.mnemonic {
+                Mnemonic::Jmp | Mnemonic::Jsr => None,
+                _ => Some(self.operand16()),
+            },
+            AddressingMode::AbsoluteX => Some(self.operand16().wrapping_add(u16::from(state.x()))),
+            AddressingMode::AbsoluteY => Some(self.operand16().wrapping_add(u16::from(state.y()))),
+            AddressingMode::Indirect => Some(self.operand16()),
+            AddressingMode::IndexedIndirect => {
+                Some(read16_zp(self.operand8().wrapping_add(state.x())))
+            }
+            AddressingMode::IndirectIndexed => {
+                Some(read16_zp(self.operand8()).wrapping_add(u16::from(state.y())))
+            }
+        }
+    }
+
+    fn operand8(&self) -> u8 {
+        self.bytes[1]
+    }
+
+    fn operand16(&self) -> u16 {
+        u16::from(self.bytes[1]) | u16::from(self.bytes[2]) << 8
+    }
+
+    fn branch_target(&self) -> u16 {
+        self.address
+            .wrapping_add(2)
+            .wrapping_add(self.operand8() as i8 as u16)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mnemonic = self.mnemonic;
+        match self.mode {
+            AddressingMode::Implied => write!(f, "{}", mnemonic),
+            AddressingMode::Accumulator => write!(f, "{} A", mnemonic),
+            AddressingMode::Immediate => write!(f, "{} #${:02X}", mnemonic, self.operand8()),
+            AddressingMode::ZeroPage => write!(f, "{} ${:02X}", mnemonic, self.operand8()),
+            AddressingMode::ZeroPageX => write!(f, "{} ${:02X},X", mnemonic, self.operand8()),
+            AddressingMode::ZeroPageY => write!(f, "{} ${:02X},Y", mnemonic, self.operand8()),
+            AddressingMode::Absolute => write!(f, "{} ${:04X}", mnemonic, self.operand16()),
+            AddressingMode::AbsoluteX => write!(f, "{} ${:04X},X", mnemonic, self.operand16()),
+            AddressingMode::AbsoluteY => write!(f, "{} ${:04X},Y", mnemonic, self.operand16()),
+            AddressingMode::Indirect => write!(f, "{} (${:04X})", mnemonic, self.operand16()),
+            AddressingMode::IndexedIndirect => {
+                write!(f, "{} (${:02X},X)", mnemonic, self.operand8())
+            }
+            AddressingMode::IndirectIndexed => {
+                write!(f, "{} (${:02X}),Y", mnemonic, self.operand8())
+            }
+            AddressingMode::Relative => write!(f, "{} ${:04X}", mnemonic, self.branch_target()),
+        }
+    }
+}
+
+/// Decodes the instruction at `addr` using the same opcode table as the executor
+pub fn disassemble<I: Interconnect>(bus: &I, addr: u16) -> Instruction {
+    let opcode = bus.peek(addr);
+    let metadata = opcodes::metadata(opcode);
+    let mut bytes = [opcode, 0, 0];
+
+    for i in 0..metadata.mode.operand_len() {
+        bytes[1 + i as usize] = bus.peek(addr.wrapping_add(1 + u16::from(i)));
+    }
+
+    Instruction {
+        address: addr,
+        bytes,
+        mnemonic: metadata.mnemonic,
+        mode: metadata.mode,
+        cycles: metadata.cycles,
+        official: metadata.official,
+    }
+}
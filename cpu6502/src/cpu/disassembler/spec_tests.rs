@@ -0,0 +1,142 @@
+use crate::cpu::{
+    disassembler::{disassemble, AddressingMode, Mnemonic},
+    test_fixture::{TestCpu, TestInterconnect},
+    Interconnect,
+};
+
+fn disassemble_bytes(bytes: &[u8]) -> String {
+    let mut bus = TestInterconnect::default();
+    bus.store_many(0x200, bytes);
+    disassemble(&bus, 0x200).to_string()
+}
+
+#[test]
+fn formatting() {
+    assert_eq!("NOP", disassemble_bytes(&[0xea]));
+    assert_eq!("ASL A", disassemble_bytes(&[0x0a]));
+    assert_eq!("LDA #$44", disassemble_bytes(&[0xa9, 0x44]));
+    assert_eq!("LDA $44", disassemble_bytes(&[0xa5, 0x44]));
+    assert_eq!("LDA $44,X", disassemble_bytes(&[0xb5, 0x44]));
+    assert_eq!("LDX $44,Y", disassemble_bytes(&[0xb6, 0x44]));
+    assert_eq!("LDA $4400", disassemble_bytes(&[0xad, 0x00, 0x44]));
+    assert_eq!("LDA $4400,X", disassemble_bytes(&[0xbd, 0x00, 0x44]));
+    assert_eq!("LDA $4400,Y", disassemble_bytes(&[0xb9, 0x00, 0x44]));
+    assert_eq!("JMP ($4400)", disassemble_bytes(&[0x6c, 0x00, 0x44]));
+    assert_eq!("JMP $4400", disassemble_bytes(&[0x4c, 0x00, 0x44]));
+    assert_eq!("LDA ($44,X)", disassemble_bytes(&[0xa1, 0x44]));
+    assert_eq!("LDA ($44),Y", disassemble_bytes(&[0xb1, 0x44]));
+}
+
+#[test]
+fn relative_shows_branch_target() {
+    assert_eq!("BEQ $0212", disassemble_bytes(&[0xf0, 0x10]));
+    assert_eq!("BNE $01F2", disassemble_bytes(&[0xd0, 0xf0]));
+}
+
+#[test]
+fn unofficial() {
+    let mut bus = TestInterconnect::default();
+    bus.store_many(0x200, &[0x04, 0x44, 0xeb, 0x44, 0xa9, 0x44]);
+
+    let instruction = disassemble(&bus, 0x200);
+    assert_eq!(Mnemonic::Nop, instruction.mnemonic());
+    assert_eq!("NOP $44", instruction.to_string());
+    assert_eq!(false, instruction.is_official());
+    assert_eq!(false, disassemble(&bus, 0x202).is_official());
+    assert_eq!(true, disassemble(&bus, 0x204).is_official());
+}
+
+#[test]
+fn instruction_fields() {
+    let mut bus = TestInterconnect::default();
+    bus.store_many(0x200, &[0xbd, 0x00, 0x44]);
+    let instruction = disassemble(&bus, 0x200);
+    assert_eq!(0x200, instruction.address());
+    assert_eq!(0xbd, instruction.opcode());
+    assert_eq!(Mnemonic::Lda, instruction.mnemonic());
+    assert_eq!(AddressingMode::AbsoluteX, instruction.mode());
+    assert_eq!(&[0xbd, 0x00, 0x44], instruction.bytes());
+    assert_eq!(&[0x00, 0x44], instruction.operand());
+    assert_eq!(3, instruction.length());
+    assert_eq!(4, instruction.cycles());
+}
+
+#[test]
+fn operand_bytes_wrap_around_address_space() {
+    let mut bus = TestInterconnect::default();
+    bus.store_many(0xffff, &[0xad]);
+    bus.store_many(0x0000, &[0x00, 0x44]);
+    assert_eq!("LDA $4400", disassemble(&bus, 0xffff).to_string());
+}
+
+/// Executes every opcode without crossing page boundaries or taking branches, and checks that
+/// the executor agrees with the metadata used by the disassembler.
+#[test]
+fn metadata_matches_executor() {
+    for opcode in 0..=0xff_u8 {
+        let mut cpu = TestCpu::new_test();
+        cpu.interconnect.store_many(0x200, &[opcode, 0x00, 0x00]);
+        let instruction = disassemble(&cpu.interconnect, 0x200);
+
+        // Branches are taken when their flag matches bit 5 of the opcode
+        if instruction.mode() == AddressingMode::Relative {
+            cpu.registers.status = if opcode & 0x20 == 0 { 0xff } else { 0x00 };
+        }
+
+        cpu.step();
+
+        assert_eq!(
+            instruction.cycles() as usize,
+            cpu.interconnect.elapsed_cycles(),
+            "cycles for opcode {:02x}",
+            opcode
+        );
+
+        match instruction.mnemonic() {
+            Mnemonic::Brk
+            | Mnemonic::Jmp
+            | Mnemonic::Jsr
+            | Mnemonic::Kil
+            | Mnemonic::Rti
+            | Mnemonic::Rts => (),
+            _ => assert_eq!(
+                0x200 + u16::from(instruction.length()),
+                cpu.registers.pc,
+                "length for opcode {:02x}",
+                opcode
+            ),
+        }
+    }
+}
+
+#[test]
+fn effective_address() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.x = 0x02;
+    cpu.registers.y = 0x03;
+    let state = cpu.state();
+    let effective_address = |bytes: &[u8]| {
+        let mut bus = TestInterconnect::default();
+        bus.store_many(0x0012, &[0x00, 0x44]);
+        bus.store_many(0x00ff, &[0xf0]);
+        bus.store_many(0x0000, &[0x45]);
+        bus.store_many(0x200, bytes);
+        disassemble(&bus, 0x200).effective_address(&bus, &state)
+    };
+
+    assert_eq!(None, effective_address(&[0xea]));
+    assert_eq!(None, effective_address(&[0x0a]));
+    assert_eq!(None, effective_address(&[0xa9, 0x44]));
+    assert_eq!(None, effective_address(&[0xf0, 0x10]));
+    assert_eq!(None, effective_address(&[0x4c, 0x00, 0x44]));
+    assert_eq!(None, effective_address(&[0x20, 0x00, 0x44]));
+    assert_eq!(Some(0x0044), effective_address(&[0xa5, 0x44]));
+    assert_eq!(Some(0x0001), effective_address(&[0xb5, 0xff]));
+    assert_eq!(Some(0x0002), effective_address(&[0xb6, 0xff]));
+    assert_eq!(Some(0x4400), effective_address(&[0x8d, 0x00, 0x44]));
+    assert_eq!(Some(0x4402), effective_address(&[0xbd, 0x00, 0x44]));
+    assert_eq!(Some(0x0002), effective_address(&[0xb9, 0xff, 0xff]));
+    assert_eq!(Some(0x4400), effective_address(&[0x6c, 0x00, 0x44]));
+    assert_eq!(Some(0x4400), effective_address(&[0xa1, 0x10]));
+    assert_eq!(Some(0x45f3), effective_address(&[0xb1, 0xff]));
+}
@@ -3,14 +3,37 @@ use std::{fs::File, io::Read};
 
 const PC_START: u16 = 0x400;
 const MAX_CYCLES: usize = 100000000;
+const IRQ_SOURCE_PROBE: u8 = 0x1;
 
 #[test]
 fn opcodes() {
-    let mut f = File::open("../test_roms/6502_functional_test.bin").unwrap();
+    let mem = TestInterconnect::default();
+    run_functional_test(
+        Cpu::new(mem, PC_START),
+        "../test_roms/6502_functional_test.bin",
+        0x3367,
+    );
+}
+
+#[test]
+fn opcodes_nmos() {
+    // Assembled with disable_decimal = 0, so decimal mode ADC and SBC are tested as well
+    let mem = TestInterconnect::default();
+    run_functional_test(
+        Cpu::with_variant(mem, PC_START, Nmos6502),
+        "../test_roms/6502_functional_test_decimal.bin",
+        0x3463,
+    );
+}
+
+fn run_functional_test<V: Variant>(
+    mut cpu: Cpu<TestInterconnect, V>,
+    rom_path: &str,
+    success_pc: u16,
+) {
+    let mut f = File::open(rom_path).unwrap();
     let mut rom = Vec::<u8>::new();
     f.read_to_end(&mut rom).unwrap();
-    let mem = TestInterconnect::default();
-    let mut cpu = Cpu::new(mem, PC_START);
     cpu.interconnect.store_many(PC_START, &rom);
     let mut last_pc = PC_START;
 
@@ -22,7 +45,7 @@ fn opcodes() {
         }
 
         if last_pc == cpu.registers.pc {
-            if cpu.registers.pc == 0x3367 {
+            if cpu.registers.pc == success_pc {
                 // Success!
                 break;
             } else {
@@ -56,9 +79,9 @@ fn interrupts() {
             }
         }
 
-        if !cpu.registers.interrupt_disable_flag() && (interrupt_probe & 0x1) > 0 {
-            cpu.pending_interrupt = Interrupt::Irq;
-        }
+        cpu.interconnect
+            .irq_line
+            .set(IRQ_SOURCE_PROBE, interrupt_probe & 0x1 > 0);
 
         cpu.step();
         // Prevent endless loop
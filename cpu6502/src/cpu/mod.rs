@@ -1,22 +1,39 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test_fixture;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod functional_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod length_and_timing_tests;
 
+#[cfg(all(test, feature = "std"))]
+mod unofficial_length_and_timing_tests;
+
+#[cfg(all(test, feature = "std"))]
+mod processor_tests;
+
+#[cfg(feature = "std")]
+pub mod bus_log;
+mod call_stack;
+pub mod disassembler;
 mod opcodes;
 mod registers;
+mod state;
 
 use crate::{
     byte_utils::{from_lo_hi, lo_hi, wrapping_dec, wrapping_inc},
     cpu::registers::Registers,
 };
+use core::{fmt, marker::PhantomData};
+
+pub use self::{
+    call_stack::{CallFrame, CallStack, FrameKind, StackTrick},
+    state::CpuState,
+};
 
 pub const ADDRESSABLE_MEMORY: usize = 65_536;
 const STACK_LOC: u16 = 0x100;
@@ -24,77 +41,298 @@ const NMI_VECTOR: u16 = 0xfffa;
 const RESET_VECTOR: u16 = 0xfffc;
 const BREAK_VECTOR: u16 = 0xfffe;
 
-#[allow(dead_code)]
-#[derive(Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum Interrupt {
     None,
     Nmi,
     Irq,
 }
 
+/// A condition that stops the CPU from making progress
+///
+/// Frontends can use this to report a crash instead of silently spinning. The registers at the
+/// time of the fault are available from `Cpu::state`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Fault {
+    /// A `JAM` opcode locked up the CPU. Only a reset recovers it.
+    Jammed { pc: u16, opcode: u8 },
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Fault::Jammed { pc, opcode } => {
+                write!(f, "CPU jammed by opcode ${:02X} at ${:04X}", opcode, pc)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Fault {}
+
+/// A level-triggered IRQ line shared by multiple sources
+///
+/// Each source is identified by a bit, and the line remains asserted for as long as any source is
+/// asserting it. Sources are responsible for releasing the line once they've been acknowledged.
+#[derive(Default, Copy, Clone, Eq, PartialEq)]
+pub struct IrqLine {
+    sources: u8,
+}
+
+impl IrqLine {
+    pub fn assert(&mut self, source: u8) {
+        self.sources |= source;
+    }
+
+    pub fn release(&mut self, source: u8) {
+        self.sources &= !source;
+    }
+
+    pub fn set(&mut self, source: u8, asserted: bool) {
+        if asserted {
+            self.assert(source)
+        } else {
+            self.release(source)
+        }
+    }
+
+    pub fn is_asserted(&self) -> bool {
+        self.sources != 0
+    }
+
+    pub fn is_asserted_by(&self, source: u8) -> bool {
+        self.sources & source != 0
+    }
+}
+
 pub trait Interconnect {
     fn read(&self, address: u16) -> u8;
     fn write(&mut self, address: u16, value: u8);
     fn tick(&mut self) -> Interrupt;
     fn elapsed_cycles(&self) -> usize;
+
+    /// Whether the IRQ line is currently asserted. The CPU samples this at the end of every cycle.
+    fn irq_asserted(&self) -> bool {
+        false
+    }
+
+    /// Returns the value a read of `address` would, without any of the side effects of a read.
+    /// Debuggers and other tooling use this to inspect memory. Implementations whose reads have
+    /// side effects must override it.
+    fn peek(&self, address: u16) -> u8 {
+        self.read(address)
+    }
+
+    /// Whether the RDY line is pulled low, as a DMA unit does to take over the bus. The CPU halts
+    /// on its next read cycle, since RDY is ignored during writes, and calls `halted_cycle` for
+    /// every cycle until the line is released.
+    fn halt_requested(&self) -> bool {
+        false
+    }
+
+    /// Runs one cycle while the CPU is halted, before the CPU ticks. The CPU keeps driving the
+    /// address of the read it was halted on, so that read is repeated on any cycle where nothing
+    /// else takes over the bus.
+    fn halted_cycle(&mut self, address: u16) {
+        self.read(address);
+    }
+
+    /// Called by `Cpu::power_on` before the reset sequence, to put the rest of the system into its
+    /// power-up state
+    fn power_on(&mut self) {}
+
+    /// Called by `Cpu::soft_reset` before the reset sequence, for devices that respond to the reset
+    /// line
+    fn soft_reset(&mut self) {}
+}
+
+/// The behavioral differences between members of the 6502 family
+pub trait Variant {
+    /// Whether `ADC` and `SBC` honor the decimal flag
+    const DECIMAL_MODE: bool;
+}
+
+/// The 6502 core found in the NES 2A03/2A07, which has decimal mode disconnected
+pub struct Ricoh2A03;
+
+impl Variant for Ricoh2A03 {
+    const DECIMAL_MODE: bool = false;
 }
 
-pub struct Cpu<I: Interconnect> {
+/// The original NMOS 6502, including its undocumented decimal mode flag behavior
+pub struct Nmos6502;
+
+impl Variant for Nmos6502 {
+    const DECIMAL_MODE: bool = true;
+}
+
+pub struct Cpu<I: Interconnect, V: Variant = Ricoh2A03> {
     registers: Registers,
     pub interconnect: I,
     pending_interrupt: Interrupt,
+    irq_poll: bool,
+    irq_pending: bool,
+    jammed: bool,
+    call_stack: CallStack,
+    variant: PhantomData<V>,
 }
 
 impl<I: Interconnect> Cpu<I> {
     pub fn new(interconnect: I, pc: u16) -> Self {
+        Cpu::with_variant(interconnect, pc, Ricoh2A03)
+    }
+}
+
+impl<I: Interconnect, V: Variant> Cpu<I, V> {
+    pub fn with_variant(interconnect: I, pc: u16, _: V) -> Self {
         let mut cpu = Cpu {
             registers: Registers::new(),
             interconnect,
             pending_interrupt: Interrupt::None,
+            irq_poll: false,
+            irq_pending: false,
+            jammed: false,
+            call_stack: CallStack::default(),
+            variant: PhantomData,
         };
         cpu.registers.pc = pc;
         cpu
     }
 
     pub fn step(&mut self) -> Interrupt {
+        if self.jammed {
+            // A jammed CPU keeps the clock running but never fetches another instruction or
+            // services an interrupt. Interrupts are still reported so callers can keep time.
+            self.tick();
+            let pending_interrupt = self.pending_interrupt;
+            self.pending_interrupt = Interrupt::None;
+            return pending_interrupt;
+        }
+
+        let pc = self.registers.pc;
+        let sp = self.registers.sp;
+        let cycle = self.interconnect.elapsed_cycles();
         let opcode = self.read_pc();
         self::opcodes::execute(self, opcode);
+        call_stack::track_instruction(self, opcode, pc, sp, cycle);
+
+        // NMI takes priority. A level-triggered IRQ that loses out remains asserted and is polled
+        // again after the NMI handler returns.
+        let pending_interrupt = if self.pending_interrupt == Interrupt::Nmi {
+            Interrupt::Nmi
+        } else if self.irq_pending {
+            Interrupt::Irq
+        } else {
+            Interrupt::None
+        };
 
-        let pending_interrupt = self.pending_interrupt;
+        let pc = self.registers.pc;
+        let sp = self.registers.sp;
+        let cycle = self.interconnect.elapsed_cycles();
         match pending_interrupt {
             Interrupt::None => (),
             Interrupt::Nmi => {
                 self.pending_interrupt = Interrupt::None;
                 self.nmi();
+                call_stack::track_interrupt(self, FrameKind::Nmi, pc, sp, cycle);
             }
             Interrupt::Irq => {
-                self.pending_interrupt = Interrupt::None;
                 self.irq();
+                call_stack::track_interrupt(self, FrameKind::Irq, pc, sp, cycle);
             }
         }
         pending_interrupt
     }
 
-    pub fn reset(&mut self) {
-        let pc_low = self.read_memory(RESET_VECTOR);
-        let pc_high = self.read_memory(RESET_VECTOR + 1);
-        self.registers.pc = from_lo_hi(pc_low, pc_high);
+    /// Like `step`, but reports a fault instead of quietly idling once the CPU can no longer make
+    /// progress
+    ///
+    /// A jammed CPU still keeps the clock running for each call, so the rest of the system
+    /// continues to tick.
+    pub fn try_step(&mut self) -> Result<Interrupt, Fault> {
+        let interrupt = self.step();
+        match self.fault() {
+            Some(fault) => Err(fault),
+            None => Ok(interrupt),
+        }
     }
 
-    fn nmi(&mut self) {
-        self.push_pc_and_status();
-        let pc = self.read_memory16(NMI_VECTOR);
+    /// The fault that has stopped the CPU, if any
+    pub fn fault(&self) -> Option<Fault> {
+        if self.jammed {
+            // A jammed CPU holds its program counter on the opcode that jammed it
+            let pc = self.registers.pc;
+            Some(Fault::Jammed {
+                pc,
+                opcode: self.interconnect.peek(pc),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Powers on the CPU and everything connected to it, then runs the reset sequence
+    ///
+    /// The registers are cleared, and the reset sequence leaves the stack pointer at `0xfd` with
+    /// interrupts disabled.
+    pub fn power_on(&mut self) {
+        self.registers = Registers::new();
+        self.registers.sp = 0;
+        self.pending_interrupt = Interrupt::None;
+        self.irq_poll = false;
+        self.irq_pending = false;
+        self.interconnect.power_on();
+        self.reset_sequence();
+    }
+
+    /// Asserts the reset line, as pressing the reset button does
+    ///
+    /// Memory and the accumulator and index registers keep their contents. The stack pointer is
+    /// decremented by three and interrupts are disabled.
+    pub fn soft_reset(&mut self) {
+        self.interconnect.soft_reset();
+        self.reset_sequence();
+    }
+
+    fn reset_sequence(&mut self) {
+        self.jammed = false;
+        call_stack::clear(self);
+        self.pending_interrupt = Interrupt::None;
+
+        // Reset is handled like an interrupt whose pushes are turned into reads, so the stack
+        // pointer is decremented without anything being written
+        let pc = self.registers.pc;
+        self.dummy_read(pc);
+        self.dummy_read(pc);
+        for _ in 0..3 {
+            self.dummy_read_stack();
+            self.registers.sp = wrapping_dec(self.registers.sp);
+        }
+
         self.registers.set_interrupt_disable_flag(true);
+        let pc = self.read_memory16(RESET_VECTOR);
         self.registers.pc = pc;
     }
 
+    fn nmi(&mut self) {
+        self.interrupt(NMI_VECTOR)
+    }
+
     fn irq(&mut self) {
-        if !self.registers.interrupt_disable_flag() {
-            self.push_pc_and_status();
-            let pc = self.read_memory16(BREAK_VECTOR);
-            self.registers.set_interrupt_disable_flag(true);
-            self.registers.pc = pc;
-        }
+        self.interrupt(BREAK_VECTOR)
+    }
+
+    fn interrupt(&mut self, vector: u16) {
+        // Two dummy reads of the next opcode while the interrupt is being recognized
+        let pc = self.registers.pc;
+        self.dummy_read(pc);
+        self.dummy_read(pc);
+
+        self.push_pc_and_status();
+        self.registers.set_interrupt_disable_flag(true);
+        let pc = self.read_memory16(vector);
+        self.registers.pc = pc;
     }
 
     fn push_pc_and_status(&mut self) {
@@ -105,14 +343,33 @@ impl<I: Interconnect> Cpu<I> {
     }
 
     fn read_memory(&mut self, addr: u16) -> u8 {
+        while self.interconnect.halt_requested() {
+            self.interconnect.halted_cycle(addr);
+            self.halted_tick();
+        }
+
         let val = self.interconnect.read(addr);
         self.tick();
         val
     }
 
+    /// A read whose value is discarded
+    ///
+    /// The 6502 accesses the bus on every cycle, even those where it is only busy internally. The
+    /// value is ignored, but the read still has any side effects the address has.
+    fn dummy_read(&mut self, addr: u16) {
+        self.read_memory(addr);
+    }
+
+    /// A dummy read of the top of the stack, made while the stack pointer is being adjusted
+    fn dummy_read_stack(&mut self) {
+        let sp = u16::from(self.registers.sp);
+        self.dummy_read(STACK_LOC + sp);
+    }
+
     fn read_memory16(&mut self, addr: u16) -> u16 {
         let low_byte = self.read_memory(addr);
-        let high_byte = self.read_memory(addr + 1);
+        let high_byte = self.read_memory(addr.wrapping_add(1));
         from_lo_hi(low_byte, high_byte)
     }
 
@@ -131,12 +388,28 @@ impl<I: Interconnect> Cpu<I> {
         if self.interconnect.tick() == Interrupt::Nmi {
             self.pending_interrupt = Interrupt::Nmi;
         }
+
+        // The 6502 polls for interrupts at the end of the second to last cycle of an instruction,
+        // so whether an IRQ is taken is decided by the poll from the cycle before the current one.
+        // Instructions that change the interrupt disable flag on their last cycle (CLI, SEI, PLP)
+        // therefore don't take effect until after the following instruction.
+        self.irq_pending = self.irq_poll;
+        self.irq_poll =
+            self.interconnect.irq_asserted() && !self.registers.interrupt_disable_flag();
+    }
+
+    // The CPU's state is frozen while it's halted, so interrupts aren't polled. An NMI edge is
+    // still latched and is handled once the CPU resumes.
+    fn halted_tick(&mut self) {
+        if self.interconnect.tick() == Interrupt::Nmi {
+            self.pending_interrupt = Interrupt::Nmi;
+        }
     }
 
     fn read_pc(&mut self) -> u8 {
         let pc = self.registers.pc;
         let operand = self.read_memory(pc);
-        self.registers.pc += 1;
+        self.registers.pc = pc.wrapping_add(1);
         operand
     }
 
@@ -1,29 +1,26 @@
-use crate::cpu::{
-    opcodes::AddressingMode,
-    test_fixture::{TestCpu, TestInterconnect},
-};
+use crate::cpu::{opcodes::AddressingMode, test_fixture::TestInterconnect, Cpu, Variant};
 use std::{cell::Cell, rc::Rc};
 
-impl AddressingMode<TestInterconnect> for u8 {
+impl<V: Variant> AddressingMode<TestInterconnect, V> for u8 {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         *self
     }
 
-    fn write(&self, cpu: &mut TestCpu, value: u8) {
+    fn write(&self, cpu: &mut Cpu<TestInterconnect, V>, value: u8) {
         cpu.registers.acc = value;
     }
 }
 
-impl AddressingMode<TestInterconnect> for i8 {
+impl<V: Variant> AddressingMode<TestInterconnect, V> for i8 {
     type Output = i8;
 
     fn read(&self) -> Self::Output {
         *self
     }
 
-    fn write(&self, _: &mut TestCpu, _: u8) {
+    fn write(&self, _: &mut Cpu<TestInterconnect, V>, _: u8) {
         unimplemented!()
     }
 }
@@ -53,26 +50,26 @@ impl WriterAddressingMode {
     }
 }
 
-impl AddressingMode<TestInterconnect> for WriterAddressingMode {
+impl<V: Variant> AddressingMode<TestInterconnect, V> for WriterAddressingMode {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.read_value
     }
 
-    fn write(&self, _: &mut TestCpu, value: u8) {
+    fn write(&self, _: &mut Cpu<TestInterconnect, V>, value: u8) {
         self.written.set(value);
     }
 }
 
-impl AddressingMode<TestInterconnect> for u16 {
+impl<V: Variant> AddressingMode<TestInterconnect, V> for u16 {
     type Output = Self;
 
     fn read(&self) -> Self::Output {
         *self
     }
 
-    fn write(&self, _: &mut TestCpu, _: u8) {
+    fn write(&self, _: &mut Cpu<TestInterconnect, V>, _: u8) {
         unimplemented!()
     }
 }
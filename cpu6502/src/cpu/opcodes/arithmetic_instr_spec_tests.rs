@@ -1,4 +1,7 @@
-use crate::cpu::{opcodes::*, test_fixture::TestCpu};
+use crate::cpu::{
+    opcodes::*,
+    test_fixture::{NmosTestCpu, TestCpu},
+};
 
 /// ## Sign and zero flag tests
 ///
@@ -257,3 +260,157 @@ fn sbc_flags_carry_and_overflow_8() {
     assert_eq!(false, cpu.registers.overflow_flag());
     assert_eq!(160, cpu.registers.acc);
 }
+
+/// ## Decimal mode tests
+///
+/// The 2A03 ignores the decimal flag entirely, while the NMOS 6502 performs BCD arithmetic with
+/// its own peculiar flag behavior. Examples are taken from
+/// http://www.6502.org/tutorials/decimal_mode.html
+#[test]
+fn adc_decimal_flag_ignored_by_2a03() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x09_u8);
+    Adc::execute(&mut cpu, 0x01_u8);
+    assert_eq!(0x0a, cpu.registers.acc);
+}
+
+#[test]
+fn sbc_decimal_flag_ignored_by_2a03() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.set_decimal_flag(true);
+    cpu.registers.set_carry_flag(true);
+    Lda::execute(&mut cpu, 0x10_u8);
+    Sbc::execute(&mut cpu, 0x01_u8);
+    assert_eq!(0x0f, cpu.registers.acc);
+}
+
+#[test]
+fn adc_decimal_1() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x58_u8);
+    cpu.registers.set_carry_flag(true);
+    Adc::execute(&mut cpu, 0x46_u8);
+    assert_eq!(0x05, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+}
+
+#[test]
+fn adc_decimal_2() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x12_u8);
+    Adc::execute(&mut cpu, 0x34_u8);
+    assert_eq!(0x46, cpu.registers.acc);
+    assert_eq!(false, cpu.registers.carry_flag());
+}
+
+#[test]
+fn adc_decimal_nmos_flags() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x99_u8);
+    Adc::execute(&mut cpu, 0x01_u8);
+    assert_eq!(0x00, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+
+    // Zero reflects the binary result (0x9a), sign reflects the partially adjusted result (0xa0)
+    assert_eq!(false, cpu.registers.zero_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+}
+
+#[test]
+fn adc_decimal_nmos_overflow() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x79_u8);
+    Adc::execute(&mut cpu, 0x10_u8);
+    assert_eq!(0x89, cpu.registers.acc);
+    assert_eq!(false, cpu.registers.carry_flag());
+    assert_eq!(true, cpu.registers.overflow_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+}
+
+#[test]
+fn sbc_decimal_1() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x46_u8);
+    cpu.registers.set_carry_flag(true);
+    Sbc::execute(&mut cpu, 0x12_u8);
+    assert_eq!(0x34, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+}
+
+#[test]
+fn sbc_decimal_2() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x32_u8);
+    cpu.registers.set_carry_flag(false);
+    Sbc::execute(&mut cpu, 0x02_u8);
+    assert_eq!(0x29, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+}
+
+#[test]
+fn sbc_decimal_3() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x21_u8);
+    cpu.registers.set_carry_flag(true);
+    Sbc::execute(&mut cpu, 0x34_u8);
+    assert_eq!(0x87, cpu.registers.acc);
+    assert_eq!(false, cpu.registers.carry_flag());
+}
+
+#[test]
+fn sbc_decimal_nmos_flags() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+    Lda::execute(&mut cpu, 0x00_u8);
+    cpu.registers.set_carry_flag(true);
+    Sbc::execute(&mut cpu, 0x01_u8);
+    assert_eq!(0x99, cpu.registers.acc);
+
+    // All flags reflect the binary result (0xff)
+    assert_eq!(false, cpu.registers.carry_flag());
+    assert_eq!(false, cpu.registers.zero_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+    assert_eq!(false, cpu.registers.overflow_flag());
+}
+
+/// Mirrors the decimal mode section of the Klaus functional test, which checks the result and
+/// carry of every valid BCD operand pair with and without carry in.
+#[test]
+fn adc_and_sbc_decimal_all_valid_operands() {
+    fn to_bcd(val: u32) -> u8 {
+        (((val / 10) << 4) | (val % 10)) as u8
+    }
+
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+
+    for lhs in 0..100 {
+        for rhs in 0..100 {
+            for &carry in &[false, true] {
+                let carry_in = if carry { 1 } else { 0 };
+
+                cpu.registers.acc = to_bcd(lhs);
+                cpu.registers.set_carry_flag(carry);
+                Adc::execute(&mut cpu, to_bcd(rhs));
+                let sum = lhs + rhs + carry_in;
+                assert_eq!(to_bcd(sum % 100), cpu.registers.acc);
+                assert_eq!(sum >= 100, cpu.registers.carry_flag());
+
+                cpu.registers.acc = to_bcd(lhs);
+                cpu.registers.set_carry_flag(carry);
+                Sbc::execute(&mut cpu, to_bcd(rhs));
+                let difference = 100 + lhs + carry_in - rhs - 1;
+                assert_eq!(to_bcd(difference % 100), cpu.registers.acc);
+                assert_eq!(difference >= 100, cpu.registers.carry_flag());
+            }
+        }
+    }
+}
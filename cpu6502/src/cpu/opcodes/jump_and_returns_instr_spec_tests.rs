@@ -13,12 +13,13 @@ fn jmp() {
 fn jsr() {
     let mut cpu = TestCpu::new_test();
     cpu.registers.sp = 0xff;
-    cpu.registers.pc = 0x102;
-    Jsr::execute(&mut cpu, 0xbeef_u16);
+    cpu.registers.pc = 0x201;
+    cpu.interconnect.store_many(0x201, &[0xef, 0xbe]);
+    Jsr::execute(&mut cpu, JsrAbsolute);
     assert_eq!(0xfd, cpu.registers.sp);
     let pushed_pc = cpu.pop_stack16();
     assert_eq!(0xbeef, cpu.registers.pc);
-    assert_eq!(0x101, pushed_pc);
+    assert_eq!(0x202, pushed_pc);
 }
 
 #[test]
@@ -10,6 +10,30 @@ fn lda_value_set() {
     assert_eq!(0xff, cpu.registers.acc);
 }
 
+#[test]
+fn lda_indexed_indirect_pointer_wraps_within_zero_page() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.x = 0x01;
+    cpu.interconnect.store_many(0x200, &[0xa1, 0xfe]);
+    cpu.interconnect.store_many(0x00ff, &[0x00]);
+    cpu.interconnect.store_many(0x0000, &[0x04]);
+    cpu.interconnect.store_many(0x0100, &[0x05]);
+    cpu.interconnect.store_many(0x0400, &[0x5d]);
+    cpu.step();
+    assert_eq!(0x5d, cpu.registers.acc);
+}
+
+#[test]
+fn lda_indirect_indexed_wraps_around_address_space() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.y = 0x02;
+    cpu.interconnect.store_many(0x200, &[0xb1, 0x10]);
+    cpu.interconnect.store_many(0x0010, &[0xff, 0xff]);
+    cpu.interconnect.store_many(0x0001, &[0x5d]);
+    cpu.step();
+    assert_eq!(0x5d, cpu.registers.acc);
+}
+
 #[test]
 fn lda_flags_sign_and_zero_1() {
     let mut cpu = TestCpu::new_test();
@@ -1,587 +1,404 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 pub mod am_test_utils;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod arithmetic_instr_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod bitwise_and_shift_instr_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod branch_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod compare_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod reg_transfer_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod flag_instr_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod loads_and_stores_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod inc_dec_spec_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod jump_and_returns_instr_spec_tests;
 
+#[cfg(all(test, feature = "std"))]
+mod unofficial_instr_spec_tests;
+
 use crate::{
     byte_utils::*,
-    cpu::{Cpu, Interconnect},
+    cpu::{
+        disassembler::{AddressingMode as Mode, Mnemonic},
+        Cpu, Interconnect, Variant as CpuVariant,
+    },
 };
+use core::marker::PhantomData;
 
 const BRK_VECTOR: u16 = 0xfffe;
 
 trait OpCode {
     type Input;
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     );
 }
 
-pub fn execute<I: Interconnect>(cpu: &mut Cpu<I>, opcode: u8) {
-    match opcode {
-        0xe8 => Inx::execute(cpu, Implied),
-        0xca => Dex::execute(cpu, Implied),
-        0xc8 => Iny::execute(cpu, Implied),
-        0x88 => Dey::execute(cpu, Implied),
-        0xaa => Tax::execute(cpu, Implied),
-        0xa8 => Tay::execute(cpu, Implied),
-        0x8a => Txa::execute(cpu, Implied),
-        0x98 => Tya::execute(cpu, Implied),
-        0x9a => Txs::execute(cpu, Implied),
-        0xba => Tsx::execute(cpu, Implied),
-        0x18 => Clc::execute(cpu, Implied),
-        0x38 => Sec::execute(cpu, Implied),
-        0x58 => Cli::execute(cpu, Implied),
-        0x78 => Sei::execute(cpu, Implied),
-        0xb8 => Clv::execute(cpu, Implied),
-        0xd8 => Cld::execute(cpu, Implied),
-        0xf8 => Sed::execute(cpu, Implied),
-        0x60 => Rts::execute(cpu, Implied),
-        0x00 => Brk::execute(cpu, Implied),
-        0x40 => Rti::execute(cpu, Implied),
-        0x48 => Pha::execute(cpu, Implied),
-        0x68 => Pla::execute(cpu, Implied),
-        0x08 => Php::execute(cpu, Implied),
-        0x28 => Plp::execute(cpu, Implied),
-        0xea => Nop::execute(cpu, Implied),
-        0x10 => {
-            let am = Relative::init(cpu);
-            Bpl::execute(cpu, am)
-        }
-        0x30 => {
-            let am = Relative::init(cpu);
-            Bmi::execute(cpu, am)
-        }
-        0x50 => {
-            let am = Relative::init(cpu);
-            Bvc::execute(cpu, am)
-        }
-        0x70 => {
-            let am = Relative::init(cpu);
-            Bvs::execute(cpu, am)
-        }
-        0x90 => {
-            let am = Relative::init(cpu);
-            Bcc::execute(cpu, am)
-        }
-        0xb0 => {
-            let am = Relative::init(cpu);
-            Bcs::execute(cpu, am)
-        }
-        0xd0 => {
-            let am = Relative::init(cpu);
-            Bne::execute(cpu, am)
-        }
-        0xf0 => {
-            let am = Relative::init(cpu);
-            Beq::execute(cpu, am)
-        }
-        0xa1 => {
-            let am = IndexedIndirect::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xa5 => {
-            let am = ZeroPage::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xa9 => {
-            let am = Immediate::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xb1 => {
-            let am = IndirectIndexed::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xb5 => {
-            let am = ZeroPageX::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xad => {
-            let am = Absolute::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xb9 => {
-            let am = AbsoluteY::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xbd => {
-            let am = AbsoluteX::init(cpu);
-            Lda::execute(cpu, am)
-        }
-        0xa2 => {
-            let am = Immediate::init(cpu);
-            Ldx::execute(cpu, am)
-        }
-        0xa6 => {
-            let am = ZeroPage::init(cpu);
-            Ldx::execute(cpu, am)
-        }
-        0xb6 => {
-            let am = ZeroPageY::init(cpu);
-            Ldx::execute(cpu, am)
-        }
-        0xae => {
-            let am = Absolute::init(cpu);
-            Ldx::execute(cpu, am)
-        }
-        0xbe => {
-            let am = AbsoluteY::init(cpu);
-            Ldx::execute(cpu, am)
-        }
-        0xa0 => {
-            let am = Immediate::init(cpu);
-            Ldy::execute(cpu, am)
-        }
-        0xa4 => {
-            let am = ZeroPage::init(cpu);
-            Ldy::execute(cpu, am)
-        }
-        0xb4 => {
-            let am = ZeroPageX::init(cpu);
-            Ldy::execute(cpu, am)
-        }
-        0xac => {
-            let am = Absolute::init(cpu);
-            Ldy::execute(cpu, am)
-        }
-        0xbc => {
-            let am = AbsoluteX::init(cpu);
-            Ldy::execute(cpu, am)
-        }
-        0x85 => {
-            let am = ZeroPage::init_store(cpu);
-            Sta::execute(cpu, am)
-        }
-        0x95 => {
-            let am = ZeroPageX::init_store(cpu);
-            Sta::execute(cpu, am)
-        }
-        0x81 => {
-            let am = IndexedIndirect::init_store(cpu);
-            Sta::execute(cpu, am)
-        }
-        0x91 => {
-            let am = IndirectIndexed::init_store(cpu);
-            Sta::execute(cpu, am)
-        }
-        0x8d => {
-            let am = Absolute::init_store(cpu);
-            Sta::execute(cpu, am)
-        }
-        0x9d => {
-            let am = AbsoluteX::init_store(cpu);
-            Sta::execute(cpu, am)
-        }
-        0x99 => {
-            let am = AbsoluteY::init_store(cpu);
-            Sta::execute(cpu, am)
-        }
-        0x86 => {
-            let am = ZeroPage::init_store(cpu);
-            Stx::execute(cpu, am)
-        }
-        0x96 => {
-            let am = ZeroPageY::init_store(cpu);
-            Stx::execute(cpu, am)
-        }
-        0x8e => {
-            let am = Absolute::init_store(cpu);
-            Stx::execute(cpu, am)
-        }
-        0x84 => {
-            let am = ZeroPage::init_store(cpu);
-            Sty::execute(cpu, am)
-        }
-        0x94 => {
-            let am = ZeroPageX::init_store(cpu);
-            Sty::execute(cpu, am)
-        }
-        0x8c => {
-            let am = Absolute::init_store(cpu);
-            Sty::execute(cpu, am)
-        }
-        0x69 => {
-            let am = Immediate::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0x65 => {
-            let am = ZeroPage::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0x75 => {
-            let am = ZeroPageX::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0x61 => {
-            let am = IndexedIndirect::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0x71 => {
-            let am = IndirectIndexed::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0x6d => {
-            let am = Absolute::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0x7d => {
-            let am = AbsoluteX::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0x79 => {
-            let am = AbsoluteY::init(cpu);
-            Adc::execute(cpu, am)
-        }
-        0xe9 => {
-            let am = Immediate::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xe5 => {
-            let am = ZeroPage::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xf5 => {
-            let am = ZeroPageX::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xe1 => {
-            let am = IndexedIndirect::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xf1 => {
-            let am = IndirectIndexed::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xed => {
-            let am = Absolute::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xfd => {
-            let am = AbsoluteX::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xf9 => {
-            let am = AbsoluteY::init(cpu);
-            Sbc::execute(cpu, am)
-        }
-        0xc9 => {
-            let am = Immediate::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xc5 => {
-            let am = ZeroPage::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xd5 => {
-            let am = ZeroPageX::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xc1 => {
-            let am = IndexedIndirect::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xd1 => {
-            let am = IndirectIndexed::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xcd => {
-            let am = Absolute::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xdd => {
-            let am = AbsoluteX::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xd9 => {
-            let am = AbsoluteY::init(cpu);
-            Cmp::execute(cpu, am)
-        }
-        0xe0 => {
-            let am = Immediate::init(cpu);
-            Cpx::execute(cpu, am)
-        }
-        0xe4 => {
-            let am = ZeroPage::init(cpu);
-            Cpx::execute(cpu, am)
-        }
-        0xec => {
-            let am = Absolute::init(cpu);
-            Cpx::execute(cpu, am)
-        }
-        0xc0 => {
-            let am = Immediate::init(cpu);
-            Cpy::execute(cpu, am)
-        }
-        0xc4 => {
-            let am = ZeroPage::init(cpu);
-            Cpy::execute(cpu, am)
-        }
-        0xcc => {
-            let am = Absolute::init(cpu);
-            Cpy::execute(cpu, am)
-        }
-        0x29 => {
-            let am = Immediate::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x25 => {
-            let am = ZeroPage::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x35 => {
-            let am = ZeroPageX::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x21 => {
-            let am = IndexedIndirect::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x31 => {
-            let am = IndirectIndexed::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x2d => {
-            let am = Absolute::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x3d => {
-            let am = AbsoluteX::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x39 => {
-            let am = AbsoluteY::init(cpu);
-            And::execute(cpu, am)
-        }
-        0x09 => {
-            let am = Immediate::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x05 => {
-            let am = ZeroPage::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x15 => {
-            let am = ZeroPageX::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x01 => {
-            let am = IndexedIndirect::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x11 => {
-            let am = IndirectIndexed::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x0d => {
-            let am = Absolute::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x1d => {
-            let am = AbsoluteX::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x19 => {
-            let am = AbsoluteY::init(cpu);
-            Ora::execute(cpu, am)
-        }
-        0x49 => {
-            let am = Immediate::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x45 => {
-            let am = ZeroPage::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x55 => {
-            let am = ZeroPageX::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x41 => {
-            let am = IndexedIndirect::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x51 => {
-            let am = IndirectIndexed::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x4d => {
-            let am = Absolute::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x5d => {
-            let am = AbsoluteX::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x59 => {
-            let am = AbsoluteY::init(cpu);
-            Eor::execute(cpu, am)
-        }
-        0x24 => {
-            let am = ZeroPage::init(cpu);
-            Bit::execute(cpu, am)
-        }
-        0x2c => {
-            let am = Absolute::init(cpu);
-            Bit::execute(cpu, am)
-        }
-        0x2a => {
-            let am = Accumulator::init(cpu);
-            Rol::execute(cpu, am)
-        }
-        0x26 => {
-            let am = ZeroPage::init(cpu);
-            Rol::execute(cpu, am)
-        }
-        0x36 => {
-            let am = ZeroPageX::init(cpu);
-            Rol::execute(cpu, am)
-        }
-        0x2e => {
-            let am = Absolute::init(cpu);
-            Rol::execute(cpu, am)
-        }
-        0x3e => {
-            let am = AbsoluteX::init_rmw(cpu);
-            Rol::execute(cpu, am)
-        }
-        0x6a => {
-            let am = Accumulator::init(cpu);
-            Ror::execute(cpu, am)
-        }
-        0x66 => {
-            let am = ZeroPage::init(cpu);
-            Ror::execute(cpu, am)
-        }
-        0x76 => {
-            let am = ZeroPageX::init(cpu);
-            Ror::execute(cpu, am)
-        }
-        0x6e => {
-            let am = Absolute::init(cpu);
-            Ror::execute(cpu, am)
-        }
-        0x7e => {
-            let am = AbsoluteX::init_rmw(cpu);
-            Ror::execute(cpu, am)
-        }
-        0x0a => {
-            let am = Accumulator::init(cpu);
-            Asl::execute(cpu, am)
-        }
-        0x06 => {
-            let am = ZeroPage::init(cpu);
-            Asl::execute(cpu, am)
-        }
-        0x16 => {
-            let am = ZeroPageX::init(cpu);
-            Asl::execute(cpu, am)
-        }
-        0x0e => {
-            let am = Absolute::init(cpu);
-            Asl::execute(cpu, am)
-        }
-        0x1e => {
-            let am = AbsoluteX::init_rmw(cpu);
-            Asl::execute(cpu, am)
-        }
-        0x4a => {
-            let am = Accumulator::init(cpu);
-            Lsr::execute(cpu, am)
-        }
-        0x46 => {
-            let am = ZeroPage::init(cpu);
-            Lsr::execute(cpu, am)
-        }
-        0x56 => {
-            let am = ZeroPageX::init(cpu);
-            Lsr::execute(cpu, am)
-        }
-        0x4e => {
-            let am = Absolute::init(cpu);
-            Lsr::execute(cpu, am)
-        }
-        0x5e => {
-            let am = AbsoluteX::init_rmw(cpu);
-            Lsr::execute(cpu, am)
-        }
-        0xe6 => {
-            let am = ZeroPage::init(cpu);
-            Inc::execute(cpu, am)
-        }
-        0xf6 => {
-            let am = ZeroPageX::init(cpu);
-            Inc::execute(cpu, am)
-        }
-        0xee => {
-            let am = Absolute::init(cpu);
-            Inc::execute(cpu, am)
-        }
-        0xfe => {
-            let am = AbsoluteX::init_rmw(cpu);
-            Inc::execute(cpu, am)
-        }
-        0xc6 => {
-            let am = ZeroPage::init(cpu);
-            Dec::execute(cpu, am)
-        }
-        0xd6 => {
-            let am = ZeroPageX::init(cpu);
-            Dec::execute(cpu, am)
-        }
-        0xce => {
-            let am = Absolute::init(cpu);
-            Dec::execute(cpu, am)
-        }
-        0xde => {
-            let am = AbsoluteX::init_rmw(cpu);
-            Dec::execute(cpu, am)
-        }
-        0x4c => {
-            let am = AbsoluteAddress::init(cpu);
-            Jmp::execute(cpu, am)
-        }
-        0x6c => {
-            let am = Indirect::init(cpu);
-            Jmp::execute(cpu, am)
-        }
-        0x20 => {
-            let am = AbsoluteAddress::init(cpu);
-            Jsr::execute(cpu, am)
-        }
-        _ => panic!("Unexpected opcode: {:0>2X}", opcode),
-    }
-}
-
-pub trait AddressingMode<I: Interconnect> {
+/// Static information about an opcode, shared by the executor and the disassembler
+#[derive(Copy, Clone)]
+pub struct Metadata {
+    pub mnemonic: Mnemonic,
+    pub mode: Mode,
+    /// Cycle count when no page boundary is crossed and no branch is taken
+    pub cycles: u8,
+    pub official: bool,
+}
+
+type Handler<I, V> = fn(&mut Cpu<I, V>);
+
+/// The handlers of every opcode, indexed by opcode
+///
+/// Each handler is monomorphized for its instruction and addressing mode, so executing an opcode
+/// is a single indirect call rather than a search through the opcode table.
+struct Dispatch<I, V>(PhantomData<(I, V)>);
+
+/// Generates `execute`, its dispatch table and `metadata` from a single opcode table so they can't
+/// disagree.
+///
+/// Each entry is `opcodes => Instruction [as Mnemonic], AddressingMode[::constructor], cycles;`.
+/// The mnemonic only needs to be given when it differs from the instruction's name.
+macro_rules! opcodes {
+    (
+        official {
+            $(
+                $($official:literal)|+ =>
+                $o_instr:ident $(as $o_mnemonic:ident)?,
+                $o_am:ident $(:: $o_init:ident)?,
+                $o_cycles:expr;
+            )*
+        }
+
+        unofficial {
+            $(
+                $($unofficial:literal)|+ =>
+                $u_instr:ident $(as $u_mnemonic:ident)?,
+                $u_am:ident $(:: $u_init:ident)?,
+                $u_cycles:expr;
+            )*
+        }
+    ) => {
+        impl<I: Interconnect, V: CpuVariant> Dispatch<I, V> {
+            // Every opcode is assigned below, which `metadata` checks by matching exhaustively on
+            // the same opcodes
+            const TABLE: [Handler<I, V>; 256] = {
+                let mut table: [Handler<I, V>; 256] = [|_| unreachable!(); 256];
+                $(
+                    let handler: Handler<I, V> = |cpu| {
+                        let am = opcodes!(@am cpu, $o_am $(:: $o_init)?);
+                        $o_instr::execute(cpu, am)
+                    };
+                    $(table[$official] = handler;)+
+                )*
+                $(
+                    let handler: Handler<I, V> = |cpu| {
+                        let am = opcodes!(@am cpu, $u_am $(:: $u_init)?);
+                        $u_instr::execute(cpu, am)
+                    };
+                    $(table[$unofficial] = handler;)+
+                )*
+                table
+            };
+        }
+
+        pub fn execute<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, opcode: u8) {
+            Dispatch::<I, V>::TABLE[opcode as usize](cpu)
+        }
+
+        pub fn metadata(opcode: u8) -> Metadata {
+            match opcode {
+                $(
+                    $($official)|+ => Metadata {
+                        mnemonic: opcodes!(@mnemonic $o_instr $(as $o_mnemonic)?),
+                        mode: $o_am::MODE,
+                        cycles: $o_cycles,
+                        official: true,
+                    },
+                )*
+                $(
+                    $($unofficial)|+ => Metadata {
+                        mnemonic: opcodes!(@mnemonic $u_instr $(as $u_mnemonic)?),
+                        mode: $u_am::MODE,
+                        cycles: $u_cycles,
+                        official: false,
+                    },
+                )*
+            }
+        }
+    };
+    (@am $cpu:ident, $am:ident) => {
+        $am
+    };
+    (@am $cpu:ident, $am:ident :: $init:ident) => {
+        $am::$init($cpu)
+    };
+    (@mnemonic $instr:ident) => {
+        Mnemonic::$instr
+    };
+    (@mnemonic $instr:ident as $mnemonic:ident) => {
+        Mnemonic::$mnemonic
+    };
+}
+
+opcodes! {
+    official {
+        0xe8 => Inx, Implied, 2;
+        0xca => Dex, Implied, 2;
+        0xc8 => Iny, Implied, 2;
+        0x88 => Dey, Implied, 2;
+        0xaa => Tax, Implied, 2;
+        0xa8 => Tay, Implied, 2;
+        0x8a => Txa, Implied, 2;
+        0x98 => Tya, Implied, 2;
+        0x9a => Txs, Implied, 2;
+        0xba => Tsx, Implied, 2;
+        0x18 => Clc, Implied, 2;
+        0x38 => Sec, Implied, 2;
+        0x58 => Cli, Implied, 2;
+        0x78 => Sei, Implied, 2;
+        0xb8 => Clv, Implied, 2;
+        0xd8 => Cld, Implied, 2;
+        0xf8 => Sed, Implied, 2;
+        0x60 => Rts, Implied, 6;
+        0x00 => Brk, Implied, 7;
+        0x40 => Rti, Implied, 6;
+        0x48 => Pha, Implied, 3;
+        0x68 => Pla, Implied, 4;
+        0x08 => Php, Implied, 3;
+        0x28 => Plp, Implied, 4;
+        0xea => Nop, Implied, 2;
+        0x10 => Bpl, Relative::init, 2;
+        0x30 => Bmi, Relative::init, 2;
+        0x50 => Bvc, Relative::init, 2;
+        0x70 => Bvs, Relative::init, 2;
+        0x90 => Bcc, Relative::init, 2;
+        0xb0 => Bcs, Relative::init, 2;
+        0xd0 => Bne, Relative::init, 2;
+        0xf0 => Beq, Relative::init, 2;
+        0xa1 => Lda, IndexedIndirect::init, 6;
+        0xa5 => Lda, ZeroPage::init, 3;
+        0xa9 => Lda, Immediate::init, 2;
+        0xb1 => Lda, IndirectIndexed::init, 5;
+        0xb5 => Lda, ZeroPageX::init, 4;
+        0xad => Lda, Absolute::init, 4;
+        0xb9 => Lda, AbsoluteY::init, 4;
+        0xbd => Lda, AbsoluteX::init, 4;
+        0xa2 => Ldx, Immediate::init, 2;
+        0xa6 => Ldx, ZeroPage::init, 3;
+        0xb6 => Ldx, ZeroPageY::init, 4;
+        0xae => Ldx, Absolute::init, 4;
+        0xbe => Ldx, AbsoluteY::init, 4;
+        0xa0 => Ldy, Immediate::init, 2;
+        0xa4 => Ldy, ZeroPage::init, 3;
+        0xb4 => Ldy, ZeroPageX::init, 4;
+        0xac => Ldy, Absolute::init, 4;
+        0xbc => Ldy, AbsoluteX::init, 4;
+        0x85 => Sta, ZeroPage::init_store, 3;
+        0x95 => Sta, ZeroPageX::init_store, 4;
+        0x81 => Sta, IndexedIndirect::init_store, 6;
+        0x91 => Sta, IndirectIndexed::init_store, 6;
+        0x8d => Sta, Absolute::init_store, 4;
+        0x9d => Sta, AbsoluteX::init_store, 5;
+        0x99 => Sta, AbsoluteY::init_store, 5;
+        0x86 => Stx, ZeroPage::init_store, 3;
+        0x96 => Stx, ZeroPageY::init_store, 4;
+        0x8e => Stx, Absolute::init_store, 4;
+        0x84 => Sty, ZeroPage::init_store, 3;
+        0x94 => Sty, ZeroPageX::init_store, 4;
+        0x8c => Sty, Absolute::init_store, 4;
+        0x69 => Adc, Immediate::init, 2;
+        0x65 => Adc, ZeroPage::init, 3;
+        0x75 => Adc, ZeroPageX::init, 4;
+        0x61 => Adc, IndexedIndirect::init, 6;
+        0x71 => Adc, IndirectIndexed::init, 5;
+        0x6d => Adc, Absolute::init, 4;
+        0x7d => Adc, AbsoluteX::init, 4;
+        0x79 => Adc, AbsoluteY::init, 4;
+        0xe9 => Sbc, Immediate::init, 2;
+        0xe5 => Sbc, ZeroPage::init, 3;
+        0xf5 => Sbc, ZeroPageX::init, 4;
+        0xe1 => Sbc, IndexedIndirect::init, 6;
+        0xf1 => Sbc, IndirectIndexed::init, 5;
+        0xed => Sbc, Absolute::init, 4;
+        0xfd => Sbc, AbsoluteX::init, 4;
+        0xf9 => Sbc, AbsoluteY::init, 4;
+        0xc9 => Cmp, Immediate::init, 2;
+        0xc5 => Cmp, ZeroPage::init, 3;
+        0xd5 => Cmp, ZeroPageX::init, 4;
+        0xc1 => Cmp, IndexedIndirect::init, 6;
+        0xd1 => Cmp, IndirectIndexed::init, 5;
+        0xcd => Cmp, Absolute::init, 4;
+        0xdd => Cmp, AbsoluteX::init, 4;
+        0xd9 => Cmp, AbsoluteY::init, 4;
+        0xe0 => Cpx, Immediate::init, 2;
+        0xe4 => Cpx, ZeroPage::init, 3;
+        0xec => Cpx, Absolute::init, 4;
+        0xc0 => Cpy, Immediate::init, 2;
+        0xc4 => Cpy, ZeroPage::init, 3;
+        0xcc => Cpy, Absolute::init, 4;
+        0x29 => And, Immediate::init, 2;
+        0x25 => And, ZeroPage::init, 3;
+        0x35 => And, ZeroPageX::init, 4;
+        0x21 => And, IndexedIndirect::init, 6;
+        0x31 => And, IndirectIndexed::init, 5;
+        0x2d => And, Absolute::init, 4;
+        0x3d => And, AbsoluteX::init, 4;
+        0x39 => And, AbsoluteY::init, 4;
+        0x09 => Ora, Immediate::init, 2;
+        0x05 => Ora, ZeroPage::init, 3;
+        0x15 => Ora, ZeroPageX::init, 4;
+        0x01 => Ora, IndexedIndirect::init, 6;
+        0x11 => Ora, IndirectIndexed::init, 5;
+        0x0d => Ora, Absolute::init, 4;
+        0x1d => Ora, AbsoluteX::init, 4;
+        0x19 => Ora, AbsoluteY::init, 4;
+        0x49 => Eor, Immediate::init, 2;
+        0x45 => Eor, ZeroPage::init, 3;
+        0x55 => Eor, ZeroPageX::init, 4;
+        0x41 => Eor, IndexedIndirect::init, 6;
+        0x51 => Eor, IndirectIndexed::init, 5;
+        0x4d => Eor, Absolute::init, 4;
+        0x5d => Eor, AbsoluteX::init, 4;
+        0x59 => Eor, AbsoluteY::init, 4;
+        0x24 => Bit, ZeroPage::init, 3;
+        0x2c => Bit, Absolute::init, 4;
+        0x2a => Rol, Accumulator::init, 2;
+        0x26 => Rol, ZeroPage::init, 5;
+        0x36 => Rol, ZeroPageX::init, 6;
+        0x2e => Rol, Absolute::init, 6;
+        0x3e => Rol, AbsoluteX::init_rmw, 7;
+        0x6a => Ror, Accumulator::init, 2;
+        0x66 => Ror, ZeroPage::init, 5;
+        0x76 => Ror, ZeroPageX::init, 6;
+        0x6e => Ror, Absolute::init, 6;
+        0x7e => Ror, AbsoluteX::init_rmw, 7;
+        0x0a => Asl, Accumulator::init, 2;
+        0x06 => Asl, ZeroPage::init, 5;
+        0x16 => Asl, ZeroPageX::init, 6;
+        0x0e => Asl, Absolute::init, 6;
+        0x1e => Asl, AbsoluteX::init_rmw, 7;
+        0x4a => Lsr, Accumulator::init, 2;
+        0x46 => Lsr, ZeroPage::init, 5;
+        0x56 => Lsr, ZeroPageX::init, 6;
+        0x4e => Lsr, Absolute::init, 6;
+        0x5e => Lsr, AbsoluteX::init_rmw, 7;
+        0xe6 => Inc, ZeroPage::init, 5;
+        0xf6 => Inc, ZeroPageX::init, 6;
+        0xee => Inc, Absolute::init, 6;
+        0xfe => Inc, AbsoluteX::init_rmw, 7;
+        0xc6 => Dec, ZeroPage::init, 5;
+        0xd6 => Dec, ZeroPageX::init, 6;
+        0xce => Dec, Absolute::init, 6;
+        0xde => Dec, AbsoluteX::init_rmw, 7;
+        0x4c => Jmp, AbsoluteAddress::init, 3;
+        0x6c => Jmp, Indirect::init, 5;
+        0x20 => Jsr, JsrAbsolute, 6;
+    }
+
+    unofficial {
+        0x1a | 0x3a | 0x5a | 0x7a | 0xda | 0xfa => Nop, Implied, 2;
+        0x02 | 0x12 | 0x22 | 0x32 | 0x42 | 0x52
+            | 0x62 | 0x72 | 0x92 | 0xb2 | 0xd2 | 0xf2 => Kil, Implied, 2;
+        0x80 | 0x82 | 0x89 | 0xc2 | 0xe2 => Ign as Nop, Immediate::init, 2;
+        0x04 | 0x44 | 0x64 => Ign as Nop, ZeroPage::init, 3;
+        0x14 | 0x34 | 0x54 | 0x74 | 0xd4 | 0xf4 => Ign as Nop, ZeroPageX::init, 4;
+        0x0c => Ign as Nop, Absolute::init, 4;
+        0x1c | 0x3c | 0x5c | 0x7c | 0xdc | 0xfc => Ign as Nop, AbsoluteX::init, 4;
+        0xa7 => Lax, ZeroPage::init, 3;
+        0xb7 => Lax, ZeroPageY::init, 4;
+        0xaf => Lax, Absolute::init, 4;
+        0xbf => Lax, AbsoluteY::init, 4;
+        0xa3 => Lax, IndexedIndirect::init, 6;
+        0xb3 => Lax, IndirectIndexed::init, 5;
+        0xab => Lxa, Immediate::init, 2;
+        0x87 => Sax, ZeroPage::init_store, 3;
+        0x97 => Sax, ZeroPageY::init_store, 4;
+        0x8f => Sax, Absolute::init_store, 4;
+        0x83 => Sax, IndexedIndirect::init_store, 6;
+        0x07 => Slo, ZeroPage::init, 5;
+        0x17 => Slo, ZeroPageX::init, 6;
+        0x0f => Slo, Absolute::init, 6;
+        0x1f => Slo, AbsoluteX::init_rmw, 7;
+        0x1b => Slo, AbsoluteY::init_rmw, 7;
+        0x03 => Slo, IndexedIndirect::init, 8;
+        0x13 => Slo, IndirectIndexed::init_rmw, 8;
+        0x27 => Rla, ZeroPage::init, 5;
+        0x37 => Rla, ZeroPageX::init, 6;
+        0x2f => Rla, Absolute::init, 6;
+        0x3f => Rla, AbsoluteX::init_rmw, 7;
+        0x3b => Rla, AbsoluteY::init_rmw, 7;
+        0x23 => Rla, IndexedIndirect::init, 8;
+        0x33 => Rla, IndirectIndexed::init_rmw, 8;
+        0x47 => Sre, ZeroPage::init, 5;
+        0x57 => Sre, ZeroPageX::init, 6;
+        0x4f => Sre, Absolute::init, 6;
+        0x5f => Sre, AbsoluteX::init_rmw, 7;
+        0x5b => Sre, AbsoluteY::init_rmw, 7;
+        0x43 => Sre, IndexedIndirect::init, 8;
+        0x53 => Sre, IndirectIndexed::init_rmw, 8;
+        0x67 => Rra, ZeroPage::init, 5;
+        0x77 => Rra, ZeroPageX::init, 6;
+        0x6f => Rra, Absolute::init, 6;
+        0x7f => Rra, AbsoluteX::init_rmw, 7;
+        0x7b => Rra, AbsoluteY::init_rmw, 7;
+        0x63 => Rra, IndexedIndirect::init, 8;
+        0x73 => Rra, IndirectIndexed::init_rmw, 8;
+        0xc7 => Dcp, ZeroPage::init, 5;
+        0xd7 => Dcp, ZeroPageX::init, 6;
+        0xcf => Dcp, Absolute::init, 6;
+        0xdf => Dcp, AbsoluteX::init_rmw, 7;
+        0xdb => Dcp, AbsoluteY::init_rmw, 7;
+        0xc3 => Dcp, IndexedIndirect::init, 8;
+        0xd3 => Dcp, IndirectIndexed::init_rmw, 8;
+        0xe7 => Isc, ZeroPage::init, 5;
+        0xf7 => Isc, ZeroPageX::init, 6;
+        0xef => Isc, Absolute::init, 6;
+        0xff => Isc, AbsoluteX::init_rmw, 7;
+        0xfb => Isc, AbsoluteY::init_rmw, 7;
+        0xe3 => Isc, IndexedIndirect::init, 8;
+        0xf3 => Isc, IndirectIndexed::init_rmw, 8;
+        0x0b | 0x2b => Anc, Immediate::init, 2;
+        0x4b => Alr, Immediate::init, 2;
+        0x6b => Arr, Immediate::init, 2;
+        0xcb => Axs, Immediate::init, 2;
+        0xeb => Sbc, Immediate::init, 2;
+        0x8b => Ane, Immediate::init, 2;
+        0xbb => Las, AbsoluteY::init, 4;
+        0x93 => Sha, IndirectIndexed::init_store, 6;
+        0x9f => Sha, AbsoluteY::init_store, 5;
+        0x9c => Shy, AbsoluteX::init_store, 5;
+        0x9e => Shx, AbsoluteY::init_store, 5;
+        0x9b => Tas, AbsoluteY::init_store, 5;
+    }
+}
+
+pub trait AddressingMode<I: Interconnect, V: CpuVariant> {
     type Output;
     fn read(&self) -> Self::Output;
-    fn write(&self, _: &mut Cpu<I>, _: u8) {
+    fn write(&self, _: &mut Cpu<I, V>, _: u8) {
+        unimplemented!();
+    }
+
+    /// Write used by the unstable SHA, SHX, SHY and TAS stores
+    ///
+    /// The value is ANDed with the high byte of the base address plus one. If adding the index
+    /// crossed a page boundary, the result also replaces the high byte of the target address.
+    fn write_unstable(&self, _: &mut Cpu<I, V>, _: u8) {
         unimplemented!();
     }
 }
@@ -594,7 +411,9 @@ pub struct Absolute {
 }
 
 impl Absolute {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::Absolute;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let addr = cpu.read_pc16();
         let value = cpu.read_memory(addr);
 
@@ -605,7 +424,7 @@ impl Absolute {
         }
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let addr = cpu.read_pc16();
 
         Absolute {
@@ -616,17 +435,17 @@ impl Absolute {
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for Absolute {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for Absolute {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
         if !self.is_store {
-            // Dummy write cycle
-            cpu.tick();
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
         }
         cpu.write_memory(self.addr, value)
     }
@@ -640,14 +459,16 @@ pub struct AbsoluteAddress {
 }
 
 impl AbsoluteAddress {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::Absolute;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         AbsoluteAddress {
             addr: cpu.read_pc16(),
         }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for AbsoluteAddress {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for AbsoluteAddress {
     type Output = u16;
 
     fn read(&self) -> Self::Output {
@@ -655,8 +476,26 @@ impl<I: Interconnect> AddressingMode<I> for AbsoluteAddress {
     }
 }
 
+/// The absolute addressing mode of `JSR`
+///
+/// `JSR` pushes the return address between reading the low and high bytes of its target, so the
+/// instruction reads the operand itself.
+#[derive(Copy, Clone)]
+pub struct JsrAbsolute;
+
+impl JsrAbsolute {
+    pub const MODE: Mode = Mode::Absolute;
+}
+
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for JsrAbsolute {
+    type Output = ();
+
+    fn read(&self) -> Self::Output {}
+}
+
 #[derive(Copy, Clone)]
 pub struct AbsoluteX {
+    base_addr: u16,
     addr: u16,
     value: u8,
     is_store: bool,
@@ -670,11 +509,13 @@ enum Variant {
 }
 
 impl AbsoluteX {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::AbsoluteX;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         Self::init_base(cpu, Variant::Standard)
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         Self::init_base(cpu, Variant::Store)
     }
 
@@ -682,29 +523,23 @@ impl AbsoluteX {
     ///
     /// Read-modify-write instructions do not have a conditional page boundary cycle. For these
     /// instructions we always execute this cycle.
-    pub fn init_rmw<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub fn init_rmw<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         Self::init_base(cpu, Variant::ReadModifyWrite)
     }
 
-    fn init_base<I: Interconnect>(cpu: &mut Cpu<I>, variant: Variant) -> Self {
+    fn init_base<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, variant: Variant) -> Self {
         let base_addr = cpu.read_pc16();
-        let target_addr = base_addr + u16::from(cpu.registers.x);
-
-        // Conditional cycle if memory page crossed
-        if variant != Variant::Store
-            && (variant == Variant::ReadModifyWrite || (base_addr & 0xff00 != target_addr & 0xff00))
-        {
-            cpu.tick();
-        }
+        let target_addr = base_addr.wrapping_add(u16::from(cpu.registers.x));
+        dummy_read_unfixed(cpu, variant, base_addr, target_addr);
 
         let val = if variant != Variant::Store {
             cpu.read_memory(target_addr)
         } else {
-            cpu.tick();
             0x0 // Stores do not read memory and can cause illegal memory access if attempted
         };
 
         AbsoluteX {
+            base_addr,
             addr: target_addr,
             value: val,
             is_store: variant == Variant::Store,
@@ -712,70 +547,91 @@ impl AbsoluteX {
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for AbsoluteX {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for AbsoluteX {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
         if !self.is_store {
-            // Dummy write cycle
-            cpu.tick();
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
         }
         cpu.write_memory(self.addr, value)
     }
+
+    fn write_unstable(&self, cpu: &mut Cpu<I, V>, value: u8) {
+        unstable_store(cpu, self.base_addr, self.addr, value)
+    }
 }
 
 #[derive(Copy, Clone)]
 pub struct AbsoluteY {
+    base_addr: u16,
     addr: u16,
     value: u8,
+    is_store: bool,
 }
 
 impl AbsoluteY {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
-        Self::init_base(cpu, false)
+    pub const MODE: Mode = Mode::AbsoluteY;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
+        Self::init_base(cpu, Variant::Standard)
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
-        Self::init_base(cpu, true)
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
+        Self::init_base(cpu, Variant::Store)
     }
 
-    fn init_base<I: Interconnect>(cpu: &mut Cpu<I>, is_store: bool) -> Self {
-        let base_addr = cpu.read_pc16();
-        let target_addr = base_addr + u16::from(cpu.registers.y);
+    /// Init using special rules for cycle counting specific to read-modify-write instructions
+    ///
+    /// Only the unofficial read-modify-write instructions use this addressing mode. Like their
+    /// Absolute,X counterparts, they always execute the page boundary cycle.
+    pub fn init_rmw<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
+        Self::init_base(cpu, Variant::ReadModifyWrite)
+    }
 
-        // Conditional cycle if memory page crossed
-        if !is_store && base_addr & 0xff00 != target_addr & 0xff00 {
-            cpu.tick()
-        }
+    fn init_base<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, variant: Variant) -> Self {
+        let base_addr = cpu.read_pc16();
+        let target_addr = base_addr.wrapping_add(u16::from(cpu.registers.y));
+        dummy_read_unfixed(cpu, variant, base_addr, target_addr);
 
-        let val = if !is_store {
+        let val = if variant != Variant::Store {
             cpu.read_memory(target_addr)
         } else {
-            cpu.tick();
             0x0 // Stores do not read memory and can cause illegal memory access if attempted
         };
 
         AbsoluteY {
+            base_addr,
             addr: target_addr,
             value: val,
+            is_store: variant == Variant::Store,
         }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for AbsoluteY {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for AbsoluteY {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
+        if !self.is_store {
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
+        }
         cpu.write_memory(self.addr, value)
     }
+
+    fn write_unstable(&self, cpu: &mut Cpu<I, V>, value: u8) {
+        unstable_store(cpu, self.base_addr, self.addr, value)
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -784,23 +640,25 @@ pub struct Accumulator {
 }
 
 impl Accumulator {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
-        // dummy read cycle
-        cpu.tick();
+    pub const MODE: Mode = Mode::Accumulator;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
+        // Dummy read of the next opcode
+        cpu.dummy_read(cpu.registers.pc);
         Accumulator {
             value: cpu.registers.acc,
         }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for Accumulator {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for Accumulator {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
         cpu.registers.acc = value;
     }
 }
@@ -811,13 +669,15 @@ pub struct Immediate {
 }
 
 impl Immediate {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::Immediate;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let val = cpu.read_pc();
         Immediate { value: val }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for Immediate {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for Immediate {
     type Output = u8;
 
     fn read(&self) -> u8 {
@@ -828,7 +688,11 @@ impl<I: Interconnect> AddressingMode<I> for Immediate {
 #[derive(Copy, Clone)]
 pub struct Implied;
 
-impl<I: Interconnect> AddressingMode<I> for Implied {
+impl Implied {
+    pub const MODE: Mode = Mode::Implied;
+}
+
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for Implied {
     type Output = ();
 
     fn read(&self) -> Self::Output {}
@@ -838,44 +702,54 @@ impl<I: Interconnect> AddressingMode<I> for Implied {
 pub struct IndexedIndirect {
     addr: u16,
     value: u8,
+    is_store: bool,
 }
 
 impl IndexedIndirect {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::IndexedIndirect;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         Self::init_base(cpu, false)
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         Self::init_base(cpu, true)
     }
 
-    fn init_base<I: Interconnect>(cpu: &mut Cpu<I>, is_store: bool) -> Self {
+    fn init_base<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, is_store: bool) -> Self {
         let operand = cpu.read_pc();
-        let base_addr = u16::from(wrapping_add(operand, cpu.registers.x));
+        let base_addr = wrapping_add(operand, cpu.registers.x);
 
-        if !is_store {
-            // Dummy read cycle
-            cpu.tick();
-        }
+        // Dummy read of the unindexed pointer while the index is added
+        cpu.dummy_read(u16::from(operand));
 
-        let target_addr = cpu.read_memory16(base_addr);
-        let value = cpu.read_memory(target_addr);
+        let target_addr = cpu.read_memory16_zp(base_addr);
+        let value = if !is_store {
+            cpu.read_memory(target_addr)
+        } else {
+            0x0 // Stores don't read memory, can cause illegal memory access if attempted
+        };
 
         IndexedIndirect {
             addr: target_addr,
             value,
+            is_store,
         }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for IndexedIndirect {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for IndexedIndirect {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
+        if !self.is_store {
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
+        }
         cpu.write_memory(self.addr, value)
     }
 }
@@ -886,7 +760,9 @@ pub struct Indirect {
 }
 
 impl Indirect {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::Indirect;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let addr = cpu.read_pc16();
 
         // Recreate hardware bug specific to indirect jmp
@@ -904,7 +780,7 @@ impl Indirect {
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for Indirect {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for Indirect {
     type Output = u16;
 
     fn read(&self) -> Self::Output {
@@ -914,48 +790,70 @@ impl<I: Interconnect> AddressingMode<I> for Indirect {
 
 #[derive(Copy, Clone)]
 pub struct IndirectIndexed {
+    base_addr: u16,
     addr: u16,
     value: u8,
+    is_store: bool,
 }
 
 impl IndirectIndexed {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
-        Self::init_base(cpu, false)
+    pub const MODE: Mode = Mode::IndirectIndexed;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
+        Self::init_base(cpu, Variant::Standard)
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
-        Self::init_base(cpu, true)
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
+        Self::init_base(cpu, Variant::Store)
+    }
+
+    /// Init using special rules for cycle counting specific to read-modify-write instructions
+    ///
+    /// Only the unofficial read-modify-write instructions use this addressing mode. They always
+    /// execute the page boundary cycle.
+    pub fn init_rmw<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
+        Self::init_base(cpu, Variant::ReadModifyWrite)
     }
 
-    fn init_base<I: Interconnect>(cpu: &mut Cpu<I>, is_store: bool) -> Self {
+    fn init_base<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, variant: Variant) -> Self {
         let addr = cpu.read_pc();
         let y = cpu.registers.y;
         let base_addr = cpu.read_memory16_zp(addr);
-        let target_addr = base_addr + u16::from(y);
+        let target_addr = base_addr.wrapping_add(u16::from(y));
+        dummy_read_unfixed(cpu, variant, base_addr, target_addr);
 
-        // Conditional cycle if memory page crossed
-        if !is_store && base_addr & 0xff00 != target_addr & 0xff00 {
-            cpu.tick();
-        }
-
-        let val = cpu.read_memory(target_addr);
+        let val = if variant != Variant::Store {
+            cpu.read_memory(target_addr)
+        } else {
+            0x0 // Stores do not read memory and can cause illegal memory access if attempted
+        };
         IndirectIndexed {
+            base_addr,
             addr: target_addr,
             value: val,
+            is_store: variant == Variant::Store,
         }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for IndirectIndexed {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for IndirectIndexed {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
+        if !self.is_store {
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
+        }
         cpu.write_memory(self.addr, value)
     }
+
+    fn write_unstable(&self, cpu: &mut Cpu<I, V>, value: u8) {
+        unstable_store(cpu, self.base_addr, self.addr, value)
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -964,13 +862,15 @@ pub struct Relative {
 }
 
 impl Relative {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::Relative;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let offset = cpu.read_pc() as i8;
         Relative { offset }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for Relative {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for Relative {
     type Output = i8;
 
     fn read(&self) -> Self::Output {
@@ -986,7 +886,9 @@ pub struct ZeroPage {
 }
 
 impl ZeroPage {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::ZeroPage;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let addr = u16::from(cpu.read_pc());
         let val = cpu.read_memory(addr);
 
@@ -997,7 +899,7 @@ impl ZeroPage {
         }
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let addr = u16::from(cpu.read_pc());
 
         ZeroPage {
@@ -1008,17 +910,17 @@ impl ZeroPage {
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for ZeroPage {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for ZeroPage {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
         if !self.is_store {
-            // Dummy write cycle
-            cpu.tick();
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
         }
         cpu.write_memory(self.addr, value)
     }
@@ -1032,12 +934,14 @@ pub struct ZeroPageX {
 }
 
 impl ZeroPageX {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::ZeroPageX;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let base_addr = cpu.read_pc();
         let target_addr = u16::from(wrapping_add(base_addr, cpu.registers.x));
 
-        // Dummy read cycle
-        cpu.tick();
+        // Dummy read of the unindexed address while the index is added
+        cpu.dummy_read(u16::from(base_addr));
 
         let val = cpu.read_memory(target_addr);
 
@@ -1048,31 +952,32 @@ impl ZeroPageX {
         }
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         let base_addr = cpu.read_pc();
         let target_addr = u16::from(wrapping_add(base_addr, cpu.registers.x));
 
-        let val = cpu.read_memory(target_addr);
+        // Dummy read of the unindexed address while the index is added
+        cpu.dummy_read(u16::from(base_addr));
 
         ZeroPageX {
             addr: target_addr,
-            value: val,
+            value: 0x0, // Stores don't read memory, can cause illegal memory access if attempted
             is_store: true,
         }
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for ZeroPageX {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for ZeroPageX {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
         if !self.is_store {
-            // Dummy write cycle
-            cpu.tick();
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
         }
         cpu.write_memory(self.addr, value)
     }
@@ -1086,27 +991,26 @@ pub struct ZeroPageY {
 }
 
 impl ZeroPageY {
-    pub fn init<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub const MODE: Mode = Mode::ZeroPageY;
+
+    pub fn init<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         Self::init_base(cpu, false)
     }
 
-    pub fn init_store<I: Interconnect>(cpu: &mut Cpu<I>) -> Self {
+    pub fn init_store<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>) -> Self {
         Self::init_base(cpu, true)
     }
 
-    fn init_base<I: Interconnect>(cpu: &mut Cpu<I>, is_store: bool) -> Self {
+    fn init_base<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, is_store: bool) -> Self {
         let base_addr = cpu.read_pc();
         let target_addr = u16::from(wrapping_add(base_addr, cpu.registers.y));
 
-        if !is_store {
-            // Dummy read cycle
-            cpu.tick();
-        }
+        // Dummy read of the unindexed address while the index is added
+        cpu.dummy_read(u16::from(base_addr));
 
         let val = if !is_store {
             cpu.read_memory(target_addr)
         } else {
-            cpu.tick();
             0x0 // Stores don't read memory, can cause illegal memory access if attempted
         };
 
@@ -1118,29 +1022,60 @@ impl ZeroPageY {
     }
 }
 
-impl<I: Interconnect> AddressingMode<I> for ZeroPageY {
+impl<I: Interconnect, V: CpuVariant> AddressingMode<I, V> for ZeroPageY {
     type Output = u8;
 
     fn read(&self) -> Self::Output {
         self.value
     }
 
-    fn write(&self, cpu: &mut Cpu<I>, value: u8) {
+    fn write(&self, cpu: &mut Cpu<I, V>, value: u8) {
         if !self.is_store {
-            // Dummy write cycle
-            cpu.tick();
+            // Read-modify-write instructions write the unmodified value back first
+            cpu.write_memory(self.addr, self.value);
         }
         cpu.write_memory(self.addr, value)
     }
 }
 
+/// Dummy read made while the high byte of an indexed address is fixed up
+///
+/// The index is first added to the low byte alone, and the CPU reads from that unfixed address.
+/// Reads only need the extra cycle when a page boundary was crossed, but stores and
+/// read-modify-write instructions always take it.
+fn dummy_read_unfixed<I: Interconnect, V: CpuVariant>(
+    cpu: &mut Cpu<I, V>,
+    variant: Variant,
+    base_addr: u16,
+    target_addr: u16,
+) {
+    if variant != Variant::Standard || base_addr & 0xff00 != target_addr & 0xff00 {
+        cpu.dummy_read((base_addr & 0xff00) | (target_addr & 0xff));
+    }
+}
+
+fn unstable_store<I: Interconnect, V: CpuVariant>(
+    cpu: &mut Cpu<I, V>,
+    base_addr: u16,
+    target_addr: u16,
+    value: u8,
+) {
+    let value = value & wrapping_inc((base_addr >> 8) as u8);
+    let addr = if base_addr & 0xff00 != target_addr & 0xff00 {
+        (u16::from(value) << 8) | (target_addr & 0xff)
+    } else {
+        target_addr
+    };
+    cpu.write_memory(addr, value)
+}
+
 struct Adc;
 
 impl OpCode for Adc {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let left = cpu.registers.acc;
@@ -1154,41 +1089,103 @@ struct Sbc;
 impl OpCode for Sbc {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let lhs = cpu.registers.acc;
         let rhs = am.read();
-        let rhs = !rhs;
-        adc_base(cpu, lhs, rhs)
+        sbc_base(cpu, lhs, rhs)
+    }
+}
+
+fn adc_base<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, lhs: u8, rhs: u8) {
+    if V::DECIMAL_MODE && cpu.registers.decimal_flag() {
+        adc_decimal(cpu, lhs, rhs)
+    } else {
+        adc_binary(cpu, lhs, rhs)
     }
 }
 
-fn adc_base<I: Interconnect>(cpu: &mut Cpu<I>, lhs: u8, rhs: u8) {
-    if cpu.registers.decimal_flag() {
-        panic!("Attempted decimal mode arithmetic");
+fn sbc_base<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, lhs: u8, rhs: u8) {
+    if V::DECIMAL_MODE && cpu.registers.decimal_flag() {
+        sbc_decimal(cpu, lhs, rhs)
     } else {
-        // See http://www.righto.com/2012/12/the-6502-overflow-flag-explained.html
-        let carry = if cpu.registers.carry_flag() { 1 } else { 0 };
+        adc_binary(cpu, lhs, !rhs)
+    }
+}
 
-        // add using the native word size
-        let res = carry + lhs as isize + rhs as isize;
+fn adc_binary<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, lhs: u8, rhs: u8) {
+    // See http://www.righto.com/2012/12/the-6502-overflow-flag-explained.html
+    let carry = if cpu.registers.carry_flag() { 1 } else { 0 };
 
-        // if the operation carries into the 8th bit, carry flag will be 1,
-        // and zero otherwise.
-        let has_carry = res & 0x100 != 0;
+    // add using the native word size
+    let res = carry + lhs as isize + rhs as isize;
 
-        let res = res as u8;
+    // if the operation carries into the 8th bit, carry flag will be 1,
+    // and zero otherwise.
+    let has_carry = res & 0x100 != 0;
 
-        // Set the overflow flag when both operands have the same sign bit AND
-        // the sign bit of the result differs from the two.
-        let has_overflow = (lhs ^ rhs) & 0x80 == 0 && (lhs ^ res) & 0x80 != 0;
+    let res = res as u8;
 
-        cpu.registers.set_carry_flag(has_carry);
-        cpu.registers.set_overflow_flag(has_overflow);
-        cpu.registers.set_acc(res);
+    // Set the overflow flag when both operands have the same sign bit AND
+    // the sign bit of the result differs from the two.
+    let has_overflow = (lhs ^ rhs) & 0x80 == 0 && (lhs ^ res) & 0x80 != 0;
+
+    cpu.registers.set_carry_flag(has_carry);
+    cpu.registers.set_overflow_flag(has_overflow);
+    cpu.registers.set_acc(res);
+}
+
+/// NMOS decimal mode addition
+///
+/// The zero flag is taken from the binary sum, while the sign and overflow flags are taken from
+/// the intermediate result after the low nibble has been adjusted but before the high nibble has.
+/// See http://www.6502.org/tutorials/decimal_mode.html#A
+fn adc_decimal<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, lhs: u8, rhs: u8) {
+    let carry = if cpu.registers.carry_flag() { 1 } else { 0 };
+    let binary_res = (u16::from(lhs) + u16::from(rhs) + carry) as u8;
+
+    let mut lo = u16::from(lhs & 0x0f) + u16::from(rhs & 0x0f) + carry;
+    if lo >= 0x0a {
+        lo = ((lo + 0x06) & 0x0f) + 0x10;
+    }
+
+    let mut res = u16::from(lhs & 0xf0) + u16::from(rhs & 0xf0) + lo;
+    let signed_res = i16::from((lhs & 0xf0) as i8) + i16::from((rhs & 0xf0) as i8) + lo as i16;
+    let has_overflow = !(-128..=127).contains(&signed_res);
+    let is_negative = res & 0x80 != 0;
+
+    if res >= 0xa0 {
+        res += 0x60;
     }
+
+    cpu.registers.acc = res as u8;
+    cpu.registers.set_carry_flag(res >= 0x100);
+    cpu.registers.set_overflow_flag(has_overflow);
+    cpu.registers.set_sign_flag(is_negative);
+    cpu.registers.set_zero_flag(binary_res == 0);
+}
+
+/// NMOS decimal mode subtraction
+///
+/// All flags are set exactly as they would be by a binary subtraction, only the accumulator
+/// receives the decimal result. See http://www.6502.org/tutorials/decimal_mode.html#A
+fn sbc_decimal<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, lhs: u8, rhs: u8) {
+    let borrow = if cpu.registers.carry_flag() { 0 } else { 1 };
+
+    let mut lo = i16::from(lhs & 0x0f) - i16::from(rhs & 0x0f) - borrow;
+    if lo < 0 {
+        lo = ((lo - 0x06) & 0x0f) - 0x10;
+    }
+
+    let mut res = i16::from(lhs & 0xf0) - i16::from(rhs & 0xf0) + lo;
+    if res < 0 {
+        res -= 0x60;
+    }
+
+    adc_binary(cpu, lhs, !rhs);
+    cpu.registers.acc = res as u8;
 }
 
 struct And;
@@ -1196,8 +1193,8 @@ struct And;
 impl OpCode for And {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let lhs = cpu.registers.acc;
@@ -1207,30 +1204,32 @@ impl OpCode for And {
     }
 }
 
-fn shift_left<I: Interconnect, AM: AddressingMode<I, Output = u8>>(
-    cpu: &mut Cpu<I>,
+fn shift_left<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = u8>>(
+    cpu: &mut Cpu<I, V>,
     am: AM,
     lsb: bool,
-) {
+) -> u8 {
     let val = am.read();
     let carry = (val & 0x80) != 0;
     let res = if lsb { (val << 1) | 0x1 } else { val << 1 };
     cpu.registers.set_carry_flag(carry);
     cpu.registers.set_sign_and_zero_flag(res);
-    am.write(cpu, res)
+    am.write(cpu, res);
+    res
 }
 
-fn shift_right<I: Interconnect, AM: AddressingMode<I, Output = u8>>(
-    cpu: &mut Cpu<I>,
+fn shift_right<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = u8>>(
+    cpu: &mut Cpu<I, V>,
     am: AM,
     msb: bool,
-) {
+) -> u8 {
     let val = am.read();
     let carry = (val & 0x1) != 0;
     let res = if msb { (val >> 1) | 0x80 } else { val >> 1 };
     cpu.registers.set_carry_flag(carry);
     cpu.registers.set_sign_and_zero_flag(res);
-    am.write(cpu, res)
+    am.write(cpu, res);
+    res
 }
 
 struct Asl;
@@ -1238,11 +1237,11 @@ struct Asl;
 impl OpCode for Asl {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
-        shift_left(cpu, am, false)
+        shift_left(cpu, am, false);
     }
 }
 
@@ -1251,12 +1250,12 @@ struct Rol;
 impl OpCode for Rol {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let carry_set = cpu.registers.carry_flag();
-        shift_left(cpu, am, carry_set)
+        shift_left(cpu, am, carry_set);
     }
 }
 
@@ -1265,12 +1264,12 @@ struct Ror;
 impl OpCode for Ror {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let carry_set = cpu.registers.carry_flag();
-        shift_right(cpu, am, carry_set)
+        shift_right(cpu, am, carry_set);
     }
 }
 
@@ -1279,16 +1278,16 @@ struct Lsr;
 impl OpCode for Lsr {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
-        shift_right(cpu, am, false)
+        shift_right(cpu, am, false);
     }
 }
 
-fn branch<I: Interconnect, AM: AddressingMode<I, Output = i8>>(
-    cpu: &mut Cpu<I>,
+fn branch<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = i8>>(
+    cpu: &mut Cpu<I, V>,
     am: AM,
     condition: bool,
 ) {
@@ -1296,11 +1295,18 @@ fn branch<I: Interconnect, AM: AddressingMode<I, Output = i8>>(
         let rel_addr = am.read();
         let old_pc = cpu.registers.pc;
         cpu.registers.pc = (i32::from(cpu.registers.pc) + i32::from(rel_addr)) as u16;
-        cpu.tick();
 
-        // Conditional cycle if pc crosses page boundary
+        // Interrupts are polled before the operand fetch like any other instruction, but a taken
+        // branch only polls them again if it crosses a page boundary.
+        let irq_pending = cpu.irq_pending;
+        cpu.dummy_read(old_pc);
+
+        // Conditional cycle if pc crosses page boundary, reading from the target before its high
+        // byte is fixed up
         if old_pc & 0xFF00 != cpu.registers.pc & 0xFF00 {
-            cpu.tick();
+            cpu.dummy_read((old_pc & 0xff00) | (cpu.registers.pc & 0xff));
+        } else {
+            cpu.irq_pending = irq_pending;
         }
     }
 }
@@ -1310,8 +1316,8 @@ struct Bcc;
 impl OpCode for Bcc {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let carry_clear = !cpu.registers.carry_flag();
@@ -1324,8 +1330,8 @@ pub struct Bpl;
 impl OpCode for Bpl {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let sign_clear = !cpu.registers.sign_flag();
@@ -1338,8 +1344,8 @@ struct Beq;
 impl OpCode for Beq {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let zero_set = cpu.registers.zero_flag();
@@ -1352,8 +1358,8 @@ struct Bmi;
 impl OpCode for Bmi {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let sign_set = cpu.registers.sign_flag();
@@ -1366,8 +1372,8 @@ struct Bvc;
 impl OpCode for Bvc {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let sign_clear = !cpu.registers.overflow_flag();
@@ -1380,8 +1386,8 @@ struct Bvs;
 impl OpCode for Bvs {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let sign_clear = cpu.registers.overflow_flag();
@@ -1394,8 +1400,8 @@ struct Bcs;
 impl OpCode for Bcs {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let carry_set = cpu.registers.carry_flag();
@@ -1408,8 +1414,8 @@ struct Bne;
 impl OpCode for Bne {
     type Input = i8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let zero_clear = !cpu.registers.zero_flag();
@@ -1417,8 +1423,7 @@ impl OpCode for Bne {
     }
 }
 
-fn compare<I: Interconnect, AM: AddressingMode<I, Output = u8>>(cpu: &mut Cpu<I>, am: AM, lhs: u8) {
-    let rhs = am.read();
+fn compare<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, lhs: u8, rhs: u8) {
     let res = i32::from(lhs) - i32::from(rhs);
     cpu.registers.set_carry_flag(res & 0x100 == 0);
     cpu.registers.set_sign_and_zero_flag(res as u8);
@@ -1429,12 +1434,13 @@ struct Cmp;
 impl OpCode for Cmp {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
-        let val = cpu.registers.acc;
-        compare(cpu, am, val);
+        let lhs = cpu.registers.acc;
+        let rhs = am.read();
+        compare(cpu, lhs, rhs);
     }
 }
 
@@ -1443,12 +1449,13 @@ struct Cpx;
 impl OpCode for Cpx {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
-        let val = cpu.registers.x;
-        compare(cpu, am, val);
+        let lhs = cpu.registers.x;
+        let rhs = am.read();
+        compare(cpu, lhs, rhs);
     }
 }
 
@@ -1457,12 +1464,13 @@ struct Cpy;
 impl OpCode for Cpy {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
-        let val = cpu.registers.y;
-        compare(cpu, am, val);
+        let lhs = cpu.registers.y;
+        let rhs = am.read();
+        compare(cpu, lhs, rhs);
     }
 }
 
@@ -1471,8 +1479,8 @@ struct Bit;
 impl OpCode for Bit {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let lhs = cpu.registers.acc;
@@ -1490,18 +1498,22 @@ struct Brk;
 impl OpCode for Brk {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
-        cpu.registers.pc += 1;
+        // BRK reads and discards the byte following the opcode
+        cpu.read_pc();
         let pc = cpu.registers.pc;
         let status = cpu.registers.status;
         cpu.push_stack16(pc);
         cpu.push_stack(status);
+
+        // The flag is set before the vector is fetched, so an IRQ polled on the last cycles isn't
+        // taken before the handler's first instruction
+        cpu.registers.set_interrupt_disable_flag(true);
         let irq_handler = cpu.read_memory16(BRK_VECTOR);
         cpu.registers.pc = irq_handler;
-        cpu.registers.set_interrupt_disable_flag(true);
     }
 }
 
@@ -1510,12 +1522,12 @@ struct Clc;
 impl OpCode for Clc {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.set_carry_flag(false);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1524,12 +1536,12 @@ struct Cld;
 impl OpCode for Cld {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.set_decimal_flag(false);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1538,12 +1550,13 @@ struct Cli;
 impl OpCode for Cli {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
+        // The flag changes after interrupts have been polled, delaying its effect by one instruction
+        cpu.dummy_read(cpu.registers.pc);
         cpu.registers.set_interrupt_disable_flag(false);
-        cpu.tick()
     }
 }
 
@@ -1552,12 +1565,12 @@ struct Clv;
 impl OpCode for Clv {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.set_overflow_flag(false);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1566,8 +1579,8 @@ struct Dec;
 impl OpCode for Dec {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let val = wrapping_dec(am.read());
@@ -1581,14 +1594,14 @@ struct Dex;
 impl OpCode for Dex {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         let val = wrapping_dec(cpu.registers.x);
         cpu.registers.x = val;
         cpu.registers.set_sign_and_zero_flag(val);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1597,14 +1610,14 @@ struct Dey;
 impl OpCode for Dey {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         let val = wrapping_dec(cpu.registers.y);
         cpu.registers.y = val;
         cpu.registers.set_sign_and_zero_flag(val);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1613,8 +1626,8 @@ struct Eor;
 impl OpCode for Eor {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let rhs = am.read();
@@ -1629,8 +1642,8 @@ struct Inc;
 impl OpCode for Inc {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let val = wrapping_inc(am.read());
@@ -1644,14 +1657,14 @@ struct Inx;
 impl OpCode for Inx {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         let val = wrapping_inc(cpu.registers.x);
         cpu.registers.x = val;
         cpu.registers.set_sign_and_zero_flag(val);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1660,14 +1673,14 @@ struct Iny;
 impl OpCode for Iny {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         let val = wrapping_inc(cpu.registers.y);
         cpu.registers.y = val;
         cpu.registers.set_sign_and_zero_flag(val);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1676,8 +1689,8 @@ struct Jmp;
 impl OpCode for Jmp {
     type Input = u16;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         cpu.registers.pc = am.read();
@@ -1687,17 +1700,20 @@ impl OpCode for Jmp {
 struct Jsr;
 
 impl OpCode for Jsr {
-    type Input = u16;
+    type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
-        am: AM,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        _: AM,
     ) {
-        let loc = am.read();
+        let low_byte = cpu.read_pc();
+        cpu.dummy_read_stack();
+
+        // The return address pushed is that of the high byte of the target, which is read last
         let pc = cpu.registers.pc;
-        cpu.push_stack16(pc - 1);
-        cpu.registers.pc = loc;
-        cpu.tick()
+        cpu.push_stack16(pc);
+        let high_byte = cpu.read_pc();
+        cpu.registers.pc = from_lo_hi(low_byte, high_byte);
     }
 }
 
@@ -1706,8 +1722,8 @@ struct Lda;
 impl OpCode for Lda {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let val = am.read();
@@ -1720,8 +1736,8 @@ struct Ldx;
 impl OpCode for Ldx {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let val = am.read();
@@ -1735,8 +1751,8 @@ struct Ldy;
 impl OpCode for Ldy {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let val = am.read();
@@ -1750,11 +1766,11 @@ struct Nop;
 impl OpCode for Nop {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1763,8 +1779,8 @@ struct Ora;
 impl OpCode for Ora {
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let lhs = cpu.registers.acc;
@@ -1779,12 +1795,12 @@ struct Pha;
 impl OpCode for Pha {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         // Dummy read
-        cpu.tick();
+        cpu.dummy_read(cpu.registers.pc);
 
         let acc = cpu.registers.acc;
         cpu.push_stack(acc)
@@ -1796,12 +1812,12 @@ struct Php;
 impl OpCode for Php {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         // Dummy read
-        cpu.tick();
+        cpu.dummy_read(cpu.registers.pc);
 
         let stat = cpu.registers.status;
         cpu.push_stack(stat)
@@ -1813,15 +1829,15 @@ struct Pla;
 impl OpCode for Pla {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         // Dummy read
-        cpu.tick();
+        cpu.dummy_read(cpu.registers.pc);
 
         // Stack pointer inc cycle
-        cpu.tick();
+        cpu.dummy_read_stack();
 
         let val = cpu.pop_stack();
         cpu.registers.set_acc(val);
@@ -1833,15 +1849,15 @@ struct Plp;
 impl OpCode for Plp {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         // Dummy read
-        cpu.tick();
+        cpu.dummy_read(cpu.registers.pc);
 
         // Stack pointer inc cycle
-        cpu.tick();
+        cpu.dummy_read_stack();
 
         let val = cpu.pop_stack();
         cpu.registers.set_status_from_stack(val);
@@ -1853,19 +1869,21 @@ struct Rti;
 impl OpCode for Rti {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         // Dummy read cycle
-        cpu.tick();
+        cpu.dummy_read(cpu.registers.pc);
 
         // Increment stack pointer cycle
-        cpu.tick();
+        cpu.dummy_read_stack();
 
+        // The status is restored before the program counter, so unlike CLI and PLP, a cleared
+        // interrupt disable flag takes effect immediately
         let stat = cpu.pop_stack();
-        let pc = cpu.pop_stack16();
         cpu.registers.set_status_from_stack(stat);
+        let pc = cpu.pop_stack16();
         cpu.registers.pc = pc;
     }
 }
@@ -1875,21 +1893,21 @@ struct Rts;
 impl OpCode for Rts {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         // Dummy read cycle
-        cpu.tick();
+        cpu.dummy_read(cpu.registers.pc);
 
         // Stack increment cycle
-        cpu.tick();
+        cpu.dummy_read_stack();
 
         let pc = cpu.pop_stack16();
-        cpu.registers.pc = pc + 1;
 
         // increment PC cycle
-        cpu.tick()
+        cpu.dummy_read(pc);
+        cpu.registers.pc = pc.wrapping_add(1);
     }
 }
 
@@ -1898,12 +1916,12 @@ struct Sec;
 impl OpCode for Sec {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.set_carry_flag(true);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1912,12 +1930,12 @@ struct Sed;
 impl OpCode for Sed {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.set_decimal_flag(true);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -1926,12 +1944,13 @@ struct Sei;
 impl OpCode for Sei {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
+        // The flag changes after interrupts have been polled, delaying its effect by one instruction
+        cpu.dummy_read(cpu.registers.pc);
         cpu.registers.set_interrupt_disable_flag(true);
-        cpu.tick()
     }
 }
 
@@ -1942,8 +1961,8 @@ impl OpCode for Sta {
     // Is there a compelling reason to have write-only addressing implementations?
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let acc = cpu.registers.acc;
@@ -1958,8 +1977,8 @@ impl OpCode for Stx {
     // Is there a compelling reason to have write-only addressing implementations?
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let x = cpu.registers.x;
@@ -1974,8 +1993,8 @@ impl OpCode for Sty {
     // Is there a compelling reason to have write-only addressing implementations?
     type Input = u8;
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         am: AM,
     ) {
         let y = cpu.registers.y;
@@ -1988,14 +2007,14 @@ struct Tax;
 impl OpCode for Tax {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.x = cpu.registers.acc;
         let x = cpu.registers.x;
         cpu.registers.set_sign_and_zero_flag(x);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -2004,14 +2023,14 @@ struct Tay;
 impl OpCode for Tay {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.y = cpu.registers.acc;
         let y = cpu.registers.y;
         cpu.registers.set_sign_and_zero_flag(y);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -2020,14 +2039,14 @@ struct Tsx;
 impl OpCode for Tsx {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.x = cpu.registers.sp;
         let x = cpu.registers.x;
         cpu.registers.set_sign_and_zero_flag(x);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -2036,14 +2055,14 @@ struct Txa;
 impl OpCode for Txa {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.acc = cpu.registers.x;
         let acc = cpu.registers.acc;
         cpu.registers.set_sign_and_zero_flag(acc);
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -2052,12 +2071,12 @@ struct Txs;
 impl OpCode for Txs {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.sp = cpu.registers.x;
-        cpu.tick()
+        cpu.dummy_read(cpu.registers.pc)
     }
 }
 
@@ -2066,13 +2085,376 @@ struct Tya;
 impl OpCode for Tya {
     type Input = ();
 
-    fn execute<I: Interconnect, AM: AddressingMode<I, Output = Self::Input>>(
-        cpu: &mut Cpu<I>,
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
         _: AM,
     ) {
         cpu.registers.acc = cpu.registers.y;
         let acc = cpu.registers.acc;
         cpu.registers.set_sign_and_zero_flag(acc);
+        cpu.dummy_read(cpu.registers.pc)
+    }
+}
+
+// Unofficial opcodes
+//
+// The NMOS 6502 decodes every byte as an instruction. Most of the undocumented ones combine the
+// logic of two official instructions that share the same opcode bits.
+
+/// Magic constant used by the unstable ANE and LXA opcodes. It varies between chips (and even
+/// with temperature), 0xee being the most commonly observed value.
+const UNSTABLE_MAGIC: u8 = 0xee;
+
+struct Alr;
+
+impl OpCode for Alr {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = cpu.registers.acc & am.read();
+        cpu.registers.set_carry_flag(val & 0x1 != 0);
+        cpu.registers.set_acc(val >> 1);
+    }
+}
+
+struct Anc;
+
+impl OpCode for Anc {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let res = cpu.registers.acc & am.read();
+        cpu.registers.set_acc(res);
+        cpu.registers.set_carry_flag(res & 0x80 != 0);
+    }
+}
+
+struct Ane;
+
+impl OpCode for Ane {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let res = (cpu.registers.acc | UNSTABLE_MAGIC) & cpu.registers.x & am.read();
+        cpu.registers.set_acc(res);
+    }
+}
+
+struct Arr;
+
+impl OpCode for Arr {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = cpu.registers.acc & am.read();
+        let carry = if cpu.registers.carry_flag() { 0x80 } else { 0 };
+        let res = (val >> 1) | carry;
+        if V::DECIMAL_MODE && cpu.registers.decimal_flag() {
+            arr_decimal(cpu, val, res)
+        } else {
+            cpu.registers.set_acc(res);
+
+            // Carry comes from bit 6 of the result and overflow from bit 6 XOR bit 5
+            cpu.registers.set_carry_flag(res & 0x40 != 0);
+            cpu.registers
+                .set_overflow_flag(((res >> 6) ^ (res >> 5)) & 0x1 != 0);
+        }
+    }
+}
+
+/// NMOS decimal mode ARR
+///
+/// The sign and zero flags come from the rotated value and overflow from bit 6 changing in the
+/// rotation. Each nibble of the rotated value is then adjusted when the matching nibble of the
+/// unrotated one, rounded up to even, is above 5, and carry is set when the high nibble is. See
+/// http://www.zimmers.net/anonftp/pub/cbm/documents/chipdata/64doc
+fn arr_decimal<I: Interconnect, V: CpuVariant>(cpu: &mut Cpu<I, V>, val: u8, rotated: u8) {
+    cpu.registers.set_sign_and_zero_flag(rotated);
+    cpu.registers.set_overflow_flag((val ^ rotated) & 0x40 != 0);
+
+    let mut res = rotated;
+    let lo = val & 0x0f;
+    if lo + (lo & 0x01) > 0x05 {
+        res = (res & 0xf0) | (res.wrapping_add(0x06) & 0x0f);
+    }
+
+    let hi = val >> 4;
+    let adjust_hi = hi + (hi & 0x01) > 0x05;
+    if adjust_hi {
+        res = res.wrapping_add(0x60);
+    }
+    cpu.registers.set_carry_flag(adjust_hi);
+    cpu.registers.acc = res;
+}
+
+struct Axs;
+
+impl OpCode for Axs {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let lhs = cpu.registers.acc & cpu.registers.x;
+        let rhs = am.read();
+        let res = wrapping_subtract(lhs, rhs);
+        cpu.registers.set_carry_flag(lhs >= rhs);
+        cpu.registers.x = res;
+        cpu.registers.set_sign_and_zero_flag(res);
+    }
+}
+
+struct Dcp;
+
+impl OpCode for Dcp {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = wrapping_dec(am.read());
+        am.write(cpu, val);
+        let lhs = cpu.registers.acc;
+        compare(cpu, lhs, val);
+    }
+}
+
+/// A NOP that reads its operand. The read itself is performed by the addressing mode, so there is
+/// nothing left to do.
+struct Ign;
+
+impl OpCode for Ign {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        _: &mut Cpu<I, V>,
+        _: AM,
+    ) {
+    }
+}
+
+struct Isc;
+
+impl OpCode for Isc {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = wrapping_inc(am.read());
+        am.write(cpu, val);
+        let lhs = cpu.registers.acc;
+        sbc_base(cpu, lhs, val)
+    }
+}
+
+/// Locks up the CPU. Real hardware only recovers on reset, so the program counter is left on the
+/// offending opcode and no further instructions are fetched until the CPU is reset.
+struct Kil;
+
+impl OpCode for Kil {
+    type Input = ();
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        _: AM,
+    ) {
+        cpu.registers.pc = cpu.registers.pc.wrapping_sub(1);
+        cpu.jammed = true;
         cpu.tick()
     }
 }
+
+struct Las;
+
+impl OpCode for Las {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let res = am.read() & cpu.registers.sp;
+        cpu.registers.sp = res;
+        cpu.registers.x = res;
+        cpu.registers.set_acc(res);
+    }
+}
+
+struct Lax;
+
+impl OpCode for Lax {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = am.read();
+        cpu.registers.x = val;
+        cpu.registers.set_acc(val);
+    }
+}
+
+struct Lxa;
+
+impl OpCode for Lxa {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let res = (cpu.registers.acc | UNSTABLE_MAGIC) & am.read();
+        cpu.registers.x = res;
+        cpu.registers.set_acc(res);
+    }
+}
+
+struct Rla;
+
+impl OpCode for Rla {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let carry_set = cpu.registers.carry_flag();
+        let rhs = shift_left(cpu, am, carry_set);
+        let res = cpu.registers.acc & rhs;
+        cpu.registers.set_acc(res);
+    }
+}
+
+struct Rra;
+
+impl OpCode for Rra {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let carry_set = cpu.registers.carry_flag();
+        let rhs = shift_right(cpu, am, carry_set);
+        let lhs = cpu.registers.acc;
+        adc_base(cpu, lhs, rhs)
+    }
+}
+
+struct Sax;
+
+impl OpCode for Sax {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = cpu.registers.acc & cpu.registers.x;
+        am.write(cpu, val)
+    }
+}
+
+struct Sha;
+
+impl OpCode for Sha {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = cpu.registers.acc & cpu.registers.x;
+        am.write_unstable(cpu, val)
+    }
+}
+
+struct Shx;
+
+impl OpCode for Shx {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let x = cpu.registers.x;
+        am.write_unstable(cpu, x)
+    }
+}
+
+struct Shy;
+
+impl OpCode for Shy {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let y = cpu.registers.y;
+        am.write_unstable(cpu, y)
+    }
+}
+
+struct Slo;
+
+impl OpCode for Slo {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let rhs = shift_left(cpu, am, false);
+        let res = cpu.registers.acc | rhs;
+        cpu.registers.set_acc(res);
+    }
+}
+
+struct Sre;
+
+impl OpCode for Sre {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let rhs = shift_right(cpu, am, false);
+        let res = cpu.registers.acc ^ rhs;
+        cpu.registers.set_acc(res);
+    }
+}
+
+struct Tas;
+
+impl OpCode for Tas {
+    type Input = u8;
+
+    fn execute<I: Interconnect, V: CpuVariant, AM: AddressingMode<I, V, Output = Self::Input>>(
+        cpu: &mut Cpu<I, V>,
+        am: AM,
+    ) {
+        let val = cpu.registers.acc & cpu.registers.x;
+        cpu.registers.sp = val;
+        am.write_unstable(cpu, val)
+    }
+}
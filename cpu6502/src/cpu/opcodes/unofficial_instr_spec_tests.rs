@@ -0,0 +1,309 @@
+use crate::cpu::{
+    opcodes::{am_test_utils::*, *},
+    test_fixture::{NmosTestCpu, TestCpu},
+};
+
+#[test]
+fn alr() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0b1111_0011;
+    Alr::execute(&mut cpu, 0b0101_0101_u8);
+    assert_eq!(0b0010_1000, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+    assert_eq!(false, cpu.registers.sign_flag());
+    assert_eq!(false, cpu.registers.zero_flag());
+}
+
+#[test]
+fn anc() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0b1111_0000;
+    Anc::execute(&mut cpu, 0b1010_1010_u8);
+    assert_eq!(0b1010_0000, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+
+    Anc::execute(&mut cpu, 0b0101_0101_u8);
+    assert_eq!(0, cpu.registers.acc);
+    assert_eq!(false, cpu.registers.carry_flag());
+    assert_eq!(true, cpu.registers.zero_flag());
+}
+
+#[test]
+fn ane() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0x01;
+    cpu.registers.x = 0x0f;
+    Ane::execute(&mut cpu, 0xff_u8);
+    assert_eq!(0x0f, cpu.registers.acc);
+}
+
+#[test]
+fn arr() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0xff;
+    cpu.registers.set_carry_flag(true);
+    Arr::execute(&mut cpu, 0xc0_u8);
+    assert_eq!(0xe0, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+    assert_eq!(false, cpu.registers.overflow_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+
+    cpu.registers.acc = 0xff;
+    cpu.registers.set_carry_flag(false);
+    Arr::execute(&mut cpu, 0x80_u8);
+    assert_eq!(0x40, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+    assert_eq!(true, cpu.registers.overflow_flag());
+    assert_eq!(false, cpu.registers.sign_flag());
+}
+
+#[test]
+fn arr_decimal_flag_ignored_by_2a03() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.set_decimal_flag(true);
+    cpu.registers.acc = 0xff;
+    cpu.registers.set_carry_flag(true);
+    Arr::execute(&mut cpu, 0xff_u8);
+    assert_eq!(0xff, cpu.registers.acc);
+}
+
+#[test]
+fn arr_decimal() {
+    let mut cpu = NmosTestCpu::new_nmos_test();
+    cpu.registers.set_decimal_flag(true);
+
+    // Both nibbles are adjusted, and the flags come from the rotated value 0xff
+    cpu.registers.acc = 0xff;
+    cpu.registers.set_carry_flag(true);
+    Arr::execute(&mut cpu, 0xff_u8);
+    assert_eq!(0x55, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+    assert_eq!(false, cpu.registers.overflow_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+    assert_eq!(false, cpu.registers.zero_flag());
+
+    // Neither nibble is adjusted, and bit 6 changed in the rotation
+    cpu.registers.acc = 0x44;
+    cpu.registers.set_carry_flag(false);
+    Arr::execute(&mut cpu, 0xff_u8);
+    assert_eq!(0x22, cpu.registers.acc);
+    assert_eq!(false, cpu.registers.carry_flag());
+    assert_eq!(true, cpu.registers.overflow_flag());
+    assert_eq!(false, cpu.registers.sign_flag());
+
+    // Zero comes from the rotated value before it's adjusted
+    cpu.registers.acc = 0x0b;
+    Arr::execute(&mut cpu, 0xff_u8);
+    assert_eq!(0x0b, cpu.registers.acc);
+    assert_eq!(false, cpu.registers.zero_flag());
+    cpu.registers.acc = 0x01;
+    Arr::execute(&mut cpu, 0xff_u8);
+    assert_eq!(0x00, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.zero_flag());
+}
+
+#[test]
+fn axs() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0xf0;
+    cpu.registers.x = 0x3c;
+    Axs::execute(&mut cpu, 0x10_u8);
+    assert_eq!(0x20, cpu.registers.x);
+    assert_eq!(true, cpu.registers.carry_flag());
+
+    Axs::execute(&mut cpu, 0x21_u8);
+    assert_eq!(0xff, cpu.registers.x);
+    assert_eq!(false, cpu.registers.carry_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+}
+
+#[test]
+fn dcp() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0x41;
+    let am = WriterAddressingMode::with_read_value(0x42);
+    let write_ref = am.write_ref();
+    Dcp::execute(&mut cpu, am);
+    assert_eq!(0x41, write_ref.get());
+    assert_eq!(true, cpu.registers.zero_flag());
+    assert_eq!(true, cpu.registers.carry_flag());
+}
+
+#[test]
+fn isc() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0x10;
+    cpu.registers.set_carry_flag(true);
+    let am = WriterAddressingMode::with_read_value(0x04);
+    let write_ref = am.write_ref();
+    Isc::execute(&mut cpu, am);
+    assert_eq!(0x05, write_ref.get());
+    assert_eq!(0x0b, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+}
+
+#[test]
+fn kil() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(0x200, &[0x02, 0xea]);
+    cpu.step();
+    cpu.step();
+    assert_eq!(0x200, cpu.registers.pc);
+    assert_eq!(true, cpu.jammed);
+
+    cpu.soft_reset();
+    assert_eq!(false, cpu.jammed);
+}
+
+#[test]
+fn las() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.sp = 0b1111_0000;
+    Las::execute(&mut cpu, 0b1010_1010_u8);
+    assert_eq!(0b1010_0000, cpu.registers.acc);
+    assert_eq!(0b1010_0000, cpu.registers.x);
+    assert_eq!(0b1010_0000, cpu.registers.sp);
+    assert_eq!(true, cpu.registers.sign_flag());
+}
+
+#[test]
+fn lax() {
+    let mut cpu = TestCpu::new_test();
+    Lax::execute(&mut cpu, 0x80_u8);
+    assert_eq!(0x80, cpu.registers.acc);
+    assert_eq!(0x80, cpu.registers.x);
+    assert_eq!(true, cpu.registers.sign_flag());
+    assert_eq!(false, cpu.registers.zero_flag());
+}
+
+#[test]
+fn lxa() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0x01;
+    Lxa::execute(&mut cpu, 0x0f_u8);
+    assert_eq!(0x0f, cpu.registers.acc);
+    assert_eq!(0x0f, cpu.registers.x);
+}
+
+#[test]
+fn rla() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0xff;
+    cpu.registers.set_carry_flag(true);
+    let am = WriterAddressingMode::with_read_value(0b1000_0001);
+    let write_ref = am.write_ref();
+    Rla::execute(&mut cpu, am);
+    assert_eq!(0b0000_0011, write_ref.get());
+    assert_eq!(0b0000_0011, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+}
+
+#[test]
+fn rra() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0x10;
+    cpu.registers.set_carry_flag(false);
+    let am = WriterAddressingMode::with_read_value(0x03);
+    let write_ref = am.write_ref();
+    Rra::execute(&mut cpu, am);
+
+    // 0x03 rotated right is 0x01 with carry set, which is then added to the accumulator
+    assert_eq!(0x01, write_ref.get());
+    assert_eq!(0x12, cpu.registers.acc);
+    assert_eq!(false, cpu.registers.carry_flag());
+}
+
+#[test]
+fn sax() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0b1100_1100;
+    cpu.registers.x = 0b1010_1010;
+    let am = WriterAddressingMode::new();
+    let write_ref = am.write_ref();
+    Sax::execute(&mut cpu, am);
+    assert_eq!(0b1000_1000, write_ref.get());
+}
+
+#[test]
+fn shx_no_page_cross() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.x = 0xff;
+    cpu.registers.y = 0x01;
+    cpu.interconnect.store_many(0x200, &[0x9e, 0x00, 0x44]);
+    cpu.step();
+
+    // X is ANDed with the high byte of the base address plus one
+    assert_eq!(0x45, cpu.interconnect.read(0x4401));
+}
+
+#[test]
+fn shx_page_cross() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.x = 0x0f;
+    cpu.registers.y = 0x01;
+    cpu.interconnect.store_many(0x200, &[0x9e, 0xff, 0x44]);
+    cpu.step();
+
+    // The written value replaces the high byte of the target address
+    assert_eq!(0x05, cpu.interconnect.read(0x0500));
+    assert_eq!(0x00, cpu.interconnect.read(0x4500));
+}
+
+#[test]
+fn shy() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.x = 0x01;
+    cpu.registers.y = 0xff;
+    cpu.interconnect.store_many(0x200, &[0x9c, 0x00, 0x44]);
+    cpu.step();
+    assert_eq!(0x45, cpu.interconnect.read(0x4401));
+}
+
+#[test]
+fn sha() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0xf3;
+    cpu.registers.x = 0x3f;
+    cpu.registers.y = 0x01;
+    cpu.interconnect.store_many(0x200, &[0x9f, 0x00, 0x44]);
+    cpu.step();
+    assert_eq!(0x33 & 0x45, cpu.interconnect.read(0x4401));
+}
+
+#[test]
+fn slo() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0b0000_0001;
+    let am = WriterAddressingMode::with_read_value(0b1000_0010);
+    let write_ref = am.write_ref();
+    Slo::execute(&mut cpu, am);
+    assert_eq!(0b0000_0100, write_ref.get());
+    assert_eq!(0b0000_0101, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+}
+
+#[test]
+fn sre() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0b1111_0000;
+    let am = WriterAddressingMode::with_read_value(0b0000_0011);
+    let write_ref = am.write_ref();
+    Sre::execute(&mut cpu, am);
+    assert_eq!(0b0000_0001, write_ref.get());
+    assert_eq!(0b1111_0001, cpu.registers.acc);
+    assert_eq!(true, cpu.registers.carry_flag());
+    assert_eq!(true, cpu.registers.sign_flag());
+}
+
+#[test]
+fn tas() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0xf3;
+    cpu.registers.x = 0x3f;
+    cpu.registers.y = 0x01;
+    cpu.interconnect.store_many(0x200, &[0x9b, 0x00, 0x44]);
+    cpu.step();
+    assert_eq!(0x33, cpu.registers.sp);
+    assert_eq!(0x33 & 0x45, cpu.interconnect.read(0x4401));
+}
@@ -0,0 +1,227 @@
+//! Runs single-instruction test vectors in the JSON format of the SingleStepTests/ProcessorTests
+//! suites, checking registers, memory and every bus cycle, dummy cycles included.
+//!
+//! The vectors in `test_roms/processor_tests` run by default. They are regression fixtures made in
+//! this repository rather than reference data: `nmos6502.json` is generated by `generate.py` from
+//! a model written independently of the emulator, and `cpu6502.json` holds hand-written edge
+//! cases. To check against the actual suite, point `PROCESSOR_TESTS_DIR` at its `6502/v1`
+//! directory and run the ignored tests.
+
+use crate::cpu::{
+    bus_log::{BusLog, BusOperation},
+    disassembler::{disassemble, Mnemonic},
+    test_fixture::TestInterconnect,
+    Cpu, Interconnect, Nmos6502,
+};
+use serde_json::Value;
+use std::{
+    env,
+    fs::{self, File},
+    path::Path,
+};
+
+const VECTORS_DIR: &str = "../test_roms/processor_tests";
+const MAX_REPORTED_FAILURES: usize = 20;
+
+// The break flag and bit 5 only exist on the stack, so they're ignored when comparing status
+const STATUS_STACK_ONLY: u8 = 0x30;
+
+struct TestCase {
+    name: String,
+    initial: Snapshot,
+    expected: Snapshot,
+    cycles: Vec<(u16, u8, BusOperation)>,
+}
+
+impl TestCase {
+    fn parse(json: &Value) -> Self {
+        let cycles = json["cycles"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|cycle| {
+                let op = match cycle[2].as_str().unwrap() {
+                    "read" => BusOperation::Read,
+                    "write" => BusOperation::Write,
+                    op => panic!("Unknown bus operation {}", op),
+                };
+                (
+                    cycle[0].as_u64().unwrap() as u16,
+                    cycle[1].as_u64().unwrap() as u8,
+                    op,
+                )
+            })
+            .collect();
+
+        TestCase {
+            name: json["name"].as_str().unwrap().to_owned(),
+            initial: Snapshot::parse(&json["initial"]),
+            expected: Snapshot::parse(&json["final"]),
+            cycles,
+        }
+    }
+}
+
+struct Snapshot {
+    pc: u16,
+    s: u8,
+    a: u8,
+    x: u8,
+    y: u8,
+    p: u8,
+    ram: Vec<(u16, u8)>,
+}
+
+impl Snapshot {
+    fn parse(json: &Value) -> Self {
+        let byte = |key: &str| json[key].as_u64().unwrap() as u8;
+        let ram = json["ram"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|cell| {
+                (
+                    cell[0].as_u64().unwrap() as u16,
+                    cell[1].as_u64().unwrap() as u8,
+                )
+            })
+            .collect();
+
+        Snapshot {
+            pc: json["pc"].as_u64().unwrap() as u16,
+            s: byte("s"),
+            a: byte("a"),
+            x: byte("x"),
+            y: byte("y"),
+            p: byte("p"),
+            ram,
+        }
+    }
+}
+
+#[test]
+fn vectors() {
+    run_dir(Path::new(VECTORS_DIR));
+}
+
+#[test]
+#[ignore]
+fn full_suite() {
+    let dir = env::var("PROCESSOR_TESTS_DIR").expect("PROCESSOR_TESTS_DIR must be set");
+    run_dir(Path::new(&dir));
+}
+
+fn run_dir(dir: &Path) {
+    let mut paths = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert!(!paths.is_empty(), "No test vectors in {}", dir.display());
+
+    let mut failures = Vec::new();
+    let mut total = 0;
+    for path in paths {
+        let json: Value = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+        for test in json.as_array().unwrap().iter().map(TestCase::parse) {
+            if let Err(reason) = run_test(&test) {
+                failures.push(format!("{}: {}", test.name, reason));
+            }
+            total += 1;
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} tests failed\n{}",
+        failures.len(),
+        total,
+        failures[..failures.len().min(MAX_REPORTED_FAILURES)].join("\n")
+    );
+}
+
+fn run_test(test: &TestCase) -> Result<(), String> {
+    let mut mem = TestInterconnect::default();
+    for &(addr, value) in &test.initial.ram {
+        mem.write(addr, value);
+    }
+
+    let mut cpu = Cpu::with_variant(BusLog::new(mem), test.initial.pc, Nmos6502);
+    cpu.registers.sp = test.initial.s;
+    cpu.registers.acc = test.initial.a;
+    cpu.registers.x = test.initial.x;
+    cpu.registers.y = test.initial.y;
+    cpu.registers.status = test.initial.p | STATUS_STACK_ONLY;
+
+    // The suites don't model the lock up caused by the JAM opcodes
+    if disassemble(&cpu.interconnect, test.initial.pc).mnemonic() == Mnemonic::Kil {
+        return Ok(());
+    }
+
+    let start = cpu.interconnect.elapsed_cycles();
+    cpu.step();
+    let bus_cycles = cpu.interconnect.take_cycles();
+
+    let expected_cycles = &test.cycles;
+    let actual_cycles = bus_cycles
+        .iter()
+        .map(|c| (c.address, c.value, c.operation))
+        .collect::<Vec<_>>();
+    if &actual_cycles != expected_cycles {
+        return Err(format!(
+            "Expected bus cycles {:x?}, got {:x?}",
+            expected_cycles, actual_cycles
+        ));
+    }
+
+    // Every cycle must access the bus exactly once
+    let elapsed = cpu.interconnect.elapsed_cycles() - start;
+    let one_per_cycle = bus_cycles
+        .iter()
+        .enumerate()
+        .all(|(i, c)| c.cycle == start + i);
+    if elapsed != bus_cycles.len() || !one_per_cycle {
+        return Err(format!(
+            "Took {} cycles for {} bus accesses",
+            elapsed,
+            bus_cycles.len()
+        ));
+    }
+
+    let expected = &test.expected;
+    let expected_registers = (
+        expected.pc,
+        expected.s,
+        expected.a,
+        expected.x,
+        expected.y,
+        expected.p | STATUS_STACK_ONLY,
+    );
+    let actual_registers = (
+        cpu.registers.pc,
+        cpu.registers.sp,
+        cpu.registers.acc,
+        cpu.registers.x,
+        cpu.registers.y,
+        cpu.registers.status | STATUS_STACK_ONLY,
+    );
+    if actual_registers != expected_registers {
+        return Err(format!(
+            "Expected registers (pc, s, a, x, y, p) {:x?}, got {:x?}",
+            expected_registers, actual_registers
+        ));
+    }
+
+    for &(addr, value) in &expected.ram {
+        let actual = cpu.interconnect.peek(addr);
+        if actual != value {
+            return Err(format!(
+                "Expected {:02x} at {:04x}, got {:02x}",
+                value, addr, actual
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -1,16 +1,120 @@
 use crate::{
     byte_utils::lo_hi,
-    cpu::{test_fixture::TestCpu, *},
+    cpu::{
+        test_fixture::{TestCpu, TestInterconnect},
+        *,
+    },
 };
 
+const IRQ_SOURCE_TEST: u8 = 0x1;
+
+#[test]
+fn power_on() {
+    let mut cpu = TestCpu::new_test();
+    let (addr_low, addr_high) = lo_hi(0xdead);
+    cpu.interconnect.write(RESET_VECTOR, addr_low);
+    cpu.interconnect.write(RESET_VECTOR + 1, addr_high);
+    cpu.registers.acc = 0x12;
+    cpu.registers.sp = 0x80;
+    cpu.registers.status = 0xff;
+    cpu.power_on();
+    assert_eq!(cpu.registers.pc, 0xdead);
+    assert_eq!(0, cpu.registers.acc);
+    assert_eq!(0xfd, cpu.registers.sp);
+    assert_eq!(0x34, cpu.registers.status);
+    assert_eq!(7, cpu.interconnect.elapsed_cycles());
+}
+
 #[test]
-fn reset() {
+fn soft_reset() {
     let mut cpu = TestCpu::new_test();
     let (addr_low, addr_high) = lo_hi(0xdead);
     cpu.interconnect.write(RESET_VECTOR, addr_low);
     cpu.interconnect.write(RESET_VECTOR + 1, addr_high);
-    cpu.reset();
+    cpu.interconnect.write(0x01f0, 0x55);
+    cpu.registers.acc = 0x12;
+    cpu.registers.x = 0x34;
+    cpu.registers.y = 0x56;
+    cpu.registers.sp = 0xf0;
+    cpu.registers.status = 0b1110_0011;
+    cpu.soft_reset();
     assert_eq!(cpu.registers.pc, 0xdead);
+    assert_eq!(0x12, cpu.registers.acc);
+    assert_eq!(0x34, cpu.registers.x);
+    assert_eq!(0x56, cpu.registers.y);
+    assert_eq!(0xed, cpu.registers.sp);
+    assert_eq!(0b1110_0111, cpu.registers.status);
+    assert_eq!(7, cpu.interconnect.elapsed_cycles());
+
+    // The stack is read instead of written
+    assert_eq!(0x55, cpu.interconnect.read(0x01f0));
+}
+
+#[test]
+fn try_step() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(0x200, &[0xea, 0x02]);
+    assert_eq!(Ok(Interrupt::None), cpu.try_step());
+    assert_eq!(None, cpu.fault());
+
+    let fault = Fault::Jammed {
+        pc: 0x201,
+        opcode: 0x02,
+    };
+    assert_eq!(Err(fault), cpu.try_step());
+    assert_eq!(Some(fault), cpu.fault());
+
+    // The clock keeps running while jammed
+    let cycles = cpu.interconnect.elapsed_cycles();
+    assert_eq!(Err(fault), cpu.try_step());
+    assert_eq!(cycles + 1, cpu.interconnect.elapsed_cycles());
+    assert_eq!(0x201, cpu.registers.pc);
+
+    cpu.soft_reset();
+    assert_eq!(None, cpu.fault());
+}
+
+#[test]
+fn jam_at_end_of_memory() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.write(0xffff, 0x02);
+    cpu.registers.pc = 0xffff;
+
+    let fault = Fault::Jammed {
+        pc: 0xffff,
+        opcode: 0x02,
+    };
+    assert_eq!(Err(fault), cpu.try_step());
+    assert_eq!(0xffff, cpu.registers.pc);
+}
+
+#[test]
+fn halt_on_read() {
+    // PHA, NOP
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(0x200, &[0x48, 0xea]);
+
+    // RDY is pulled low on the write cycle of PHA, which isn't halted
+    cpu.interconnect.halt_at = 2;
+    cpu.interconnect.halt_cycles = 3;
+    cpu.step();
+    assert_eq!(3, cpu.interconnect.elapsed_cycles());
+    assert_eq!(true, cpu.interconnect.halted_reads.is_empty());
+
+    // The opcode fetch of NOP is halted, and the halted cycles repeat its address
+    cpu.step();
+    assert_eq!(vec![0x201; 3], cpu.interconnect.halted_reads);
+    assert_eq!(8, cpu.interconnect.elapsed_cycles());
+    assert_eq!(0x202, cpu.registers.pc);
+}
+
+#[test]
+fn fault_display() {
+    let fault = Fault::Jammed {
+        pc: 0xc123,
+        opcode: 0x02,
+    };
+    assert_eq!("CPU jammed by opcode $02 at $C123", fault.to_string());
 }
 
 #[test]
@@ -68,3 +172,272 @@ fn pop_stack16() {
     assert_eq!(0xff, cpu.registers.sp);
     assert_eq!(0xf00d, val);
 }
+
+#[test]
+fn irq() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(BREAK_VECTOR, &[0xad, 0xde]);
+    cpu.interconnect.store_many(0x200, &[0xea]);
+    cpu.registers.set_interrupt_disable_flag(false);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    let interrupt = cpu.step();
+    assert_eq!(true, interrupt == Interrupt::Irq);
+    assert_eq!(0xdead, cpu.registers.pc);
+    assert_eq!(true, cpu.registers.interrupt_disable_flag());
+
+    // NOP takes 2 cycles and the interrupt sequence takes 7
+    assert_eq!(9, cpu.interconnect.elapsed_cycles());
+    assert_eq!(0x201, cpu.read_memory16(0x1fc));
+}
+
+#[test]
+fn irq_during_brk() {
+    // BRK sets the interrupt disable flag, so an IRQ asserted while it runs isn't taken until the
+    // handler clears it
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(BREAK_VECTOR, &[0xad, 0xde]);
+    cpu.interconnect.store_many(0x200, &[0x00, 0x00]);
+    cpu.registers.set_interrupt_disable_flag(false);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    cpu.registers.sp = 0xfd;
+    let interrupt = cpu.step();
+    assert_eq!(true, interrupt == Interrupt::None);
+    assert_eq!(0xdead, cpu.registers.pc);
+    assert_eq!(0xfa, cpu.registers.sp);
+    assert_eq!(0x202, cpu.read_memory16(0x1fc));
+}
+
+#[test]
+fn irq_masked() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(0x200, &[0xea]);
+    cpu.registers.set_interrupt_disable_flag(true);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    let interrupt = cpu.step();
+    assert_eq!(true, interrupt == Interrupt::None);
+    assert_eq!(0x201, cpu.registers.pc);
+}
+
+#[test]
+fn irq_line_released() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(0x200, &[0xea]);
+    cpu.registers.set_interrupt_disable_flag(false);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    cpu.interconnect.irq_line.assert(0x2);
+    cpu.interconnect.irq_line.release(IRQ_SOURCE_TEST);
+    assert_eq!(true, cpu.interconnect.irq_line.is_asserted());
+    cpu.interconnect.irq_line.release(0x2);
+    let interrupt = cpu.step();
+    assert_eq!(true, interrupt == Interrupt::None);
+    assert_eq!(0x201, cpu.registers.pc);
+}
+
+#[test]
+fn irq_delayed_after_cli() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(BREAK_VECTOR, &[0xad, 0xde]);
+    cpu.interconnect.store_many(0x200, &[0x58, 0xea]);
+    cpu.registers.set_interrupt_disable_flag(true);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+
+    // The instruction following CLI always executes before the interrupt is taken
+    assert_eq!(true, cpu.step() == Interrupt::None);
+    assert_eq!(0x201, cpu.registers.pc);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+    assert_eq!(0xdead, cpu.registers.pc);
+}
+
+#[test]
+fn irq_taken_after_sei() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(BREAK_VECTOR, &[0xad, 0xde]);
+    cpu.interconnect.store_many(0x200, &[0x78]);
+    cpu.registers.set_interrupt_disable_flag(false);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+
+    // SEI sets the flag after interrupts have been polled, so the interrupt is still taken. The
+    // status pushed to the stack has the interrupt disable flag set.
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+    assert_eq!(0xdead, cpu.registers.pc);
+    assert_eq!(true, cpu.interconnect.read(0x1fb) & 0b0000_0100 != 0);
+}
+
+#[test]
+fn irq_delayed_after_plp() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(BREAK_VECTOR, &[0xad, 0xde]);
+    cpu.interconnect.store_many(0x200, &[0x28, 0xea]);
+    cpu.registers.set_interrupt_disable_flag(true);
+    cpu.registers.sp = 0xfe;
+    cpu.interconnect.write(0x1ff, 0);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    assert_eq!(true, cpu.step() == Interrupt::None);
+    assert_eq!(0x201, cpu.registers.pc);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+    assert_eq!(0xdead, cpu.registers.pc);
+}
+
+#[test]
+fn irq_taken_immediately_after_rti() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(BREAK_VECTOR, &[0xad, 0xde]);
+    cpu.interconnect.store_many(0x200, &[0x40]);
+    cpu.registers.set_interrupt_disable_flag(true);
+    cpu.registers.sp = 0xfc;
+    cpu.interconnect.store_many(0x1fd, &[0x00, 0x34, 0x12]);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+    assert_eq!(0xdead, cpu.registers.pc);
+    assert_eq!(0x1234, cpu.read_memory16(0x1fe));
+}
+
+#[test]
+fn nmi_takes_priority_over_irq() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(NMI_VECTOR, &[0xef, 0xbe]);
+    cpu.interconnect.store_many(0x200, &[0xea]);
+    cpu.interconnect.irq_line.assert(IRQ_SOURCE_TEST);
+    cpu.pending_interrupt = Interrupt::Nmi;
+    assert_eq!(true, cpu.step() == Interrupt::Nmi);
+    assert_eq!(0xbeef, cpu.registers.pc);
+}
+
+/// Asserts the IRQ line once the given cycle has elapsed, so that tests can control which of an
+/// instruction's cycles first observes the interrupt.
+struct DelayedIrqInterconnect {
+    inner: TestInterconnect,
+    irq_cycle: usize,
+}
+
+impl Interconnect for DelayedIrqInterconnect {
+    fn read(&self, address: u16) -> u8 {
+        self.inner.read(address)
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.inner.write(address, value)
+    }
+
+    fn tick(&mut self) -> Interrupt {
+        let interrupt = self.inner.tick();
+        if self.inner.elapsed_cycles() >= self.irq_cycle {
+            self.inner.irq_line.assert(IRQ_SOURCE_TEST);
+        }
+        interrupt
+    }
+
+    fn elapsed_cycles(&self) -> usize {
+        self.inner.elapsed_cycles()
+    }
+
+    fn irq_asserted(&self) -> bool {
+        self.inner.irq_asserted()
+    }
+}
+
+fn new_delayed_irq_cpu(pc: u16, program: &[u8], irq_cycle: usize) -> Cpu<DelayedIrqInterconnect> {
+    let mut inner = TestInterconnect::default();
+    inner.store_many(BREAK_VECTOR, &[0xad, 0xde]);
+    inner.store_many(pc, program);
+    let mut cpu = Cpu::new(DelayedIrqInterconnect { inner, irq_cycle }, pc);
+    cpu.registers.set_interrupt_disable_flag(false);
+    cpu
+}
+
+#[test]
+fn irq_polled_on_second_to_last_cycle() {
+    // LDA zero page takes 3 cycles
+    let mut cpu = new_delayed_irq_cpu(0x200, &[0xa5, 0x00], 2);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+
+    let mut cpu = new_delayed_irq_cpu(0x200, &[0xa5, 0x00, 0xea], 3);
+    assert_eq!(true, cpu.step() == Interrupt::None);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+}
+
+#[test]
+fn irq_not_polled_by_taken_branch_without_page_cross() {
+    // BEQ with zero offset takes 3 cycles, but only polls interrupts before the operand fetch
+    let mut cpu = new_delayed_irq_cpu(0x200, &[0xf0, 0x00, 0xea], 2);
+    cpu.registers.set_zero_flag(true);
+    assert_eq!(true, cpu.step() == Interrupt::None);
+    assert_eq!(0x202, cpu.registers.pc);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+
+    let mut cpu = new_delayed_irq_cpu(0x200, &[0xf0, 0x00], 1);
+    cpu.registers.set_zero_flag(true);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+}
+
+#[test]
+fn irq_polled_by_taken_branch_with_page_cross() {
+    // A taken branch that crosses a page takes 4 cycles and polls before the high byte fixup
+    let mut cpu = new_delayed_irq_cpu(0x2fd, &[0xf0, 0x10], 3);
+    cpu.registers.set_zero_flag(true);
+    assert_eq!(true, cpu.step() == Interrupt::Irq);
+    assert_eq!(0x30f, cpu.read_memory16(0x1fc));
+}
+
+#[test]
+fn state() {
+    let mut cpu = TestCpu::new_test();
+    cpu.registers.acc = 0x11;
+    cpu.registers.x = 0x22;
+    cpu.registers.y = 0x33;
+    cpu.registers.sp = 0x44;
+    cpu.registers.set_carry_flag(true);
+    cpu.pending_interrupt = Interrupt::Nmi;
+    let state = cpu.state();
+    assert_eq!(0x200, state.pc());
+    assert_eq!(0x11, state.acc());
+    assert_eq!(0x22, state.x());
+    assert_eq!(0x33, state.y());
+    assert_eq!(0x44, state.sp());
+
+    // Interrupt disable, carry and the unused bit are set
+    assert_eq!(0x25, state.status());
+    assert_eq!(Interrupt::Nmi, state.pending_interrupt());
+    assert_eq!(false, state.jammed());
+}
+
+#[test]
+fn set_state() {
+    let mut cpu = TestCpu::new_test();
+    let mut state = cpu.state();
+    state.set_pc(0x300);
+    state.set_acc(0x80);
+    state.set_x(0x01);
+    state.set_y(0x02);
+    state.set_sp(0xf0);
+    state.set_status(0b1100_0011);
+    state.set_jammed(true);
+    cpu.set_state(state);
+
+    assert_eq!(0x300, cpu.registers.pc);
+    assert_eq!(0x80, cpu.registers.acc);
+    assert_eq!(0x01, cpu.registers.x);
+    assert_eq!(0x02, cpu.registers.y);
+    assert_eq!(0xf0, cpu.registers.sp);
+    assert_eq!(true, cpu.registers.sign_flag());
+    assert_eq!(true, cpu.registers.overflow_flag());
+    assert_eq!(true, cpu.registers.zero_flag());
+    assert_eq!(true, cpu.registers.carry_flag());
+    assert_eq!(false, cpu.registers.interrupt_disable_flag());
+    assert_eq!(true, cpu.jammed);
+
+    // The unused bit is always set
+    assert_eq!(0b1110_0011, cpu.state().status());
+}
+
+#[test]
+fn set_state_restores_pending_interrupt() {
+    let mut cpu = TestCpu::new_test();
+    cpu.interconnect.store_many(NMI_VECTOR, &[0xef, 0xbe]);
+    cpu.interconnect.store_many(0x200, &[0xea]);
+    let mut state = cpu.state();
+    state.set_pending_interrupt(Interrupt::Nmi);
+    cpu.set_state(state);
+    assert_eq!(Interrupt::Nmi, cpu.step());
+    assert_eq!(0xbeef, cpu.registers.pc);
+}
@@ -0,0 +1,138 @@
+use crate::cpu::{Cpu, Interconnect, Interrupt, Variant};
+
+/// A snapshot of everything needed to suspend and resume a CPU
+///
+/// Besides the registers, this includes the interrupt bookkeeping that spans instruction
+/// boundaries, so that restoring a snapshot reproduces interrupt timing exactly.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CpuState {
+    pc: u16,
+    sp: u8,
+    acc: u8,
+    x: u8,
+    y: u8,
+    status: u8,
+    pending_interrupt: Interrupt,
+    irq_poll: bool,
+    irq_pending: bool,
+    jammed: bool,
+}
+
+impl CpuState {
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    pub fn set_pc(&mut self, pc: u16) {
+        self.pc = pc;
+    }
+
+    pub fn sp(&self) -> u8 {
+        self.sp
+    }
+
+    pub fn set_sp(&mut self, sp: u8) {
+        self.sp = sp;
+    }
+
+    pub fn acc(&self) -> u8 {
+        self.acc
+    }
+
+    pub fn set_acc(&mut self, acc: u8) {
+        self.acc = acc;
+    }
+
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    pub fn set_x(&mut self, x: u8) {
+        self.x = x;
+    }
+
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    pub fn set_y(&mut self, y: u8) {
+        self.y = y;
+    }
+
+    /// The processor status as it would be pushed by an interrupt, with the break flag clear
+    pub fn status(&self) -> u8 {
+        self.status
+    }
+
+    /// Sets the processor status. As with `PLP`, the break and unused bits are ignored.
+    pub fn set_status(&mut self, status: u8) {
+        self.status = status;
+    }
+
+    /// The edge-triggered interrupt (NMI) waiting to be serviced after the current instruction
+    pub fn pending_interrupt(&self) -> Interrupt {
+        self.pending_interrupt
+    }
+
+    pub fn set_pending_interrupt(&mut self, interrupt: Interrupt) {
+        self.pending_interrupt = interrupt;
+    }
+
+    /// Whether the IRQ line was asserted and unmasked when it was last sampled
+    pub fn irq_poll(&self) -> bool {
+        self.irq_poll
+    }
+
+    pub fn set_irq_poll(&mut self, irq_poll: bool) {
+        self.irq_poll = irq_poll;
+    }
+
+    /// Whether an IRQ will be taken at the end of the current instruction. This is the result of
+    /// the poll preceding the most recent one.
+    pub fn irq_pending(&self) -> bool {
+        self.irq_pending
+    }
+
+    pub fn set_irq_pending(&mut self, irq_pending: bool) {
+        self.irq_pending = irq_pending;
+    }
+
+    /// Whether the CPU has been locked up by a `JAM` opcode
+    pub fn jammed(&self) -> bool {
+        self.jammed
+    }
+
+    pub fn set_jammed(&mut self, jammed: bool) {
+        self.jammed = jammed;
+    }
+}
+
+impl<I: Interconnect, V: Variant> Cpu<I, V> {
+    pub fn state(&self) -> CpuState {
+        CpuState {
+            pc: self.registers.pc,
+            sp: self.registers.sp,
+            acc: self.registers.acc,
+            x: self.registers.x,
+            y: self.registers.y,
+            status: self.registers.status_sans_break(),
+            pending_interrupt: self.pending_interrupt,
+            irq_poll: self.irq_poll,
+            irq_pending: self.irq_pending,
+            jammed: self.jammed,
+        }
+    }
+
+    pub fn set_state(&mut self, state: CpuState) {
+        self.registers.pc = state.pc;
+        self.registers.sp = state.sp;
+        self.registers.acc = state.acc;
+        self.registers.x = state.x;
+        self.registers.y = state.y;
+        self.registers.set_status_from_stack(state.status);
+        self.pending_interrupt = state.pending_interrupt;
+        self.irq_poll = state.irq_poll;
+        self.irq_pending = state.irq_pending;
+        self.jammed = state.jammed;
+    }
+}
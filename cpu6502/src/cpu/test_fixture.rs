@@ -1,10 +1,15 @@
 use crate::cpu::Cpu;
 
-use crate::cpu::{Interconnect, Interrupt, ADDRESSABLE_MEMORY};
+use crate::cpu::{Interconnect, Interrupt, IrqLine, Nmos6502, ADDRESSABLE_MEMORY};
 
 pub struct TestInterconnect {
     addr: [u8; ADDRESSABLE_MEMORY],
     elapsed_cycles: usize,
+    pub irq_line: IrqLine,
+    /// The cycle from which RDY is pulled low, and for how many halted cycles
+    pub halt_at: usize,
+    pub halt_cycles: usize,
+    pub halted_reads: Vec<u16>,
 }
 
 impl TestInterconnect {
@@ -12,6 +17,10 @@ impl TestInterconnect {
         TestInterconnect {
             addr: [0; ADDRESSABLE_MEMORY],
             elapsed_cycles: 0,
+            irq_line: IrqLine::default(),
+            halt_at: 0,
+            halt_cycles: 0,
+            halted_reads: Vec::new(),
         }
     }
 
@@ -47,6 +56,19 @@ impl Interconnect for TestInterconnect {
     fn elapsed_cycles(&self) -> usize {
         self.elapsed_cycles
     }
+
+    fn irq_asserted(&self) -> bool {
+        self.irq_line.is_asserted()
+    }
+
+    fn halt_requested(&self) -> bool {
+        self.halt_cycles > 0 && self.elapsed_cycles >= self.halt_at
+    }
+
+    fn halted_cycle(&mut self, addr: u16) {
+        self.halt_cycles -= 1;
+        self.halted_reads.push(addr);
+    }
 }
 
 pub type TestCpu = Cpu<TestInterconnect>;
@@ -57,3 +79,12 @@ impl Cpu<TestInterconnect> {
         Cpu::new(interconnect, 0x200)
     }
 }
+
+pub type NmosTestCpu = Cpu<TestInterconnect, Nmos6502>;
+
+impl Cpu<TestInterconnect, Nmos6502> {
+    pub fn new_nmos_test() -> Self {
+        let interconnect = TestInterconnect::default();
+        Cpu::with_variant(interconnect, 0x200, Nmos6502)
+    }
+}
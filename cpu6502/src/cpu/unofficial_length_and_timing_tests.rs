@@ -0,0 +1,287 @@
+use crate::cpu::{test_fixture::TestCpu, Interconnect};
+
+/// # Executes a test fixture that asserts bytes read and cycles executed
+///
+/// The assembler used by `length_and_timing_tests` doesn't know the unofficial mnemonics, so the
+/// instructions are given as raw bytes. The CPU fixture is created with the same state:
+///
+///   * The program counter is set to 0x200
+///
+///   * X and Y registers are set to 0x1 to accommodate page crossing for indexed addressing modes
+///
+///   * Memory location 0x55 contains the indirect address 0x33ff to accommodate testing page
+///     crossing for indirect indexed addressing modes
+///
+macro_rules! assert_length_and_cycles {
+    ($bytes:expr, $expected_len:expr, $expected_cycles:expr) => {{
+        let mut cpu = TestCpu::new_test();
+        cpu.registers.x = 1;
+        cpu.registers.y = 1;
+        cpu.interconnect.store_many(0x55, &[0xff, 0x33]);
+        cpu.interconnect.store_many(0x200, &$bytes);
+        let expected_cycles = $expected_cycles;
+        let expected_len = $expected_len;
+        cpu.step();
+        let actual_len = cpu.registers.pc - 0x200;
+
+        if expected_len != actual_len {
+            panic!(
+                "Expected instruction length is {} but it was {}",
+                expected_len, actual_len
+            )
+        }
+
+        if expected_cycles != cpu.interconnect.elapsed_cycles() {
+            panic!(
+                "Expected number of executed cycles to be {} but it was {}",
+                expected_cycles,
+                cpu.interconnect.elapsed_cycles()
+            )
+        }
+    }};
+}
+
+/// Asserts the timing shared by all of the unofficial read-modify-write instructions, given the
+/// opcode for the (Indirect,X) addressing mode. The remaining opcodes are at fixed offsets.
+macro_rules! assert_rmw_length_and_cycles {
+    ($base_opcode:expr) => {{
+        let base = $base_opcode;
+
+        // Zero Page
+        assert_length_and_cycles!([base + 0x04, 0x44], 2, 5);
+
+        // Zero Page,X
+        assert_length_and_cycles!([base + 0x14, 0x44], 2, 6);
+
+        // Absolute
+        assert_length_and_cycles!([base + 0x0c, 0x00, 0x44], 3, 6);
+
+        // Absolute,X
+        assert_length_and_cycles!([base + 0x1c, 0x00, 0x44], 3, 7);
+
+        // Absolute,X with page cross
+        assert_length_and_cycles!([base + 0x1c, 0xff, 0x44], 3, 7);
+
+        // Absolute,Y
+        assert_length_and_cycles!([base + 0x18, 0x00, 0x44], 3, 7);
+
+        // Absolute,Y with page cross
+        assert_length_and_cycles!([base + 0x18, 0xff, 0x44], 3, 7);
+
+        // Indirect,X
+        assert_length_and_cycles!([base, 0x44], 2, 8);
+
+        // Indirect,Y
+        assert_length_and_cycles!([base + 0x10, 0x44], 2, 8);
+
+        // Indirect,Y with page cross
+        assert_length_and_cycles!([base + 0x10, 0x55], 2, 8);
+    }};
+}
+
+#[test]
+fn alr() {
+    // Immediate
+    assert_length_and_cycles!([0x4b, 0x44], 2, 2);
+}
+
+#[test]
+fn anc() {
+    // Immediate
+    assert_length_and_cycles!([0x0b, 0x44], 2, 2);
+    assert_length_and_cycles!([0x2b, 0x44], 2, 2);
+}
+
+#[test]
+fn ane() {
+    // Immediate
+    assert_length_and_cycles!([0x8b, 0x44], 2, 2);
+}
+
+#[test]
+fn arr() {
+    // Immediate
+    assert_length_and_cycles!([0x6b, 0x44], 2, 2);
+}
+
+#[test]
+fn axs() {
+    // Immediate
+    assert_length_and_cycles!([0xcb, 0x44], 2, 2);
+}
+
+#[test]
+fn dcp() {
+    assert_rmw_length_and_cycles!(0xc3);
+}
+
+#[test]
+fn isc() {
+    assert_rmw_length_and_cycles!(0xe3);
+}
+
+#[test]
+fn kil() {
+    for opcode in &[
+        0x02, 0x12, 0x22, 0x32, 0x42, 0x52, 0x62, 0x72, 0x92, 0xb2, 0xd2, 0xf2,
+    ] {
+        // The program counter never moves past a jammed opcode
+        assert_length_and_cycles!([*opcode], 0, 2);
+    }
+}
+
+#[test]
+fn las() {
+    // Absolute,Y
+    assert_length_and_cycles!([0xbb, 0x00, 0x44], 3, 4);
+
+    // Absolute,Y with page cross
+    assert_length_and_cycles!([0xbb, 0xff, 0x44], 3, 5);
+}
+
+#[test]
+fn lax() {
+    // Immediate
+    assert_length_and_cycles!([0xab, 0x44], 2, 2);
+
+    // Zero Page
+    assert_length_and_cycles!([0xa7, 0x44], 2, 3);
+
+    // Zero Page,Y
+    assert_length_and_cycles!([0xb7, 0x44], 2, 4);
+
+    // Absolute
+    assert_length_and_cycles!([0xaf, 0x00, 0x44], 3, 4);
+
+    // Absolute,Y
+    assert_length_and_cycles!([0xbf, 0x00, 0x44], 3, 4);
+
+    // Absolute,Y with page cross
+    assert_length_and_cycles!([0xbf, 0xff, 0x44], 3, 5);
+
+    // Indirect,X
+    assert_length_and_cycles!([0xa3, 0x44], 2, 6);
+
+    // Indirect,Y
+    assert_length_and_cycles!([0xb3, 0x44], 2, 5);
+
+    // Indirect,Y with page cross
+    assert_length_and_cycles!([0xb3, 0x55], 2, 6);
+}
+
+#[test]
+fn nop() {
+    // Implied
+    for opcode in &[0x1a, 0x3a, 0x5a, 0x7a, 0xda, 0xfa] {
+        assert_length_and_cycles!([*opcode], 1, 2);
+    }
+
+    // Immediate
+    for opcode in &[0x80, 0x82, 0x89, 0xc2, 0xe2] {
+        assert_length_and_cycles!([*opcode, 0x44], 2, 2);
+    }
+
+    // Zero Page
+    for opcode in &[0x04, 0x44, 0x64] {
+        assert_length_and_cycles!([*opcode, 0x44], 2, 3);
+    }
+
+    // Zero Page,X
+    for opcode in &[0x14, 0x34, 0x54, 0x74, 0xd4, 0xf4] {
+        assert_length_and_cycles!([*opcode, 0x44], 2, 4);
+    }
+
+    // Absolute
+    assert_length_and_cycles!([0x0c, 0x00, 0x44], 3, 4);
+
+    for opcode in &[0x1c, 0x3c, 0x5c, 0x7c, 0xdc, 0xfc] {
+        // Absolute,X
+        assert_length_and_cycles!([*opcode, 0x00, 0x44], 3, 4);
+
+        // Absolute,X with page cross
+        assert_length_and_cycles!([*opcode, 0xff, 0x44], 3, 5);
+    }
+}
+
+#[test]
+fn rla() {
+    assert_rmw_length_and_cycles!(0x23);
+}
+
+#[test]
+fn rra() {
+    assert_rmw_length_and_cycles!(0x63);
+}
+
+#[test]
+fn sax() {
+    // Zero Page
+    assert_length_and_cycles!([0x87, 0x44], 2, 3);
+
+    // Zero Page,Y
+    assert_length_and_cycles!([0x97, 0x44], 2, 4);
+
+    // Absolute
+    assert_length_and_cycles!([0x8f, 0x00, 0x44], 3, 4);
+
+    // Indirect,X
+    assert_length_and_cycles!([0x83, 0x44], 2, 6);
+}
+
+#[test]
+fn sbc() {
+    // Immediate
+    assert_length_and_cycles!([0xeb, 0x44], 2, 2);
+}
+
+#[test]
+fn sha() {
+    // Absolute,Y
+    assert_length_and_cycles!([0x9f, 0x00, 0x44], 3, 5);
+
+    // Absolute,Y with page cross
+    assert_length_and_cycles!([0x9f, 0xff, 0x44], 3, 5);
+
+    // Indirect,Y
+    assert_length_and_cycles!([0x93, 0x44], 2, 6);
+
+    // Indirect,Y with page cross
+    assert_length_and_cycles!([0x93, 0x55], 2, 6);
+}
+
+#[test]
+fn shx() {
+    // Absolute,Y
+    assert_length_and_cycles!([0x9e, 0x00, 0x44], 3, 5);
+
+    // Absolute,Y with page cross
+    assert_length_and_cycles!([0x9e, 0xff, 0x44], 3, 5);
+}
+
+#[test]
+fn shy() {
+    // Absolute,X
+    assert_length_and_cycles!([0x9c, 0x00, 0x44], 3, 5);
+
+    // Absolute,X with page cross
+    assert_length_and_cycles!([0x9c, 0xff, 0x44], 3, 5);
+}
+
+#[test]
+fn slo() {
+    assert_rmw_length_and_cycles!(0x03);
+}
+
+#[test]
+fn sre() {
+    assert_rmw_length_and_cycles!(0x43);
+}
+
+#[test]
+fn tas() {
+    // Absolute,Y
+    assert_length_and_cycles!([0x9b, 0x00, 0x44], 3, 5);
+
+    // Absolute,Y with page cross
+    assert_length_and_cycles!([0x9b, 0xff, 0x44], 3, 5);
+}
@@ -23,7 +23,7 @@ const INC_COARSE_X: u32 = 1 << 8;
 const INC_FINE_Y: u32 = 1 << 9;
 const HORI_V_EQ_HORI_T: u32 = 1 << 10;
 const SET_VBLANK: u32 = 1 << 11;
-const CLEAR_VBLANK_AND_SPRITE_ZERO_HIT: u32 = 1 << 12;
+const CLEAR_STATUS_FLAGS: u32 = 1 << 12;
 const VERT_V_EQ_VERT_T: u32 = 1 << 13;
 const ODD_FRAME_SKIP_CYCLE: u32 = 1 << 14;
 const FRAME_INC: u32 = 1 << 15;
@@ -55,7 +55,7 @@ fn ppu_loop_impl() -> proc_macro2::TokenStream {
             // Check for specific cycle actions
             match (x, scanline) {
                 (1, VBLANK_SCANLINE) => cycle_type |= SET_VBLANK,
-                (1, LAST_SCANLINE) => cycle_type |= CLEAR_VBLANK_AND_SPRITE_ZERO_HIT,
+                (1, LAST_SCANLINE) => cycle_type |= CLEAR_STATUS_FLAGS,
                 (339, LAST_SCANLINE) => cycle_type |= ODD_FRAME_SKIP_CYCLE,
                 (340, LAST_SCANLINE) => cycle_type |= FRAME_INC,
                 (..) => (),
@@ -346,16 +346,21 @@ fn actions(cycle_type: u32) -> Vec<Action> {
 
     if cycle_type & TICK_SPRITE_EVALUATION > 0 {
         let lines = quote! {
-            self.sprite_renderer.tick_sprite_evaluation();
+            if self.sprite_renderer.tick_sprite_evaluation() {
+                self.status.set_sprite_overflow();
+            }
         };
         actions.push(Action::WhenRenderingEnabled(lines, 100))
     }
 
     if cycle_type & DRAW_PIXEL > 0 {
+        // Pixels are output whether or not rendering is enabled, and the mask decides what shows.
+        // No return expressions come before the rendering actions, so the pixel is drawn before
+        // the shift registers are ticked.
         let lines = quote! {
             self.draw_pixel(x, scanline);
         };
-        actions.push(Action::WhenRenderingEnabled(lines, -10000))
+        actions.push(Action::NoReturnExpression(lines))
     }
 
     if cycle_type & SET_VBLANK > 0 {
@@ -369,13 +374,14 @@ fn actions(cycle_type: u32) -> Vec<Action> {
         };
         actions.push(Action::ReturnExpression(lines))
     }
-    if cycle_type & CLEAR_VBLANK_AND_SPRITE_ZERO_HIT > 0 {
+    if cycle_type & CLEAR_STATUS_FLAGS > 0 {
         let lines = quote! {
             // Updating palettes here isn't accurate, but should suffice for now
             self.background_renderer.update_palettes(self.vram.as_ref());
             self.sprite_renderer.update_palettes(self.vram.as_ref());
             self.status.clear_in_vblank();
             self.status.clear_sprite_zero_hit();
+            self.status.clear_sprite_overflow();
         };
 
         actions.push(Action::NoReturnExpression(lines))
@@ -5,6 +5,10 @@ pub struct ApuMock {
     write_addr: u16,
     write_value: u8,
     control: u8,
+    frame_irq: bool,
+    dmc_irq: bool,
+    dmc_request: Option<u16>,
+    dmc_samples: Vec<u8>,
 }
 
 impl ApuMock {
@@ -19,6 +23,23 @@ impl ApuMock {
     pub fn set_control(&mut self, val: u8) {
         self.control = val;
     }
+
+    pub fn set_frame_irq(&mut self, val: bool) {
+        self.frame_irq = val;
+    }
+
+    pub fn set_dmc_irq(&mut self, val: bool) {
+        self.dmc_irq = val;
+    }
+
+    /// Requests a sample fetch from `addr`, which is cleared once the sample has been fetched
+    pub fn request_dmc_sample(&mut self, addr: u16) {
+        self.dmc_request = Some(addr);
+    }
+
+    pub fn dmc_samples(&self) -> &[u8] {
+        &self.dmc_samples
+    }
 }
 
 impl IApu for ApuMock {
@@ -30,4 +51,25 @@ impl IApu for ApuMock {
     fn read_control(&self) -> u8 {
         self.control
     }
+
+    fn peek_control(&self) -> u8 {
+        self.control
+    }
+
+    fn frame_irq_asserted(&self) -> bool {
+        self.frame_irq
+    }
+
+    fn dmc_irq_asserted(&self) -> bool {
+        self.dmc_irq
+    }
+
+    fn dmc_dma_request(&self) -> Option<u16> {
+        self.dmc_request
+    }
+
+    fn dmc_dma_complete(&mut self, value: u8) {
+        self.dmc_request = None;
+        self.dmc_samples.push(value);
+    }
 }
@@ -9,6 +9,36 @@ pub trait IApu: Default {
     fn read_control(&self) -> u8 {
         0
     }
+
+    /// Returns the value `read_control` would, without acknowledging any interrupts
+    fn peek_control(&self) -> u8 {
+        0
+    }
+
+    /// Whether the frame counter is asserting the IRQ line
+    fn frame_irq_asserted(&self) -> bool {
+        false
+    }
+
+    /// Whether the DMC is asserting the IRQ line
+    fn dmc_irq_asserted(&self) -> bool {
+        false
+    }
+
+    /// The address of the next sample byte, while the DMC's sample buffer is empty and it needs
+    /// one fetched by DMA
+    fn dmc_dma_request(&self) -> Option<u16> {
+        None
+    }
+
+    /// Fills the sample buffer with the byte fetched by DMA
+    fn dmc_dma_complete(&mut self, _: u8) {}
+
+    /// Called when the console is reset. Like a write of `$00` to `$4015`, a reset silences every
+    /// channel.
+    fn reset(&mut self) {
+        self.write(0x4015, 0);
+    }
 }
 
 impl IApu for Apu {}
@@ -2,10 +2,10 @@ extern crate cpu6502;
 extern crate rs_nes;
 extern crate sdl2;
 
-use cpu6502::cpu::{Cpu, Interrupt};
+use cpu6502::cpu::{Cpu, Fault, Interconnect, Interrupt};
 use rs_nes::{
-    load_cart, Apu, Button, Cart, IInput, IPpu, Input, NesInterconnect, NesRom, Nrom128, Nrom256,
-    Ppu, SpriteRenderer, Uxrom, Vram,
+    load_cart, Apu, Button, Cart, FrameBuffer, IInput, IPpu, Input, NesInterconnect, NesRom,
+    Nrom128, Nrom256, Palette, Ppu, SpriteRenderer, Uxrom, Vram,
 };
 use sdl2::{event::Event, keyboard::Keycode, pixels::PixelFormatEnum};
 use std::{
@@ -19,22 +19,6 @@ const SCREEN_WIDTH: u32 = 256;
 const SCREEN_HEIGHT: u32 = 240;
 const SCREEN_BUFFER_SIZE: usize = (SCREEN_WIDTH as usize) * (SCREEN_HEIGHT as usize) * 3;
 
-// Represents the NES color palette. Colors are in RGB format, and color indices (i) start at 0 and
-// are every 3 bytes, so R = i, G = i + 1, and B = i + 2
-pub const PALETTE: [u8; 192] = [
-    0x7C, 0x7C, 0x7C, 0x00, 0x00, 0xFC, 0x00, 0x00, 0xBC, 0x44, 0x28, 0xBC, 0x94, 0x00, 0x84, 0xA8,
-    0x00, 0x20, 0xA8, 0x10, 0x00, 0x88, 0x14, 0x00, 0x50, 0x30, 0x00, 0x00, 0x78, 0x00, 0x00, 0x68,
-    0x00, 0x00, 0x58, 0x00, 0x00, 0x40, 0x58, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0xBC, 0xBC, 0xBC, 0x00, 0x78, 0xF8, 0x00, 0x58, 0xF8, 0x68, 0x44, 0xFC, 0xD8, 0x00, 0xCC, 0xE4,
-    0x00, 0x58, 0xF8, 0x38, 0x00, 0xE4, 0x5C, 0x10, 0xAC, 0x7C, 0x00, 0x00, 0xB8, 0x00, 0x00, 0xA8,
-    0x00, 0x00, 0xA8, 0x44, 0x00, 0x88, 0x88, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0xF8, 0xF8, 0xF8, 0x3C, 0xBC, 0xFC, 0x68, 0x88, 0xFC, 0x98, 0x78, 0xF8, 0xF8, 0x78, 0xF8, 0xF8,
-    0x58, 0x98, 0xF8, 0x78, 0x58, 0xFC, 0xA0, 0x44, 0xF8, 0xB8, 0x00, 0xB8, 0xF8, 0x18, 0x58, 0xD8,
-    0x54, 0x58, 0xF8, 0x98, 0x00, 0xE8, 0xD8, 0x78, 0x78, 0x78, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-    0xFC, 0xFC, 0xFC, 0xA4, 0xE4, 0xFC, 0xB8, 0xB8, 0xF8, 0xD8, 0xB8, 0xF8, 0xF8, 0xB8, 0xF8, 0xF8,
-    0xA4, 0xC0, 0xF0, 0xD0, 0xB0, 0xFC, 0xE0, 0xA8, 0xF8, 0xD8, 0x78, 0xD8, 0xF8, 0x78, 0xB8, 0xF8,
-    0xB8, 0xB8, 0xF8, 0xD8, 0x00, 0xFC, 0xFC, 0xF8, 0xD8, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-];
 fn main() {
     // INIT NES
     let rom_path = env::args().last().expect("Unable to determine rom path");
@@ -99,6 +83,8 @@ fn run<C: Cart>(mut cpu: Box<Cpu<NesInterconnect<Ppu<Vram, SpriteRenderer>, Apu,
     let mut previous_clock = Instant::now();
     let fixed_time_stamp = Duration::new(0, 16666667);
     let mut screen_buffer: [u8; SCREEN_BUFFER_SIZE] = [0; SCREEN_BUFFER_SIZE];
+    let mut frame_buffer = FrameBuffer::default();
+    let palette = Palette::default();
     'running: loop {
         let now = Instant::now();
         accumulator += now - previous_clock;
@@ -149,31 +135,16 @@ fn run<C: Cart>(mut cpu: Box<Cpu<NesInterconnect<Ppu<Vram, SpriteRenderer>, Apu,
         while accumulator >= fixed_time_stamp {
             accumulator -= fixed_time_stamp;
             loop {
-                if cpu.step() == Interrupt::Nmi {
-                    let nes_screen_buffer = &*cpu.interconnect.ppu.screen();
-                    for i in 0..SCREEN_WIDTH * SCREEN_HEIGHT {
-                        let i = (i * 3) as usize;
-                        let bg_palette_index = (nes_screen_buffer[i] >> 2) * 3;
-                        let bg_pixel_value = nes_screen_buffer[i] & 0b0000_0011;
-                        let sprite_palette_index = (nes_screen_buffer[i + 1] >> 2) * 3;
-                        let sprite_pixel_value = nes_screen_buffer[i + 1] & 0b0000_0011;
-                        let sprite_has_priority = (nes_screen_buffer[i + 2] & 1) == 1;
-                        let palette_index = match (bg_pixel_value, sprite_pixel_value) {
-                            (0, 0) | (_, 0) => bg_palette_index,
-                            (0, _) => sprite_palette_index,
-                            _ => {
-                                if sprite_has_priority {
-                                    sprite_palette_index
-                                } else {
-                                    bg_palette_index
-                                }
-                            }
-                        } as usize;
-
-                        screen_buffer[i] = PALETTE[palette_index];
-                        screen_buffer[i + 1] = PALETTE[palette_index + 1];
-                        screen_buffer[i + 2] = PALETTE[palette_index + 2];
+                let interrupt = match cpu.try_step() {
+                    Ok(interrupt) => interrupt,
+                    Err(fault) => {
+                        report_fault(&cpu, fault);
+                        break 'running;
                     }
+                };
+                if interrupt == Interrupt::Nmi {
+                    frame_buffer.compose(cpu.interconnect.ppu.screen());
+                    frame_buffer.write_rgb24(&palette, &mut screen_buffer);
                     texture
                         .update(None, &screen_buffer, SCREEN_WIDTH as usize * 3)
                         .expect("unable to update texture");
@@ -189,3 +160,18 @@ fn run<C: Cart>(mut cpu: Box<Cpu<NesInterconnect<Ppu<Vram, SpriteRenderer>, Apu,
         thread::sleep(fixed_time_stamp - accumulator);
     }
 }
+
+fn report_fault<I: Interconnect>(cpu: &Cpu<I>, fault: Fault) {
+    let state = cpu.state();
+    eprintln!("Emulation stopped: {}", fault);
+    eprintln!(
+        "PC:{:04X} A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+        state.pc(),
+        state.acc(),
+        state.x(),
+        state.y(),
+        state.status(),
+        state.sp(),
+        cpu.interconnect.elapsed_cycles()
+    );
+}
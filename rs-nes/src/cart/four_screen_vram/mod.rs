@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod spec_tests;
+
+use crate::{cart::FOUR_SCREEN_VRAM_SIZE, rom::Mirroring};
+
+/// The extra VRAM of a four-screen cart, which is only allocated for ROMs that use four-screen
+/// mirroring. Without it reads return 0 and writes are ignored, like the `Cart` defaults.
+pub struct FourScreenVram {
+    vram: Option<Box<[u8; FOUR_SCREEN_VRAM_SIZE]>>,
+}
+
+impl FourScreenVram {
+    pub fn new(mirroring: Mirroring) -> Self {
+        let vram = if mirroring == Mirroring::FourScreen {
+            Some(Box::new([0; FOUR_SCREEN_VRAM_SIZE]))
+        } else {
+            None
+        };
+        FourScreenVram { vram }
+    }
+
+    pub fn read(&self, offset: usize) -> u8 {
+        self.vram.as_ref().map_or(0, |vram| vram[offset])
+    }
+
+    pub fn write(&mut self, offset: usize, value: u8) {
+        if let Some(vram) = self.vram.as_mut() {
+            vram[offset] = value
+        }
+    }
+}
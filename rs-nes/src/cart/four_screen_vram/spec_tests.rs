@@ -0,0 +1,12 @@
+use crate::{cart::four_screen_vram::FourScreenVram, rom::Mirroring};
+
+#[test]
+fn allocated_for_four_screen() {
+    let mut vram = FourScreenVram::new(Mirroring::FourScreen);
+    vram.write(0x7ff, 0x42);
+    assert_eq!(0x42, vram.read(0x7ff));
+
+    let mut vram = FourScreenVram::new(Mirroring::Vertical);
+    vram.write(0x7ff, 0x42);
+    assert_eq!(0, vram.read(0x7ff));
+}
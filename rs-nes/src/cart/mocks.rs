@@ -1,11 +1,14 @@
 use crate::{
-    cart::Cart,
-    rom::{CHR_BANK_SIZE, PRG_BANK_SIZE},
+    cart::{Cart, FOUR_SCREEN_VRAM_SIZE},
+    rom::{Mirroring, CHR_BANK_SIZE, PRG_BANK_SIZE},
 };
 
 pub struct CartMock {
     pub prg: [u8; PRG_BANK_SIZE],
     pub chr: [u8; CHR_BANK_SIZE],
+    pub irq: bool,
+    pub mirroring: Mirroring,
+    pub vram: [u8; FOUR_SCREEN_VRAM_SIZE],
 }
 
 impl Default for CartMock {
@@ -13,17 +16,22 @@ impl Default for CartMock {
         CartMock {
             prg: [0; PRG_BANK_SIZE],
             chr: [0; CHR_BANK_SIZE],
+            irq: false,
+            mirroring: Mirroring::FourScreen,
+            vram: [0; FOUR_SCREEN_VRAM_SIZE],
         }
     }
 }
 
+// PRG is mirrored across the address space, like NROM-128. Nametables default to four-screen
+// mirroring, so that each has its own memory.
 impl Cart for CartMock {
     fn read_prg(&self, addr: u16) -> u8 {
-        self.prg[addr as usize]
+        self.prg[addr as usize & (PRG_BANK_SIZE - 1)]
     }
 
     fn write_prg(&mut self, addr: u16, value: u8) {
-        self.prg[addr as usize] = value
+        self.prg[addr as usize & (PRG_BANK_SIZE - 1)] = value
     }
 
     fn read_chr(&self, addr: u16) -> u8 {
@@ -33,4 +41,28 @@ impl Cart for CartMock {
     fn write_chr(&mut self, addr: u16, value: u8) {
         self.chr[addr as usize] = value
     }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn read_vram(&self, offset: usize) -> u8 {
+        self.vram[offset]
+    }
+
+    fn write_vram(&mut self, offset: usize, value: u8) {
+        self.vram[offset] = value
+    }
+
+    fn prg_rom_offset(&self, addr: u16) -> Option<usize> {
+        if addr >= 0x8000 {
+            Some(addr as usize & (PRG_BANK_SIZE - 1))
+        } else {
+            None
+        }
+    }
+
+    fn irq_asserted(&self) -> bool {
+        self.irq
+    }
 }
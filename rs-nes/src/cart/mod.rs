@@ -1,15 +1,64 @@
 #[cfg(test)]
 pub mod mocks;
 
+mod four_screen_vram;
 mod nrom128;
 mod nrom256;
 mod uxrom;
 
-pub use self::{nrom128::Nrom128, nrom256::Nrom256, uxrom::Uxrom};
+pub use self::{
+    four_screen_vram::FourScreenVram, nrom128::Nrom128, nrom256::Nrom256, uxrom::Uxrom,
+};
+
+use crate::rom::Mirroring;
+
+/// The size of the extra VRAM on four-screen carts
+pub const FOUR_SCREEN_VRAM_SIZE: usize = 0x800;
 
 pub trait Cart: Sized {
     fn read_prg(&self, addr: u16) -> u8;
     fn write_prg(&mut self, addr: u16, value: u8);
     fn read_chr(&self, addr: u16) -> u8;
     fn write_chr(&mut self, addr: u16, value: u8);
+
+    /// Translates a CPU address into an offset in PRG ROM, using the current bank mapping.
+    /// Addresses that aren't mapped to PRG ROM return `None`.
+    fn prg_rom_offset(&self, addr: u16) -> Option<usize>;
+
+    /// Translates a PPU address into an offset in CHR ROM. Mappers that bank-switch CHR must
+    /// override this.
+    fn chr_rom_offset(&self, addr: u16) -> Option<usize> {
+        if addr < 0x2000 {
+            Some(addr as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The current nametable mirroring, which the PPU consults on every nametable access. Mappers
+    /// that switch mirroring must return the mode they're currently set to.
+    fn mirroring(&self) -> Mirroring;
+
+    /// Reads the extra VRAM of a four-screen cart, which holds the nametables at $2800 and $2C00.
+    /// `offset` is less than `FOUR_SCREEN_VRAM_SIZE`.
+    fn read_vram(&self, _: usize) -> u8 {
+        0
+    }
+
+    fn write_vram(&mut self, _: usize, _: u8) {}
+
+    /// Returns the value `read_prg` would, without any side effects. Mappers that react to PRG
+    /// reads must override this.
+    fn peek_prg(&self, addr: u16) -> u8 {
+        self.read_prg(addr)
+    }
+
+    /// Whether the mapper is asserting the IRQ line
+    fn irq_asserted(&self) -> bool {
+        false
+    }
+
+    /// Called when the console is powered on or reset. Most mappers aren't connected to the reset
+    /// line and keep their registers.
+    fn reset(&mut self) {}
 }
@@ -1,11 +1,13 @@
 use crate::{
-    cart::Cart,
-    rom::{NesRom, CHR_BANK_SIZE, PRG_BANK_SIZE},
+    cart::{Cart, FourScreenVram},
+    rom::{Mirroring, NesRom, CHR_BANK_SIZE, PRG_BANK_SIZE},
 };
 
 pub struct Nrom256 {
     prg_rom: [u8; PRG_BANK_SIZE * 2],
     chr_rom: [u8; CHR_BANK_SIZE],
+    mirroring: Mirroring,
+    vram: FourScreenVram,
 }
 
 impl Nrom256 {
@@ -19,6 +21,8 @@ impl Nrom256 {
             let mut cart = Nrom256 {
                 prg_rom: [0; PRG_BANK_SIZE * 2],
                 chr_rom: [0; CHR_BANK_SIZE],
+                mirroring: rom.mirroring,
+                vram: FourScreenVram::new(rom.mirroring),
             };
             cart.prg_rom.copy_from_slice(&rom.prg);
 
@@ -46,4 +50,24 @@ impl Cart for Nrom256 {
     fn write_chr(&mut self, addr: u16, value: u8) {
         self.chr_rom[addr as usize] = value
     }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn read_vram(&self, offset: usize) -> u8 {
+        self.vram.read(offset)
+    }
+
+    fn write_vram(&mut self, offset: usize, value: u8) {
+        self.vram.write(offset, value)
+    }
+
+    fn prg_rom_offset(&self, addr: u16) -> Option<usize> {
+        if addr >= 0x8000 {
+            Some(addr as usize & 0x7fff)
+        } else {
+            None
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    cart::Cart,
-    rom::{NesRom, CHR_BANK_SIZE, PRG_BANK_SIZE},
+    cart::{Cart, FourScreenVram},
+    rom::{Mirroring, NesRom, CHR_BANK_SIZE, PRG_BANK_SIZE},
 };
 
 pub struct Uxrom {
@@ -8,6 +8,8 @@ pub struct Uxrom {
     chr_rom: [u8; CHR_BANK_SIZE],
     bank_select: u8,
     last_bank: u8,
+    mirroring: Mirroring,
+    vram: FourScreenVram,
 }
 
 impl Uxrom {
@@ -23,6 +25,8 @@ impl Uxrom {
                 chr_rom: [0; CHR_BANK_SIZE],
                 bank_select: 0,
                 last_bank: rom.prg_rom_banks - 1,
+                mirroring: rom.mirroring,
+                vram: FourScreenVram::new(rom.mirroring),
             };
 
             cart.prg_bank.copy_from_slice(&rom.prg);
@@ -55,6 +59,26 @@ impl Cart for Uxrom {
     fn write_chr(&mut self, addr: u16, value: u8) {
         self.chr_rom[addr as usize] = value
     }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn read_vram(&self, offset: usize) -> u8 {
+        self.vram.read(offset)
+    }
+
+    fn write_vram(&mut self, offset: usize, value: u8) {
+        self.vram.write(offset, value)
+    }
+
+    fn prg_rom_offset(&self, addr: u16) -> Option<usize> {
+        if addr >= 0x8000 {
+            Some(resolve_prg_addr(addr, self.bank_select, self.last_bank))
+        } else {
+            None
+        }
+    }
 }
 
 fn resolve_prg_addr(addr: u16, bank_select: u8, last_bank: u8) -> usize {
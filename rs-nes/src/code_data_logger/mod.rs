@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod spec_tests;
+
+use crate::{
+    apu::IApu,
+    cart::Cart,
+    debugger::{access, Access},
+    input::IInput,
+    interconnect::NesInterconnect,
+    ppu::IPpu,
+    rom::{Mirroring, NesRom},
+};
+use cpu6502::cpu::{
+    disassembler::{disassemble, AddressingMode, Mnemonic},
+    Cpu, Interrupt,
+};
+use std::{
+    cell::{Cell, RefCell},
+    io::{self, Read, Write},
+};
+
+/// Executed as an opcode or operand
+pub const PRG_CODE: u8 = 0x01;
+
+/// Read as the memory operand of an instruction
+pub const PRG_DATA: u8 = 0x02;
+
+/// The 8KB window the byte was mapped into when last accessed, where 0 is $8000-$9FFF and 3 is
+/// $E000-$FFFF
+pub const PRG_BANK_MASK: u8 = 0x0c;
+
+/// The target of an indirect jump
+pub const PRG_INDIRECT_CODE: u8 = 0x10;
+
+/// Read through a pointer, as `LDA ($nn),Y` does
+pub const PRG_INDIRECT_DATA: u8 = 0x20;
+
+/// Fetched as a DMC sample. The APU doesn't fetch samples yet, so this is never logged.
+pub const PRG_PCM_DATA: u8 = 0x40;
+
+/// Fetched by the PPU while rendering
+pub const CHR_RENDERED: u8 = 0x01;
+
+/// Read by the CPU through PPUDATA
+pub const CHR_READ: u8 = 0x02;
+
+/// Records how each byte of PRG and CHR ROM is used, in the format of FCEUX .cdl files
+///
+/// The logger wraps the cart so that it sees every CHR fetch the PPU makes. PRG accesses are
+/// classified by decoding each instruction before it executes, so the CPU must be stepped through
+/// `CodeDataLogger::step` for PRG to be logged. Only instruction bytes and memory operands are
+/// logged; vectors, stack accesses and OAM DMA reads aren't.
+pub struct CodeDataLogger<C: Cart> {
+    cart: C,
+    prg_log: Vec<u8>,
+    chr_log: RefCell<Vec<u8>>,
+    // The CHR address about to be read through PPUDATA, which would otherwise look like a fetch
+    // for rendering
+    pending_chr_read: Cell<Option<u16>>,
+}
+
+impl<C: Cart> CodeDataLogger<C> {
+    /// Wraps `cart`, which was created from `rom`. Carts with CHR RAM have no CHR log.
+    pub fn new(cart: C, rom: &NesRom) -> Self {
+        CodeDataLogger {
+            cart,
+            prg_log: vec![0; rom.prg.len()],
+            chr_log: RefCell::new(vec![0; rom.chr.len()]),
+            pending_chr_read: Cell::new(None),
+        }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.cart
+    }
+
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.cart
+    }
+
+    pub fn into_inner(self) -> C {
+        self.cart
+    }
+
+    /// The flags logged for each byte of PRG ROM
+    pub fn prg_log(&self) -> &[u8] {
+        &self.prg_log
+    }
+
+    /// The flags logged for each byte of CHR ROM
+    pub fn chr_log(&self) -> Vec<u8> {
+        self.chr_log.borrow().clone()
+    }
+
+    /// Writes the log as a .cdl file, which is the PRG log followed by the CHR log
+    pub fn save<W: Write>(&self, mut out: W) -> io::Result<()> {
+        out.write_all(&self.prg_log)?;
+        out.write_all(&self.chr_log.borrow())
+    }
+
+    /// Replaces the log with the contents of a .cdl file, so that logging can continue from an
+    /// earlier session. The file must have been logged from the same ROM.
+    pub fn load<R: Read>(&mut self, mut input: R) -> io::Result<()> {
+        let mut cdl = Vec::new();
+        input.read_to_end(&mut cdl)?;
+
+        let prg_len = self.prg_log.len();
+        let chr_log = self.chr_log.get_mut();
+        if cdl.len() != prg_len + chr_log.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "CDL file size doesn't match the ROM",
+            ));
+        }
+
+        self.prg_log.copy_from_slice(&cdl[..prg_len]);
+        chr_log.copy_from_slice(&cdl[prg_len..]);
+        Ok(())
+    }
+
+    /// Logs the instruction at the program counter, then executes it
+    pub fn step<P: IPpu, A: IApu, I: IInput>(
+        cpu: &mut Cpu<NesInterconnect<P, A, I, Self>>,
+    ) -> Interrupt {
+        let state = cpu.state();
+        let instruction = disassemble(&cpu.interconnect, state.pc());
+        let effective_address = instruction.effective_address(&cpu.interconnect, &state);
+        let vram_addr = cpu.interconnect.ppu.vram_addr();
+        let logger = cpu.interconnect.cart_mut();
+
+        for i in 0..instruction.bytes().len() {
+            logger.log_prg(state.pc().wrapping_add(i as u16), PRG_CODE);
+        }
+
+        if let Some(addr) = effective_address {
+            if access(&instruction) != Access::Write {
+                match instruction.mode() {
+                    AddressingMode::Indirect => {
+                        // The pointer of JMP ($nnnn), whose high byte doesn't carry into the
+                        // next page
+                        logger.log_prg(addr, PRG_DATA);
+                        logger.log_prg((addr & 0xff00) | (addr.wrapping_add(1) & 0xff), PRG_DATA);
+                    }
+                    AddressingMode::IndexedIndirect | AddressingMode::IndirectIndexed => {
+                        logger.log_prg(addr, PRG_DATA | PRG_INDIRECT_DATA)
+                    }
+                    _ => logger.log_prg(addr, PRG_DATA),
+                }
+
+                if (0x2000..0x4000).contains(&addr) && addr & 7 == 7 {
+                    logger.pending_chr_read.set(Some(vram_addr & 0x3fff));
+                }
+            }
+        }
+
+        let interrupt = cpu.step();
+        cpu.interconnect.cart_mut().pending_chr_read.set(None);
+
+        let is_indirect = instruction.mode() == AddressingMode::Indirect;
+        if instruction.mnemonic() == Mnemonic::Jmp && is_indirect {
+            let pc = cpu.state().pc();
+            cpu.interconnect.cart_mut().log_prg(pc, PRG_INDIRECT_CODE);
+        }
+
+        interrupt
+    }
+
+    fn log_prg(&mut self, addr: u16, flags: u8) {
+        if let Some(offset) = self.cart.prg_rom_offset(addr) {
+            if let Some(entry) = self.prg_log.get_mut(offset) {
+                let bank = (addr >> 11) as u8 & PRG_BANK_MASK;
+                *entry = (*entry & !PRG_BANK_MASK) | bank | flags;
+            }
+        }
+    }
+
+    fn log_chr(&self, addr: u16) {
+        let flags = if self.pending_chr_read.get() == Some(addr) {
+            self.pending_chr_read.set(None);
+            CHR_READ
+        } else {
+            CHR_RENDERED
+        };
+
+        if let Some(offset) = self.cart.chr_rom_offset(addr) {
+            if let Some(entry) = self.chr_log.borrow_mut().get_mut(offset) {
+                *entry |= flags;
+            }
+        }
+    }
+}
+
+impl<C: Cart> Cart for CodeDataLogger<C> {
+    fn read_prg(&self, addr: u16) -> u8 {
+        self.cart.read_prg(addr)
+    }
+
+    fn write_prg(&mut self, addr: u16, value: u8) {
+        self.cart.write_prg(addr, value)
+    }
+
+    fn read_chr(&self, addr: u16) -> u8 {
+        self.log_chr(addr);
+        self.cart.read_chr(addr)
+    }
+
+    fn write_chr(&mut self, addr: u16, value: u8) {
+        self.cart.write_chr(addr, value)
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.cart.mirroring()
+    }
+
+    fn read_vram(&self, offset: usize) -> u8 {
+        self.cart.read_vram(offset)
+    }
+
+    fn write_vram(&mut self, offset: usize, value: u8) {
+        self.cart.write_vram(offset, value)
+    }
+
+    fn prg_rom_offset(&self, addr: u16) -> Option<usize> {
+        self.cart.prg_rom_offset(addr)
+    }
+
+    fn chr_rom_offset(&self, addr: u16) -> Option<usize> {
+        self.cart.chr_rom_offset(addr)
+    }
+
+    fn peek_prg(&self, addr: u16) -> u8 {
+        self.cart.peek_prg(addr)
+    }
+
+    fn irq_asserted(&self) -> bool {
+        self.cart.irq_asserted()
+    }
+
+    fn reset(&mut self) {
+        self.cart.reset()
+    }
+}
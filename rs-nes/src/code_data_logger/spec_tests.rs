@@ -0,0 +1,150 @@
+use crate::{
+    cart::Cart,
+    code_data_logger::{
+        CodeDataLogger, CHR_READ, CHR_RENDERED, PRG_CODE, PRG_DATA, PRG_INDIRECT_CODE,
+        PRG_INDIRECT_DATA,
+    },
+    interconnect::NesInterconnect,
+    mocks::{ApuMock, CartMock, InputMock, MockSpriteRenderer, PpuMock},
+    ppu::{IPpu, Ppu, Vram},
+    rom::{Mirroring, NesRom, VideoStandard, CHR_BANK_SIZE, PRG_BANK_SIZE},
+};
+use cpu6502::cpu::{Cpu, Interconnect};
+
+type LoggerCpu<P> = Cpu<NesInterconnect<P, ApuMock, InputMock, CodeDataLogger<CartMock>>>;
+
+fn rom() -> NesRom {
+    NesRom {
+        video_standard: VideoStandard::Ntsc,
+        mapper: 0,
+        mirroring: Mirroring::Horizontal,
+        prg_rom_banks: 1,
+        prg_ram_banks: 0,
+        chr_rom_banks: 1,
+        has_chr_ram: false,
+        has_sram: false,
+        has_trainer: false,
+        is_pc10: false,
+        is_vs_unisystem: false,
+        chr: vec![0; CHR_BANK_SIZE],
+        prg: vec![0; PRG_BANK_SIZE],
+    }
+}
+
+// The PRG of the mock cart is mirrored at $8000 and $C000
+fn new_fixture<P: IPpu>(program: &[(u16, &[u8])]) -> LoggerCpu<P> {
+    let mut cart = CartMock::default();
+    for &(addr, bytes) in program {
+        for (i, byte) in bytes.iter().enumerate() {
+            cart.write_prg(addr + i as u16, *byte);
+        }
+    }
+    let logger = CodeDataLogger::new(cart, &rom());
+    Cpu::new(NesInterconnect::new(logger), 0x8000)
+}
+
+fn run<P: IPpu>(cpu: &mut LoggerCpu<P>, steps: usize) {
+    for _ in 0..steps {
+        CodeDataLogger::step(cpu);
+    }
+}
+
+#[test]
+fn code_and_data() {
+    // LDA $8010, STA $8011
+    let mut cpu = new_fixture::<PpuMock>(&[(0x8000, &[0xad, 0x10, 0x80, 0x8d, 0x11, 0x80])]);
+    run(&mut cpu, 2);
+
+    let prg_log = cpu.interconnect.cart().prg_log();
+    assert_eq!(&[PRG_CODE; 6], &prg_log[..6]);
+    assert_eq!(PRG_DATA, prg_log[0x10]);
+
+    // Stores are mapper writes, not data
+    assert_eq!(0, prg_log[0x11]);
+    assert_eq!(0, prg_log[0x06]);
+}
+
+#[test]
+fn bank_bits() {
+    // JMP $E000, where $E000 does LDA $A010
+    let mut cpu =
+        new_fixture::<PpuMock>(&[(0x8000, &[0x4c, 0x00, 0xe0]), (0xe000, &[0xad, 0x10, 0xa0])]);
+    run(&mut cpu, 2);
+
+    let prg_log = cpu.interconnect.cart().prg_log();
+    assert_eq!(PRG_CODE, prg_log[0x0000]);
+    assert_eq!(PRG_CODE | 0x0c, prg_log[0x2000]);
+    assert_eq!(PRG_DATA | 0x04, prg_log[0x2010]);
+}
+
+#[test]
+fn indirect_accesses() {
+    // LDA ($00),Y, then JMP ($8030) to $8040
+    let mut cpu = new_fixture::<PpuMock>(&[
+        (0x8000, &[0xb1, 0x00, 0x6c, 0x30, 0x80]),
+        (0x8030, &[0x40, 0x80]),
+        (0x8040, &[0xea]),
+    ]);
+    cpu.interconnect.write(0x00, 0x1e);
+    cpu.interconnect.write(0x01, 0xc0);
+    let mut state = cpu.state();
+    state.set_y(0x02);
+    cpu.set_state(state);
+    run(&mut cpu, 3);
+
+    let prg_log = cpu.interconnect.cart().prg_log();
+    assert_eq!(PRG_DATA | PRG_INDIRECT_DATA | 0x08, prg_log[0x20]);
+    assert_eq!(PRG_DATA, prg_log[0x30]);
+    assert_eq!(PRG_DATA, prg_log[0x31]);
+    assert_eq!(PRG_CODE | PRG_INDIRECT_CODE, prg_log[0x40]);
+}
+
+#[test]
+fn chr_reads() {
+    // Reads CHR $0123 through PPUDATA with rendering disabled
+    let mut cpu = new_fixture::<Ppu<Vram, MockSpriteRenderer>>(&[(
+        0x8000,
+        &[
+            0xa9, 0x01, 0x8d, 0x06, 0x20, 0xa9, 0x23, 0x8d, 0x06, 0x20, 0xad, 0x07, 0x20,
+        ],
+    )]);
+    run(&mut cpu, 5);
+
+    let chr_log = cpu.interconnect.cart().chr_log();
+    assert_eq!(CHR_READ, chr_log[0x123]);
+    assert_eq!(1, chr_log.iter().filter(|&&flags| flags != 0).count());
+}
+
+#[test]
+fn chr_rendered() {
+    let logger = CodeDataLogger::new(CartMock::default(), &rom());
+    logger.pending_chr_read.set(Some(0x10));
+    logger.read_chr(0x20);
+    logger.read_chr(0x10);
+    logger.read_chr(0x10);
+
+    let chr_log = logger.chr_log();
+    assert_eq!(CHR_RENDERED, chr_log[0x20]);
+    assert_eq!(CHR_READ | CHR_RENDERED, chr_log[0x10]);
+}
+
+#[test]
+fn save_and_load() {
+    let mut cpu = new_fixture::<PpuMock>(&[(0x8000, &[0xad, 0x10, 0x80])]);
+    run(&mut cpu, 1);
+    cpu.interconnect.cart().read_chr(0x05);
+
+    let mut cdl = Vec::new();
+    cpu.interconnect.cart().save(&mut cdl).unwrap();
+    assert_eq!(PRG_BANK_SIZE + CHR_BANK_SIZE, cdl.len());
+    assert_eq!(PRG_CODE, cdl[0]);
+    assert_eq!(PRG_DATA, cdl[0x10]);
+    assert_eq!(CHR_RENDERED, cdl[PRG_BANK_SIZE + 0x05]);
+
+    let mut logger = CodeDataLogger::new(CartMock::default(), &rom());
+    logger.load(&cdl[..]).unwrap();
+    assert_eq!(cpu.interconnect.cart().prg_log(), logger.prg_log());
+    assert_eq!(cpu.interconnect.cart().chr_log(), logger.chr_log());
+
+    assert_eq!(true, logger.load(&cdl[1..]).is_err());
+}
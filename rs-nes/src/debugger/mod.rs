@@ -0,0 +1,348 @@
+#[cfg(test)]
+mod spec_tests;
+
+use crate::{apu::IApu, cart::Cart, input::IInput, interconnect::NesInterconnect, ppu::IPpu};
+use cpu6502::cpu::{
+    bus_log::BusOperation,
+    disassembler::{disassemble, Instruction, Mnemonic},
+    Cpu, Interrupt,
+};
+use std::collections::HashSet;
+
+/// Why the debugger stopped before executing the instruction at the program counter
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Break {
+    /// Execution is paused and must be resumed or stepped before anything else executes
+    Paused,
+    Breakpoint(u16),
+    /// The previous instruction read the address, which may be a mirror of the watched one
+    ReadWatchpoint(u16),
+    /// The previous instruction wrote the address, which may be a mirror of the watched one
+    WriteWatchpoint(u16),
+    /// The previous instruction accessed the PPU register at the address
+    PpuRegisterAccess(u16),
+    StepComplete,
+    Scanline(u16),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Mode {
+    Paused,
+    Running,
+    StepInto,
+    StepOver { return_pc: u16, sp: u8 },
+    StepOut { depth: usize, sp: u8 },
+    RunToScanline { scanline: u16, departed: bool },
+}
+
+/// How an instruction accesses its memory operand
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+/// Controls execution of a `Nes` for debugging frontends
+///
+/// Frontends drive emulation by calling `step` in place of `Cpu::step`, and stop running whenever
+/// it returns a `Break`. Commands such as `resume` and `step_over` only change how far the next
+/// calls to `step` will go, so they work the same whether the frontend runs a frame at a time or
+/// an instruction at a time.
+///
+/// Watchpoints and PPU register breaks are checked against every access the CPU makes on the bus,
+/// including opcode, operand and pointer fetches, dummy reads and writes, stack accesses and
+/// interrupt vector fetches. A watchpoint on internal RAM also catches its mirrors. Since the
+/// accesses are only known once they have happened, the debugger breaks after the instruction
+/// that made them, with the program counter at the next instruction (or the interrupt handler).
+/// Accesses made by OAM and DMC DMA aren't watched.
+pub struct Debugger {
+    breakpoints: HashSet<u16>,
+    read_watchpoints: HashSet<u16>,
+    write_watchpoints: HashSet<u16>,
+    ppu_register_breaks: u8,
+    mode: Mode,
+    executed: bool,
+    last_mnemonic: Option<Mnemonic>,
+    watch_hit: Option<Break>,
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Debugger {
+            breakpoints: HashSet::new(),
+            read_watchpoints: HashSet::new(),
+            write_watchpoints: HashSet::new(),
+            ppu_register_breaks: 0,
+            mode: Mode::Running,
+            executed: false,
+            last_mnemonic: None,
+            watch_hit: None,
+        }
+    }
+}
+
+impl Debugger {
+    pub fn add_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.insert(addr);
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.remove(&addr);
+    }
+
+    pub fn breakpoints(&self) -> &HashSet<u16> {
+        &self.breakpoints
+    }
+
+    pub fn add_read_watchpoint(&mut self, addr: u16) {
+        self.read_watchpoints.insert(addr);
+    }
+
+    pub fn remove_read_watchpoint(&mut self, addr: u16) {
+        self.read_watchpoints.remove(&addr);
+    }
+
+    pub fn read_watchpoints(&self) -> &HashSet<u16> {
+        &self.read_watchpoints
+    }
+
+    pub fn add_write_watchpoint(&mut self, addr: u16) {
+        self.write_watchpoints.insert(addr);
+    }
+
+    pub fn remove_write_watchpoint(&mut self, addr: u16) {
+        self.write_watchpoints.remove(&addr);
+    }
+
+    pub fn write_watchpoints(&self) -> &HashSet<u16> {
+        &self.write_watchpoints
+    }
+
+    /// Breaks on any CPU access to the given PPU register (0-7), including its mirrors
+    pub fn set_ppu_register_break(&mut self, register: u8, enabled: bool) {
+        let mask = 1 << (register & 7);
+        if enabled {
+            self.ppu_register_breaks |= mask;
+        } else {
+            self.ppu_register_breaks &= !mask;
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.mode == Mode::Paused
+    }
+
+    pub fn pause(&mut self) {
+        self.mode = Mode::Paused;
+    }
+
+    /// Runs until a breakpoint, watchpoint or PPU register break is hit
+    pub fn resume(&mut self) {
+        self.start(Mode::Running);
+    }
+
+    /// Executes a single instruction
+    pub fn step_into(&mut self) {
+        self.start(Mode::StepInto);
+    }
+
+    /// Executes a single instruction, running any subroutine called by a `JSR` until it returns
+    pub fn step_over<P: IPpu, A: IApu, I: IInput, C: Cart>(
+        &mut self,
+        cpu: &Cpu<NesInterconnect<P, A, I, C>>,
+    ) {
+        let state = cpu.state();
+        let instruction = disassemble(&cpu.interconnect, state.pc());
+        let mode = if instruction.mnemonic() == Mnemonic::Jsr {
+            Mode::StepOver {
+                return_pc: state.pc().wrapping_add(u16::from(instruction.length())),
+                sp: state.sp(),
+            }
+        } else {
+            Mode::StepInto
+        };
+        self.start(mode);
+    }
+
+    /// Runs until the current subroutine or interrupt handler is left
+    ///
+    /// The frame is found on the CPU's call stack, so leaving it by discarding the return address
+    /// counts as well. Without a frame, such as before any call has been tracked, this runs until
+    /// an `RTS` or `RTI` pulls the stack pointer above where it is now.
+    pub fn step_out<P: IPpu, A: IApu, I: IInput, C: Cart>(
+        &mut self,
+        cpu: &Cpu<NesInterconnect<P, A, I, C>>,
+    ) {
+        self.start(Mode::StepOut {
+            depth: cpu.call_stack().depth(),
+            sp: cpu.state().sp(),
+        });
+    }
+
+    /// Runs until the PPU next arrives at the start of `scanline`
+    pub fn run_to_scanline(&mut self, scanline: u16) {
+        self.start(Mode::RunToScanline {
+            scanline,
+            departed: false,
+        });
+    }
+
+    /// Executes the instruction at the program counter unless the debugger breaks first
+    pub fn step<P: IPpu, A: IApu, I: IInput, C: Cart>(
+        &mut self,
+        cpu: &mut Cpu<NesInterconnect<P, A, I, C>>,
+    ) -> Result<Interrupt, Break> {
+        if let Err(reason) = self.check(cpu) {
+            self.mode = Mode::Paused;
+            return Err(reason);
+        }
+
+        let instruction = disassemble(&cpu.interconnect, cpu.state().pc());
+        self.executed = true;
+        self.last_mnemonic = Some(instruction.mnemonic());
+
+        let watching = !self.read_watchpoints.is_empty()
+            || !self.write_watchpoints.is_empty()
+            || self.ppu_register_breaks != 0;
+        cpu.interconnect.record_bus_accesses(watching);
+        let interrupt = cpu.step();
+        if watching {
+            // Reported by the next call, so the interrupt raised by this step isn't lost
+            self.watch_hit = cpu
+                .interconnect
+                .take_bus_accesses()
+                .into_iter()
+                .find_map(|(addr, operation)| self.watch(addr, operation));
+        }
+        Ok(interrupt)
+    }
+
+    fn start(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.executed = false;
+        self.last_mnemonic = None;
+    }
+
+    fn check<P: IPpu, A: IApu, I: IInput, C: Cart>(
+        &mut self,
+        cpu: &Cpu<NesInterconnect<P, A, I, C>>,
+    ) -> Result<(), Break> {
+        let state = cpu.state();
+        let scanline = cpu.interconnect.ppu.scanline();
+
+        if let Some(reason) = self.watch_hit.take() {
+            return Err(reason);
+        }
+
+        match self.mode {
+            Mode::Paused => return Err(Break::Paused),
+            Mode::Running => (),
+            Mode::StepInto => {
+                if self.executed {
+                    return Err(Break::StepComplete);
+                }
+            }
+            Mode::StepOver { return_pc, sp } => {
+                if state.pc() == return_pc && state.sp() == sp {
+                    return Err(Break::StepComplete);
+                }
+            }
+            Mode::StepOut { depth, .. } if depth > 0 => {
+                if cpu.call_stack().depth() < depth {
+                    return Err(Break::StepComplete);
+                }
+            }
+            Mode::StepOut { sp, .. } => {
+                // The stack pointer wraps, so how far it rose is taken as a signed distance
+                let rose = state.sp().wrapping_sub(sp) as i8 > 0;
+                match self.last_mnemonic {
+                    Some(Mnemonic::Rts) | Some(Mnemonic::Rti) if rose => {
+                        return Err(Break::StepComplete);
+                    }
+                    _ => (),
+                }
+            }
+            Mode::RunToScanline {
+                scanline: target,
+                departed,
+            } => {
+                if scanline != target {
+                    self.mode = Mode::RunToScanline {
+                        scanline: target,
+                        departed: true,
+                    };
+                } else if departed {
+                    return Err(Break::Scanline(target));
+                }
+            }
+        }
+
+        // The instruction that execution resumes from must be allowed to run, otherwise it would
+        // break on itself forever
+        if !self.executed {
+            return Ok(());
+        }
+
+        if self.breakpoints.contains(&state.pc()) {
+            return Err(Break::Breakpoint(state.pc()));
+        }
+
+        Ok(())
+    }
+
+    fn watch(&self, addr: u16, operation: BusOperation) -> Option<Break> {
+        let watchpoints = match operation {
+            BusOperation::Read => &self.read_watchpoints,
+            BusOperation::Write => &self.write_watchpoints,
+        };
+        if watchpoints.iter().any(|&w| mirror(w) == mirror(addr)) {
+            return Some(match operation {
+                BusOperation::Read => Break::ReadWatchpoint(addr),
+                BusOperation::Write => Break::WriteWatchpoint(addr),
+            });
+        }
+
+        let is_ppu_register = (0x2000..0x4000).contains(&addr);
+        if is_ppu_register && self.ppu_register_breaks & (1 << (addr & 7)) > 0 {
+            return Some(Break::PpuRegisterAccess(addr));
+        }
+
+        None
+    }
+}
+
+/// Folds internal RAM and PPU register mirrors onto their lowest address
+fn mirror(addr: u16) -> u16 {
+    match addr {
+        0x0000..=0x1fff => addr & 0x7ff,
+        0x2000..=0x3fff => 0x2000 | (addr & 7),
+        _ => addr,
+    }
+}
+
+pub fn access(instruction: &Instruction) -> Access {
+    match instruction.mnemonic() {
+        Mnemonic::Sta
+        | Mnemonic::Stx
+        | Mnemonic::Sty
+        | Mnemonic::Sax
+        | Mnemonic::Sha
+        | Mnemonic::Shx
+        | Mnemonic::Shy
+        | Mnemonic::Tas => Access::Write,
+        Mnemonic::Asl
+        | Mnemonic::Lsr
+        | Mnemonic::Rol
+        | Mnemonic::Ror
+        | Mnemonic::Inc
+        | Mnemonic::Dec
+        | Mnemonic::Slo
+        | Mnemonic::Rla
+        | Mnemonic::Sre
+        | Mnemonic::Rra
+        | Mnemonic::Dcp
+        | Mnemonic::Isc => Access::ReadWrite,
+        _ => Access::Read,
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{
+    debugger::{Break, Debugger},
+    interconnect::NesInterconnect,
+    mocks::{ApuMock, CartMock, InputMock, MockSpriteRenderer, MockVram},
+    ppu::{IPpu, Ppu},
+};
+use cpu6502::cpu::{Cpu, Interconnect};
+
+type DebugCpu =
+    Cpu<NesInterconnect<Ppu<MockVram, MockSpriteRenderer>, ApuMock, InputMock, CartMock>>;
+
+const MAX_STEPS: usize = 100_000;
+
+fn new_fixture(program: &[u8]) -> DebugCpu {
+    let mut cpu = Cpu::new(NesInterconnect::new(CartMock::default()), 0x0400);
+    for (i, byte) in program.iter().enumerate() {
+        cpu.interconnect.write(0x0400 + i as u16, *byte);
+    }
+    cpu
+}
+
+fn run(debugger: &mut Debugger, cpu: &mut DebugCpu) -> Break {
+    for _ in 0..MAX_STEPS {
+        if let Err(reason) = debugger.step(cpu) {
+            return reason;
+        }
+    }
+    panic!("The debugger never stopped");
+}
+
+#[test]
+fn breakpoint() {
+    // NOP, NOP, JMP $0400
+    let mut cpu = new_fixture(&[0xea, 0xea, 0x4c, 0x00, 0x04]);
+    let mut debugger = Debugger::default();
+    debugger.add_breakpoint(0x0401);
+
+    assert_eq!(Break::Breakpoint(0x0401), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0401, cpu.state().pc());
+    assert_eq!(true, debugger.is_paused());
+    assert_eq!(Err(Break::Paused), debugger.step(&mut cpu));
+
+    // Resuming executes the instruction at the breakpoint rather than breaking on it again
+    debugger.resume();
+    assert!(debugger.step(&mut cpu).is_ok());
+    assert_eq!(0x0402, cpu.state().pc());
+    assert_eq!(Break::Breakpoint(0x0401), run(&mut debugger, &mut cpu));
+
+    debugger.remove_breakpoint(0x0401);
+    assert_eq!(true, debugger.breakpoints().is_empty());
+}
+
+#[test]
+fn step_into() {
+    // JSR $0410, ..., INX, RTS
+    let mut cpu = new_fixture(&[0x20, 0x10, 0x04]);
+    cpu.interconnect.write(0x0410, 0xe8);
+    cpu.interconnect.write(0x0411, 0x60);
+    let mut debugger = Debugger::default();
+
+    debugger.step_into();
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0410, cpu.state().pc());
+
+    debugger.step_into();
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0411, cpu.state().pc());
+}
+
+#[test]
+fn step_over() {
+    // JSR $0410, NOP, ..., INX, RTS
+    let mut cpu = new_fixture(&[0x20, 0x10, 0x04, 0xea]);
+    cpu.interconnect.write(0x0410, 0xe8);
+    cpu.interconnect.write(0x0411, 0x60);
+    let mut debugger = Debugger::default();
+
+    debugger.step_over(&cpu);
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0403, cpu.state().pc());
+    assert_eq!(1, cpu.state().x());
+
+    // Anything other than a JSR behaves like step into
+    debugger.step_over(&cpu);
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0404, cpu.state().pc());
+}
+
+#[test]
+fn step_over_recursive_subroutine() {
+    // JSR $0410, NOP, ..., DEX, BEQ +3, JSR $0410, RTS
+    let mut cpu = new_fixture(&[0x20, 0x10, 0x04, 0xea]);
+    for (i, byte) in [0xca, 0xf0, 0x03, 0x20, 0x10, 0x04, 0x60]
+        .iter()
+        .enumerate()
+    {
+        cpu.interconnect.write(0x0410 + i as u16, *byte);
+    }
+    let mut state = cpu.state();
+    state.set_x(3);
+    cpu.set_state(state);
+    let mut debugger = Debugger::default();
+
+    debugger.step_over(&cpu);
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0403, cpu.state().pc());
+    assert_eq!(0, cpu.state().x());
+}
+
+#[test]
+fn step_out() {
+    // JSR $0410, NOP, ..., JSR $0420, RTS, ..., INX, RTS
+    let mut cpu = new_fixture(&[0x20, 0x10, 0x04, 0xea]);
+    for (i, byte) in [0x20, 0x20, 0x04, 0x60].iter().enumerate() {
+        cpu.interconnect.write(0x0410 + i as u16, *byte);
+    }
+    cpu.interconnect.write(0x0420, 0xe8);
+    cpu.interconnect.write(0x0421, 0x60);
+    let mut debugger = Debugger::default();
+
+    debugger.step_into();
+    run(&mut debugger, &mut cpu);
+    assert_eq!(0x0410, cpu.state().pc());
+
+    // The nested subroutine returns without ending the step
+    debugger.step_out(&cpu);
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0403, cpu.state().pc());
+    assert_eq!(1, cpu.state().x());
+}
+
+#[test]
+fn step_out_discarded_frame() {
+    // JSR $0410, NOP, ..., PLA, PLA, JMP $0403
+    let mut cpu = new_fixture(&[0x20, 0x10, 0x04, 0xea]);
+    for (i, byte) in [0x68, 0x68, 0x4c, 0x03, 0x04].iter().enumerate() {
+        cpu.interconnect.write(0x0410 + i as u16, *byte);
+    }
+    let mut debugger = Debugger::default();
+
+    debugger.step_into();
+    run(&mut debugger, &mut cpu);
+    assert_eq!(1, cpu.call_stack().depth());
+
+    // The frame is left once its return address is pulled, without an RTS
+    debugger.step_out(&cpu);
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0412, cpu.state().pc());
+    assert_eq!(0, cpu.call_stack().depth());
+}
+
+#[test]
+fn step_out_without_frame_across_stack_wrap() {
+    // RTS, which pulls the return address $040F from $0100 and $0101, ..., NOP
+    let mut cpu = new_fixture(&[0x60]);
+    cpu.interconnect.write(0x0100, 0x0f);
+    cpu.interconnect.write(0x0101, 0x04);
+    cpu.interconnect.write(0x0410, 0xea);
+    let mut state = cpu.state();
+    state.set_sp(0xff);
+    cpu.set_state(state);
+    let mut debugger = Debugger::default();
+
+    debugger.step_out(&cpu);
+    assert_eq!(Break::StepComplete, run(&mut debugger, &mut cpu));
+    assert_eq!(0x0410, cpu.state().pc());
+    assert_eq!(0x01, cpu.state().sp());
+}
+
+#[test]
+fn watchpoints() {
+    // LDA $10, STA $11, INC $12, JMP $0400
+    let mut cpu = new_fixture(&[0xa5, 0x10, 0x85, 0x11, 0xe6, 0x12, 0x4c, 0x00, 0x04]);
+    let mut debugger = Debugger::default();
+    debugger.add_read_watchpoint(0x0011);
+    debugger.add_read_watchpoint(0x0012);
+    debugger.add_write_watchpoint(0x0010);
+
+    // A read-modify-write instruction both reads and writes, but stores only write. The break
+    // comes once the accessing instruction has executed.
+    assert_eq!(Break::ReadWatchpoint(0x0012), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0406, cpu.state().pc());
+
+    debugger.remove_read_watchpoint(0x0012);
+    debugger.add_write_watchpoint(0x0012);
+    debugger.resume();
+    assert_eq!(Break::WriteWatchpoint(0x0012), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0406, cpu.state().pc());
+
+    debugger.remove_write_watchpoint(0x0012);
+    debugger.add_write_watchpoint(0x0011);
+    debugger.resume();
+    assert_eq!(Break::WriteWatchpoint(0x0011), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0404, cpu.state().pc());
+}
+
+#[test]
+fn watchpoint_ram_mirror() {
+    // LDA $0810, JMP $0400
+    let mut cpu = new_fixture(&[0xad, 0x10, 0x08, 0x4c, 0x00, 0x04]);
+    let mut debugger = Debugger::default();
+    debugger.add_read_watchpoint(0x0010);
+
+    assert_eq!(Break::ReadWatchpoint(0x0810), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0403, cpu.state().pc());
+}
+
+#[test]
+fn watchpoint_pointer_fetch() {
+    // LDA ($20),Y, JMP $0400
+    let mut cpu = new_fixture(&[0xb1, 0x20, 0x4c, 0x00, 0x04]);
+    let mut debugger = Debugger::default();
+    debugger.add_read_watchpoint(0x0021);
+
+    assert_eq!(Break::ReadWatchpoint(0x0021), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0402, cpu.state().pc());
+}
+
+#[test]
+fn watchpoint_dummy_read() {
+    // LDA $02FF,X, which reads $0200 before fixing up the high byte, JMP $0400
+    let mut cpu = new_fixture(&[0xbd, 0xff, 0x02, 0x4c, 0x00, 0x04]);
+    let mut state = cpu.state();
+    state.set_x(1);
+    cpu.set_state(state);
+    let mut debugger = Debugger::default();
+    debugger.add_read_watchpoint(0x0200);
+
+    assert_eq!(Break::ReadWatchpoint(0x0200), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0403, cpu.state().pc());
+}
+
+#[test]
+fn watchpoint_stack() {
+    // PHA, JMP $0400
+    let mut cpu = new_fixture(&[0x48, 0x4c, 0x00, 0x04]);
+    let mut state = cpu.state();
+    state.set_sp(0xff);
+    cpu.set_state(state);
+    let mut debugger = Debugger::default();
+    debugger.add_write_watchpoint(0x01fe);
+
+    // The second push lands on the watched address
+    assert_eq!(Break::WriteWatchpoint(0x01fe), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0401, cpu.state().pc());
+    assert_eq!(0xfd, cpu.state().sp());
+}
+
+#[test]
+fn watchpoint_vector_fetch() {
+    // BRK, with the IRQ vector pointing at $0410
+    let mut cpu = new_fixture(&[0x00]);
+    cpu.interconnect.write(0xfffe, 0x10);
+    cpu.interconnect.write(0xffff, 0x04);
+    let mut debugger = Debugger::default();
+    debugger.add_read_watchpoint(0xffff);
+
+    assert_eq!(Break::ReadWatchpoint(0xffff), run(&mut debugger, &mut cpu));
+    assert_eq!(0x0410, cpu.state().pc());
+}
+
+#[test]
+fn ppu_register_access() {
+    // LDA $3FFA, STA $2000, JMP $0400
+    let mut cpu = new_fixture(&[0xad, 0xfa, 0x3f, 0x8d, 0x00, 0x20, 0x4c, 0x00, 0x04]);
+    let mut debugger = Debugger::default();
+    debugger.set_ppu_register_break(0, true);
+
+    assert_eq!(
+        Break::PpuRegisterAccess(0x2000),
+        run(&mut debugger, &mut cpu)
+    );
+    assert_eq!(0x0406, cpu.state().pc());
+
+    // Mirrors of the PPU registers break too
+    debugger.set_ppu_register_break(0, false);
+    debugger.set_ppu_register_break(2, true);
+    debugger.resume();
+    assert_eq!(
+        Break::PpuRegisterAccess(0x3ffa),
+        run(&mut debugger, &mut cpu)
+    );
+    assert_eq!(0x0403, cpu.state().pc());
+}
+
+#[test]
+fn run_to_scanline() {
+    // JMP $0400
+    let mut cpu = new_fixture(&[0x4c, 0x00, 0x04]);
+    let mut debugger = Debugger::default();
+
+    debugger.run_to_scanline(10);
+    assert_eq!(Break::Scanline(10), run(&mut debugger, &mut cpu));
+    assert_eq!(10, cpu.interconnect.ppu.scanline());
+    assert!(cpu.interconnect.ppu.dot() < 9);
+
+    // Running to the current scanline waits for the next frame
+    debugger.run_to_scanline(10);
+    let cycles = cpu.interconnect.elapsed_cycles();
+    assert_eq!(Break::Scanline(10), run(&mut debugger, &mut cpu));
+    assert!(cpu.interconnect.elapsed_cycles() - cycles > 29_000);
+}
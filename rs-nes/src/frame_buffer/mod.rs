@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod spec_tests;
+
+use crate::{
+    palette::{Palette, PALETTE_SIZE},
+    ppu::{SCREEN_HEIGHT, SCREEN_WIDTH},
+};
+
+const PIXELS: usize = SCREEN_WIDTH * SCREEN_HEIGHT;
+
+/// A frame composed from the pixels the PPU outputs, with a palette index for each pixel
+///
+/// Composing a pixel decides whether the background or the sprite shows, and combines its color
+/// palette index with the pixel's emphasis bits into a 9-bit palette index for `Palette`. The
+/// frame can then be converted to RGB in whichever layout a client draws with.
+pub struct FrameBuffer {
+    palette_indexes: Box<[u16; PIXELS]>,
+}
+
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        FrameBuffer {
+            palette_indexes: Box::new([0; PIXELS]),
+        }
+    }
+}
+
+impl FrameBuffer {
+    /// Composes the pixels of `IPpu::screen`
+    pub fn compose(&mut self, screen: &[u8; PIXELS * 3]) {
+        for (index, pixel) in self.palette_indexes.iter_mut().zip(screen.chunks(3)) {
+            *index = compose_pixel(pixel[0], pixel[1], pixel[2]);
+        }
+    }
+
+    /// The palette index of each pixel, in rows from the top left
+    pub fn palette_indexes(&self) -> &[u16; PIXELS] {
+        &self.palette_indexes
+    }
+
+    /// Writes the frame to `buffer` as 3 bytes per pixel, in the order red, green, blue
+    pub fn write_rgb24(&self, palette: &Palette, buffer: &mut [u8]) {
+        self.write(palette, buffer, 3, |rgb, out| out.copy_from_slice(&rgb))
+    }
+
+    /// Writes the frame to `buffer` as 4 bytes per pixel, in the order red, green, blue, alpha
+    pub fn write_rgba32(&self, palette: &Palette, buffer: &mut [u8]) {
+        self.write(palette, buffer, 4, |[r, g, b], out| {
+            out.copy_from_slice(&[r, g, b, 0xff])
+        })
+    }
+
+    /// Writes the frame to `buffer` as 4 bytes per pixel, in the order blue, green, red, alpha
+    pub fn write_bgra32(&self, palette: &Palette, buffer: &mut [u8]) {
+        self.write(palette, buffer, 4, |[r, g, b], out| {
+            out.copy_from_slice(&[b, g, r, 0xff])
+        })
+    }
+
+    fn write<F>(&self, palette: &Palette, buffer: &mut [u8], bytes_per_pixel: usize, write_pixel: F)
+    where
+        F: Fn([u8; 3], &mut [u8]),
+    {
+        assert_eq!(
+            PIXELS * bytes_per_pixel,
+            buffer.len(),
+            "Buffer must be {} bytes per pixel",
+            bytes_per_pixel
+        );
+        for (index, out) in self
+            .palette_indexes
+            .iter()
+            .zip(buffer.chunks_mut(bytes_per_pixel))
+        {
+            write_pixel(palette.rgb(*index), out);
+        }
+    }
+}
+
+// Decodes the 3 bytes the PPU outputs for a pixel. See `Ppu::draw_pixel` for the format.
+fn compose_pixel(background: u8, sprite: u8, properties: u8) -> u16 {
+    let background_value = background & 0b11;
+    let sprite_value = sprite & 0b11;
+    let sprite_has_priority = properties & 1 == 1;
+    let color = match (background_value, sprite_value) {
+        (_, 0) => background >> 2,
+        (0, _) => sprite >> 2,
+        _ if sprite_has_priority => sprite >> 2,
+        _ => background >> 2,
+    };
+
+    // The emphasis bits are stored as `rgb` above the priority bit, and a palette index puts red in
+    // the lowest emphasis bit as PPUMASK does
+    let red = u16::from((properties >> 3) & 1);
+    let green = u16::from((properties >> 2) & 1);
+    let blue = u16::from((properties >> 1) & 1);
+    let emphasis = (blue << 2) | (green << 1) | red;
+    emphasis * PALETTE_SIZE as u16 + u16::from(color)
+}
@@ -0,0 +1,85 @@
+use crate::{
+    frame_buffer::{compose_pixel, FrameBuffer, PIXELS},
+    palette::Palette,
+};
+
+#[test]
+fn compose_background_and_sprite() {
+    let background = 0x16 << 2 | 1;
+    let sprite = 0x2a << 2 | 2;
+
+    // A transparent sprite shows the background, including its color when it's transparent
+    assert_eq!(0x16, compose_pixel(background, 0x2a << 2, 1));
+    assert_eq!(0x16, compose_pixel(0x16 << 2, 0x2a << 2, 1));
+
+    // A transparent background shows the sprite
+    assert_eq!(0x2a, compose_pixel(0x16 << 2, sprite, 0));
+    assert_eq!(0x2a, compose_pixel(0x16 << 2, sprite, 1));
+
+    // When both are opaque the sprite shows if it has priority
+    assert_eq!(0x2a, compose_pixel(background, sprite, 1));
+    assert_eq!(0x16, compose_pixel(background, sprite, 0));
+}
+
+#[test]
+fn compose_emphasis() {
+    // Red, green and blue are stored in bits 3, 2 and 1, and become PPUMASK's bits 5, 6 and 7
+    assert_eq!(0x40 | 0x16, compose_pixel(0x16 << 2, 0, 0b1000));
+    assert_eq!(0x80 | 0x16, compose_pixel(0x16 << 2, 0, 0b0100));
+    assert_eq!(0x100 | 0x16, compose_pixel(0x16 << 2, 0, 0b0010));
+    assert_eq!(0x1c0 | 0x16, compose_pixel(0x16 << 2, 0, 0b1111));
+}
+
+#[test]
+fn compose() {
+    let mut screen = [0; PIXELS * 3];
+    screen[0] = 0x01 << 2;
+    screen[3 * 3] = 0x16 << 2 | 1;
+    screen[3 * 3 + 2] = 0b1000;
+    screen[(PIXELS - 1) * 3 + 1] = 0x2a << 2 | 3;
+
+    let mut frame_buffer = FrameBuffer::default();
+    frame_buffer.compose(&screen);
+    let palette_indexes = frame_buffer.palette_indexes();
+    assert_eq!(0x01, palette_indexes[0]);
+    assert_eq!(0x00, palette_indexes[1]);
+    assert_eq!(0x40 | 0x16, palette_indexes[3]);
+    assert_eq!(0x2a, palette_indexes[PIXELS - 1]);
+}
+
+#[test]
+fn write_rgb() {
+    let mut screen = [0; PIXELS * 3];
+    screen[0] = 0x16 << 2;
+    screen[3] = 0x2a << 2;
+    screen[5] = 0b1000;
+
+    let mut frame_buffer = FrameBuffer::default();
+    frame_buffer.compose(&screen);
+    let palette = Palette::default();
+    let red = palette.rgb(0x16);
+    let green = palette.rgb(0x40 | 0x2a);
+
+    let mut rgb24 = vec![0; PIXELS * 3];
+    frame_buffer.write_rgb24(&palette, &mut rgb24);
+    assert_eq!(red, rgb24[0..3]);
+    assert_eq!(green, rgb24[3..6]);
+
+    let mut rgba32 = vec![0; PIXELS * 4];
+    frame_buffer.write_rgba32(&palette, &mut rgba32);
+    assert_eq!([red[0], red[1], red[2], 0xff], rgba32[0..4]);
+    assert_eq!([green[0], green[1], green[2], 0xff], rgba32[4..8]);
+
+    let mut bgra32 = vec![0; PIXELS * 4];
+    frame_buffer.write_bgra32(&palette, &mut bgra32);
+    assert_eq!([red[2], red[1], red[0], 0xff], bgra32[0..4]);
+    assert_eq!([green[2], green[1], green[0], 0xff], bgra32[4..8]);
+}
+
+#[test]
+#[should_panic(expected = "Buffer must be 4 bytes per pixel")]
+fn write_rgba32_buffer_too_small() {
+    let frame_buffer = FrameBuffer::default();
+    let mut buffer = vec![0; PIXELS * 3];
+    frame_buffer.write_rgba32(&Palette::default(), &mut buffer);
+}
@@ -0,0 +1,79 @@
+use crate::{cart::Nrom128, load_cart, rom::NesRom, tracer::trace_line};
+use cpu6502::cpu::Interconnect;
+use std::{collections::VecDeque, fs::File, io::Read};
+
+const NESTEST_AUTOMATION_START: u16 = 0xc000;
+const NESTEST_START_CYCLES: usize = 7;
+const CONTEXT_LINES: usize = 8;
+
+/// Runs nestest in automation mode and compares the CPU state before every instruction against
+/// the golden log. Disassembly annotations are not compared since they depend on what unmapped
+/// and I/O addresses read as.
+#[test]
+fn nestest() {
+    let mut rom_file = File::open("../test_roms/nestest.nes").unwrap();
+    let rom = NesRom::load(&mut rom_file).unwrap();
+    let mut cpu = load_cart(Nrom128::new(&rom).unwrap()).unwrap();
+
+    let mut state = cpu.state();
+    state.set_pc(NESTEST_AUTOMATION_START);
+    cpu.set_state(state);
+
+    // The log begins after the 7 cycle reset sequence
+    assert_eq!(NESTEST_START_CYCLES, cpu.interconnect.elapsed_cycles());
+
+    let mut log = String::new();
+    File::open("../test_roms/nestest.log")
+        .unwrap()
+        .read_to_string(&mut log)
+        .unwrap();
+
+    let mut context = VecDeque::with_capacity(CONTEXT_LINES);
+    for (line_number, expected) in log.lines().map(str::trim_end).enumerate() {
+        let actual = trace_line(&cpu);
+
+        if actual[..4] != expected[..4] || actual[48..] != expected[48..] {
+            let context = context.iter().cloned().collect::<Vec<String>>().join("\n");
+            panic!(
+                "Divergence at line {}\n{}\nexpected: {}\nactual:   {}",
+                line_number + 1,
+                context,
+                expected,
+                actual
+            );
+        }
+
+        if context.len() == CONTEXT_LINES {
+            context.pop_front();
+        }
+        context.push_back(format!("          {}", actual));
+        cpu.step();
+    }
+
+    // nestest reports the first failed official and unofficial opcode tests at $02 and $03
+    assert_eq!(0, cpu.interconnect.read(0x02));
+    assert_eq!(0, cpu.interconnect.read(0x03));
+}
+
+/// Runs a ROM that checks when sprite overflow is set and cleared. It stops in a loop at
+/// `SPRITE_OVERFLOW_DONE` with the number of the failed test at $f0, or 0 if they all passed. See
+/// `test_roms/sprite_overflow_basics.a65` for the tests.
+#[test]
+fn sprite_overflow_basics() {
+    const SPRITE_OVERFLOW_DONE: u16 = 0xc0c0;
+    const MAX_CYCLES: usize = 2_000_000;
+
+    let mut rom_file = File::open("../test_roms/sprite_overflow_basics.nes").unwrap();
+    let rom = NesRom::load(&mut rom_file).unwrap();
+    let mut cpu = load_cart(Nrom128::new(&rom).unwrap()).unwrap();
+
+    while cpu.state().pc() != SPRITE_OVERFLOW_DONE {
+        assert!(
+            cpu.interconnect.elapsed_cycles() < MAX_CYCLES,
+            "Took too many cycles to complete"
+        );
+        cpu.step();
+    }
+
+    assert_eq!(0, cpu.interconnect.read(0xf0), "failed test");
+}
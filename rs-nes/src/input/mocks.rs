@@ -10,6 +10,10 @@ impl IInput for InputMock {
         0
     }
 
+    fn peek(&self, _: u16) -> u8 {
+        0
+    }
+
     fn player1_press(&self, _: Button) {
         unimplemented!()
     }
@@ -1,6 +1,9 @@
 #[cfg(test)]
 pub mod mocks;
 
+#[cfg(test)]
+mod spec_tests;
+
 use std::cell::Cell;
 
 const STROBE_A: u8 = 0;
@@ -32,6 +35,10 @@ pub struct Input {
 pub trait IInput: Default {
     fn write(&mut self, addr: u16, val: u8);
     fn read(&self, addr: u16) -> u8;
+
+    /// Returns the value `read` would, without advancing the strobe
+    fn peek(&self, addr: u16) -> u8;
+
     fn player1_press(&self, button: Button);
     fn player1_release(&self, button: Button);
 }
@@ -44,11 +51,18 @@ impl IInput for Input {
     }
 
     fn read(&self, addr: u16) -> u8 {
-        debug_assert!(addr == 0x4016);
+        let val = self.peek(addr);
+        if addr == 0x4016 {
+            self.strobe.set((self.strobe.get() + 1) & 7);
+        }
+        val
+    }
+
+    /// Only the first controller is implemented, so $4017 always reads 0
+    fn peek(&self, addr: u16) -> u8 {
         if addr == 0x4016 {
             let strobe = self.strobe.get();
             let state = self.state.get();
-            self.strobe.set((strobe + 1) & 7);
             match strobe {
                 STROBE_A => state & 1,
                 STROBE_B => (state & (1 << 1)) >> 1,
@@ -104,6 +118,10 @@ impl IInput for NoInput {
         0
     }
 
+    fn peek(&self, _: u16) -> u8 {
+        0
+    }
+
     fn player1_press(&self, _: Button) {
         unimplemented!()
     }
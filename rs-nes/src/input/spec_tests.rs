@@ -0,0 +1,23 @@
+use crate::input::{Button, IInput, Input};
+
+#[test]
+fn read_first_controller() {
+    let input = Input::default();
+    input.player1_press(Button::B);
+    assert_eq!(0, input.peek(0x4016));
+    assert_eq!(0, input.read(0x4016));
+    assert_eq!(1, input.peek(0x4016));
+    assert_eq!(1, input.read(0x4016));
+    assert_eq!(0, input.read(0x4016));
+}
+
+#[test]
+fn read_second_controller() {
+    let input = Input::default();
+    input.player1_press(Button::A);
+    assert_eq!(0, input.peek(0x4017));
+    assert_eq!(0, input.read(0x4017));
+
+    // Reading the second controller doesn't advance the first
+    assert_eq!(1, input.read(0x4016));
+}
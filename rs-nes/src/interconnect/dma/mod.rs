@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod spec_tests;
+
+const OAM_DMA_LEN: u16 = 0x100;
+
+// A DMC fetch has to wait for a halt cycle and a dummy cycle before it can take a get cycle
+const DMC_WAIT_CYCLES: u8 = 2;
+
+/// What the bus is used for on a cycle where the CPU is halted
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DmaCycle {
+    /// Repeats the read the CPU was halted on, as the halt, dummy and alignment cycles do
+    Halted,
+    DmcRead(u16),
+    OamRead(u16),
+    OamWrite(u8),
+}
+
+#[derive(Copy, Clone)]
+struct OamDma {
+    addr: u16,
+    remaining: u16,
+    // The byte read on the last get cycle, which is written to OAM on the next put cycle
+    value: Option<u8>,
+}
+
+#[derive(Copy, Clone)]
+struct DmcDma {
+    addr: u16,
+    wait: u8,
+}
+
+/// The DMA unit of the 2A03, which halts the CPU to copy sprites to OAM and fetch DMC samples
+///
+/// The unit alternates between get cycles, on which it reads, and put cycles, on which it
+/// writes. Once the CPU has been halted, a transfer that isn't ready for the current cycle spends
+/// it repeating the CPU's read, which is how alignment and dummy cycles are made. This gives OAM
+/// DMA its 513 or 514 cycles and a DMC fetch its 3 or 4. When a DMC fetch happens during OAM DMA,
+/// its halt and dummy cycles overlap OAM transfers, and it usually costs 2 cycles: the get cycle it
+/// takes from OAM DMA and the put cycle spent realigning.
+#[derive(Default)]
+pub struct Dma {
+    oam: Option<OamDma>,
+    dmc: Option<DmcDma>,
+    halted: bool,
+}
+
+impl Dma {
+    /// Whether a transfer is pending or in progress, which holds RDY low
+    pub fn is_active(&self) -> bool {
+        self.oam.is_some() || self.dmc.is_some()
+    }
+
+    /// Starts copying the 256 bytes of `page` to OAM
+    pub fn start_oam(&mut self, page: u8) {
+        self.oam = Some(OamDma {
+            addr: u16::from(page) << 8,
+            remaining: OAM_DMA_LEN,
+            value: None,
+        });
+    }
+
+    /// Starts fetching the DMC sample at `addr`, unless a fetch is already pending
+    pub fn start_dmc(&mut self, addr: u16) {
+        if self.dmc.is_none() {
+            self.dmc = Some(DmcDma {
+                addr,
+                wait: DMC_WAIT_CYCLES,
+            });
+        }
+    }
+
+    /// Decides what the bus is used for on a cycle where the CPU is halted
+    pub fn next_cycle(&mut self, is_get_cycle: bool) -> DmaCycle {
+        let cycle = if !self.halted {
+            self.halted = true;
+            DmaCycle::Halted
+        } else if is_get_cycle {
+            match (self.dmc, self.oam) {
+                (Some(dmc), _) if dmc.wait == 0 => {
+                    self.dmc = None;
+                    DmaCycle::DmcRead(dmc.addr)
+                }
+                (_, Some(oam)) if oam.value.is_none() => DmaCycle::OamRead(oam.addr),
+                _ => DmaCycle::Halted,
+            }
+        } else {
+            match self.oam {
+                Some(OamDma {
+                    value: Some(value), ..
+                }) => {
+                    self.oam_write_complete();
+                    DmaCycle::OamWrite(value)
+                }
+                _ => DmaCycle::Halted,
+            }
+        };
+
+        if let Some(ref mut dmc) = self.dmc {
+            dmc.wait = dmc.wait.saturating_sub(1);
+        }
+        if !self.is_active() {
+            self.halted = false;
+        }
+        cycle
+    }
+
+    /// Latches the byte read on an `OamRead` cycle
+    pub fn oam_read_complete(&mut self, value: u8) {
+        if let Some(ref mut oam) = self.oam {
+            oam.value = Some(value);
+        }
+    }
+
+    fn oam_write_complete(&mut self) {
+        if let Some(mut oam) = self.oam {
+            oam.addr = oam.addr.wrapping_add(1);
+            oam.remaining -= 1;
+            oam.value = None;
+            self.oam = if oam.remaining > 0 { Some(oam) } else { None };
+        }
+    }
+}
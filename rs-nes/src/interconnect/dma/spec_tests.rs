@@ -0,0 +1,102 @@
+use crate::interconnect::dma::{Dma, DmaCycle};
+
+// Runs the DMA unit until it releases the CPU, starting on a get or put cycle. Bytes read for OAM
+// are the low byte of their address.
+fn run(dma: &mut Dma, start_on_get_cycle: bool) -> Vec<DmaCycle> {
+    let mut cycles = Vec::new();
+    let mut is_get_cycle = start_on_get_cycle;
+    while dma.is_active() {
+        let cycle = dma.next_cycle(is_get_cycle);
+        if let DmaCycle::OamRead(addr) = cycle {
+            dma.oam_read_complete(addr as u8);
+        }
+        cycles.push(cycle);
+        is_get_cycle = !is_get_cycle;
+    }
+    cycles
+}
+
+#[test]
+fn oam_dma() {
+    let mut dma = Dma::default();
+    dma.start_oam(0x02);
+    let cycles = run(&mut dma, false);
+
+    assert_eq!(513, cycles.len());
+    assert_eq!(DmaCycle::Halted, cycles[0]);
+    assert_eq!(DmaCycle::OamRead(0x0200), cycles[1]);
+    assert_eq!(DmaCycle::OamWrite(0x00), cycles[2]);
+    assert_eq!(DmaCycle::OamRead(0x02ff), cycles[511]);
+    assert_eq!(DmaCycle::OamWrite(0xff), cycles[512]);
+}
+
+#[test]
+fn oam_dma_alignment() {
+    // Halting on a get cycle leaves the next put cycle with nothing to write
+    let mut dma = Dma::default();
+    dma.start_oam(0x02);
+    let cycles = run(&mut dma, true);
+
+    assert_eq!(514, cycles.len());
+    assert_eq!(DmaCycle::Halted, cycles[0]);
+    assert_eq!(DmaCycle::Halted, cycles[1]);
+    assert_eq!(DmaCycle::OamRead(0x0200), cycles[2]);
+}
+
+#[test]
+fn dmc_dma() {
+    let mut dma = Dma::default();
+    dma.start_dmc(0xc000);
+    assert_eq!(
+        vec![
+            DmaCycle::Halted,
+            DmaCycle::Halted,
+            DmaCycle::DmcRead(0xc000)
+        ],
+        run(&mut dma, true)
+    );
+
+    dma.start_dmc(0xc001);
+    assert_eq!(
+        vec![
+            DmaCycle::Halted,
+            DmaCycle::Halted,
+            DmaCycle::Halted,
+            DmaCycle::DmcRead(0xc001)
+        ],
+        run(&mut dma, false)
+    );
+}
+
+#[test]
+fn dmc_dma_during_oam_dma() {
+    let mut dma = Dma::default();
+    dma.start_oam(0x02);
+    for i in 0..100 {
+        let cycle = dma.next_cycle(i % 2 == 1);
+        if let DmaCycle::OamRead(addr) = cycle {
+            dma.oam_read_complete(addr as u8);
+        }
+    }
+
+    // The halt and dummy cycles overlap OAM transfers, then the fetch takes a get cycle and OAM
+    // DMA realigns on the following put cycle
+    dma.start_dmc(0xc000);
+    let cycles = run(&mut dma, false);
+    assert_eq!(513 + 2, 100 + cycles.len());
+    assert_eq!(DmaCycle::OamWrite(0x31), cycles[0]);
+    assert_eq!(DmaCycle::OamRead(0x0232), cycles[1]);
+    assert_eq!(DmaCycle::OamWrite(0x32), cycles[2]);
+    assert_eq!(DmaCycle::DmcRead(0xc000), cycles[3]);
+    assert_eq!(DmaCycle::Halted, cycles[4]);
+    assert_eq!(DmaCycle::OamRead(0x0233), cycles[5]);
+}
+
+#[test]
+fn repeated_dmc_request() {
+    // A request made while a fetch is pending doesn't replace it
+    let mut dma = Dma::default();
+    dma.start_dmc(0xc000);
+    dma.start_dmc(0xc001);
+    assert_eq!(Some(&DmaCycle::DmcRead(0xc000)), run(&mut dma, true).last());
+}
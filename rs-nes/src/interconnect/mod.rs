@@ -1,8 +1,16 @@
 #[cfg(test)]
 mod spec_tests;
 
+mod dma;
+
+use self::dma::{Dma, DmaCycle};
 use crate::{apu::IApu, cart::Cart, input::IInput, ppu::IPpu};
-use cpu6502::cpu::{Interconnect, Interrupt};
+use cpu6502::cpu::{bus_log::BusOperation, Interconnect, Interrupt, IrqLine};
+use std::{cell::RefCell, mem};
+
+pub const IRQ_SOURCE_FRAME_COUNTER: u8 = 0b001;
+pub const IRQ_SOURCE_DMC: u8 = 0b010;
+pub const IRQ_SOURCE_MAPPER: u8 = 0b100;
 
 pub struct NesInterconnect<P: IPpu, A: IApu, I: IInput, C: Cart> {
     ram: [u8; 0x800],
@@ -10,7 +18,12 @@ pub struct NesInterconnect<P: IPpu, A: IApu, I: IInput, C: Cart> {
     pub ppu: P,
     pub apu: A,
     pub input: I,
+    pub irq_line: IrqLine,
     elapsed_cycles: usize,
+    dma: Dma,
+    dma_cycles: usize,
+    records_bus_accesses: bool,
+    bus_accesses: RefCell<Vec<(u16, BusOperation)>>,
 }
 
 impl<P: IPpu, A: IApu, I: IInput, C: Cart> NesInterconnect<P, A, I, C> {
@@ -21,31 +34,48 @@ impl<P: IPpu, A: IApu, I: IInput, C: Cart> NesInterconnect<P, A, I, C> {
             ppu: P::default(),
             apu: A::default(),
             input: I::default(),
+            irq_line: IrqLine::default(),
             elapsed_cycles: 0,
+            dma: Dma::default(),
+            dma_cycles: 0,
+            records_bus_accesses: false,
+            bus_accesses: RefCell::new(Vec::new()),
         }
     }
 
-    fn dma_write(&mut self, value: u8) {
-        let is_odd_cycle = self.elapsed_cycles % 2 == 1;
-        self.tick();
+    pub fn cart(&self) -> &C {
+        &self.rom
+    }
 
-        if is_odd_cycle {
-            self.tick();
-        }
+    pub fn cart_mut(&mut self) -> &mut C {
+        &mut self.rom
+    }
 
-        let start = u16::from(value) << 8;
+    /// The number of cycles the CPU has spent halted by OAM and DMC DMA, which are included in
+    /// `elapsed_cycles`. Like `elapsed_cycles`, it keeps counting across resets.
+    pub fn dma_cycles(&self) -> usize {
+        self.dma_cycles
+    }
 
-        for i in 0..0x100 {
-            let val = self.read(i + start);
-            self.tick();
-            self.write(0x2004, val);
-            self.tick();
+    /// Records the address of every read and write the CPU makes while enabled, for the debugger's
+    /// watchpoints. Peeks aren't recorded, and neither are DMA transfers made while the CPU is
+    /// halted.
+    pub fn record_bus_accesses(&mut self, enabled: bool) {
+        self.records_bus_accesses = enabled;
+    }
+
+    /// Returns the accesses recorded so far and clears them
+    pub fn take_bus_accesses(&mut self) -> Vec<(u16, BusOperation)> {
+        mem::take(self.bus_accesses.get_mut())
+    }
+
+    fn record(&self, address: u16, operation: BusOperation) {
+        if self.records_bus_accesses {
+            self.bus_accesses.borrow_mut().push((address, operation));
         }
     }
-}
 
-impl<P: IPpu, A: IApu, I: IInput, C: Cart> Interconnect for NesInterconnect<P, A, I, C> {
-    fn read(&self, address: u16) -> u8 {
+    fn read_bus(&self, address: u16) -> u8 {
         match address >> 13 {
             0b000 => self.ram[address as usize & 0x7ff],
             0b001 => self.ppu.read(address, &self.rom),
@@ -68,7 +98,7 @@ impl<P: IPpu, A: IApu, I: IInput, C: Cart> Interconnect for NesInterconnect<P, A
         }
     }
 
-    fn write(&mut self, address: u16, value: u8) {
+    fn write_bus(&mut self, address: u16, value: u8) {
         match address >> 13 {
             0b000 => self.ram[address as usize & 0x7ff] = value,
             0b001 => self.ppu.write(address, value, &mut self.rom),
@@ -76,7 +106,7 @@ impl<P: IPpu, A: IApu, I: IInput, C: Cart> Interconnect for NesInterconnect<P, A
                 if address < 0x4020 {
                     match address & 0x1f {
                         0...19 => self.apu.write(address, value),
-                        20 => self.dma_write(value),
+                        20 => self.dma.start_oam(value),
                         21 => self.apu.write(address, value),
                         22 => self.input.write(address, value),
                         23 => self.apu.write(address, value),
@@ -91,6 +121,18 @@ impl<P: IPpu, A: IApu, I: IInput, C: Cart> Interconnect for NesInterconnect<P, A
             _ => unreachable!(),
         }
     }
+}
+
+impl<P: IPpu, A: IApu, I: IInput, C: Cart> Interconnect for NesInterconnect<P, A, I, C> {
+    fn read(&self, address: u16) -> u8 {
+        self.record(address, BusOperation::Read);
+        self.read_bus(address)
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.record(address, BusOperation::Write);
+        self.write_bus(address, value)
+    }
 
     fn tick(&mut self) -> Interrupt {
         self.elapsed_cycles += 1;
@@ -106,10 +148,93 @@ impl<P: IPpu, A: IApu, I: IInput, C: Cart> Interconnect for NesInterconnect<P, A
                 tick_action = Interrupt::Nmi;
             }
         }
+
+        self.irq_line
+            .set(IRQ_SOURCE_FRAME_COUNTER, self.apu.frame_irq_asserted());
+        self.irq_line
+            .set(IRQ_SOURCE_DMC, self.apu.dmc_irq_asserted());
+        self.irq_line
+            .set(IRQ_SOURCE_MAPPER, self.rom.irq_asserted());
+
+        if let Some(addr) = self.apu.dmc_dma_request() {
+            self.dma.start_dmc(addr);
+        }
         tick_action
     }
 
     fn elapsed_cycles(&self) -> usize {
         self.elapsed_cycles
     }
+
+    fn irq_asserted(&self) -> bool {
+        self.irq_line.is_asserted()
+    }
+
+    fn halt_requested(&self) -> bool {
+        self.dma.is_active()
+    }
+
+    /// Reads are made on even cycles and writes on odd ones. The halted read is repeated with all
+    /// of its side effects, which is why DMC fetches can corrupt reads of PPUDATA and the
+    /// controller ports.
+    fn halted_cycle(&mut self, address: u16) {
+        let is_get_cycle = self.elapsed_cycles & 1 == 0;
+        match self.dma.next_cycle(is_get_cycle) {
+            DmaCycle::Halted => {
+                self.read_bus(address);
+            }
+            DmaCycle::DmcRead(addr) => {
+                let value = self.read_bus(addr);
+                self.apu.dmc_dma_complete(value);
+            }
+            DmaCycle::OamRead(addr) => {
+                let value = self.read_bus(addr);
+                self.dma.oam_read_complete(value);
+            }
+            DmaCycle::OamWrite(value) => self.write_bus(0x2004, value),
+        }
+        self.dma_cycles += 1;
+    }
+
+    /// RAM is cleared and the PPU, APU and input devices start from their default state. Mappers
+    /// are reset, since the contents of the cartridge can't be recreated.
+    fn power_on(&mut self) {
+        self.ram = [0_u8; 0x800];
+        self.ppu = P::default();
+        self.apu = A::default();
+        self.input = I::default();
+        self.irq_line = IrqLine::default();
+        self.dma = Dma::default();
+        self.rom.reset();
+    }
+
+    /// RAM is kept, and the PPU, APU and mapper each handle the reset line themselves. A DMA
+    /// transfer in progress is abandoned along with the rest of the CPU's state.
+    fn soft_reset(&mut self) {
+        self.ppu.reset();
+        self.apu.reset();
+        self.dma = Dma::default();
+        self.rom.reset();
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        match address >> 13 {
+            0b000 => self.ram[address as usize & 0x7ff],
+            0b001 => self.ppu.peek(address, &self.rom),
+            0b010 => {
+                if address < 0x4020 {
+                    match address & 0x1f {
+                        21 => self.apu.peek_control(),
+                        22 | 23 => self.input.peek(address),
+                        _ => 0,
+                    }
+                } else {
+                    0
+                }
+            }
+            0b011 => 0,
+            0b100 | 0b101 | 0b110 | 0b111 => self.rom.peek_prg(address),
+            _ => unreachable!(),
+        }
+    }
 }
@@ -1,8 +1,11 @@
 use crate::{
-    interconnect::NesInterconnect,
+    interconnect::{
+        dma::Dma, NesInterconnect, IRQ_SOURCE_DMC, IRQ_SOURCE_FRAME_COUNTER, IRQ_SOURCE_MAPPER,
+    },
     mocks::{ApuMock, CartMock, InputMock, PpuMock},
 };
-use cpu6502::cpu::Interconnect;
+use cpu6502::cpu::{bus_log::BusOperation, Interconnect, IrqLine};
+use std::cell::RefCell;
 
 #[test]
 fn ram_memory_mapped_read() {
@@ -83,6 +86,74 @@ fn apu_memory_mapped_write() {
     }
 }
 
+#[test]
+fn peek() {
+    let mut fixture = new_fixture();
+    fixture.ram[0x10] = 0x44;
+    fixture.ppu.set_value(0x55);
+    fixture.apu.set_control(0x66);
+
+    assert_eq!(0x44, fixture.peek(0x0810));
+    assert_eq!(0x55, fixture.peek(0x2002));
+    assert_eq!(0x66, fixture.peek(0x4015));
+    assert_eq!(0, fixture.peek(0x4000));
+    assert_eq!(0, fixture.elapsed_cycles());
+}
+
+#[test]
+fn power_on() {
+    let mut fixture = new_fixture();
+    fixture.ram[0x10] = 0x44;
+    fixture.ppu.set_value(0x55);
+    fixture.irq_line.assert(IRQ_SOURCE_MAPPER);
+
+    fixture.power_on();
+    assert_eq!(0, fixture.ram[0x10]);
+    assert_eq!(0, fixture.ppu.value());
+    assert_eq!(false, fixture.irq_asserted());
+}
+
+#[test]
+fn soft_reset() {
+    let mut fixture = new_fixture();
+    fixture.ram[0x10] = 0x44;
+
+    fixture.soft_reset();
+    assert_eq!(0x44, fixture.ram[0x10]);
+    assert_eq!(true, fixture.ppu.reset_called());
+
+    // The APU is silenced as if $00 had been written to $4015
+    assert_eq!(0x4015, fixture.apu.write_addr());
+    assert_eq!(0, fixture.apu.write_value());
+}
+
+#[test]
+fn power_on_stops_dma() {
+    let mut fixture = new_fixture();
+    write_cycle(&mut fixture, 0x4014, 0x02);
+    fixture.apu.request_dmc_sample(0xc000);
+    fixture.tick();
+    assert_eq!(true, fixture.halt_requested());
+
+    fixture.power_on();
+    assert_eq!(false, fixture.halt_requested());
+}
+
+#[test]
+fn soft_reset_stops_dma() {
+    let mut fixture = new_fixture();
+    write_cycle(&mut fixture, 0x4014, 0x02);
+    for _ in 0..10 {
+        fixture.halted_cycle(0x0000);
+        fixture.tick();
+    }
+    assert_eq!(true, fixture.halt_requested());
+
+    fixture.soft_reset();
+    assert_eq!(false, fixture.halt_requested());
+    assert_eq!(10, fixture.dma_cycles());
+}
+
 #[test]
 #[ignore]
 fn input_memory_mapped_read() {
@@ -98,16 +169,104 @@ fn input_memory_mapped_write() {
 #[test]
 fn oam_dma_timing_even_cycle() {
     let mut fixture = new_fixture();
-    fixture.write(0x4014, 0x02);
-    assert_eq!(513, fixture.elapsed_cycles());
+    write_cycle(&mut fixture, 0x4014, 0x02);
+    assert_eq!(513, run_halted(&mut fixture, 0x0000));
+    assert_eq!(513, fixture.dma_cycles());
 }
 
 #[test]
 fn oam_dma_timing_odd_cycle() {
     let mut fixture = new_fixture();
     fixture.tick();
-    fixture.write(0x4014, 0x02);
-    assert_eq!(514, fixture.elapsed_cycles() - 1);
+    write_cycle(&mut fixture, 0x4014, 0x02);
+    assert_eq!(514, run_halted(&mut fixture, 0x0000));
+    assert_eq!(514, fixture.dma_cycles());
+}
+
+#[test]
+fn oam_dma_copies_page() {
+    let mut fixture = new_fixture();
+    fixture.ram[0x2ff] = 0x55;
+    write_cycle(&mut fixture, 0x4014, 0x02);
+    assert_eq!(true, fixture.halt_requested());
+
+    run_halted(&mut fixture, 0x0000);
+    assert_eq!(false, fixture.halt_requested());
+    assert_eq!(0x2004, fixture.ppu.addr());
+    assert_eq!(0x55, fixture.ppu.value());
+}
+
+#[test]
+fn dmc_dma() {
+    let mut fixture = new_fixture();
+    fixture.rom.prg[0x0123] = 0x66;
+    fixture.apu.request_dmc_sample(0xc123);
+    fixture.tick();
+
+    assert_eq!(4, run_halted(&mut fixture, 0x0000));
+    assert_eq!(&[0x66], fixture.apu.dmc_samples());
+    assert_eq!(false, fixture.halt_requested());
+}
+
+#[test]
+fn dmc_dma_during_oam_dma() {
+    let mut fixture = new_fixture();
+    write_cycle(&mut fixture, 0x4014, 0x02);
+    fixture.apu.request_dmc_sample(0xc000);
+    assert_eq!(513 + 2, run_halted(&mut fixture, 0x0000));
+    assert_eq!(1, fixture.apu.dmc_samples().len());
+}
+
+#[test]
+fn records_cpu_bus_accesses() {
+    let mut fixture = new_fixture();
+    fixture.read(0x0010);
+    assert_eq!(true, fixture.take_bus_accesses().is_empty());
+
+    fixture.record_bus_accesses(true);
+    fixture.read(0x0810);
+    fixture.write(0x2001, 0);
+    let expected = vec![(0x0810, BusOperation::Read), (0x2001, BusOperation::Write)];
+    assert_eq!(expected, fixture.take_bus_accesses());
+    assert_eq!(true, fixture.take_bus_accesses().is_empty());
+}
+
+#[test]
+fn oam_dma_accesses_not_recorded() {
+    let mut fixture = new_fixture();
+    fixture.record_bus_accesses(true);
+    write_cycle(&mut fixture, 0x4014, 0x02);
+    fixture.take_bus_accesses();
+    run_halted(&mut fixture, 0x8000);
+    assert_eq!(true, fixture.take_bus_accesses().is_empty());
+}
+
+#[test]
+fn irq_sources() {
+    let mut fixture = new_fixture();
+    fixture.apu.set_frame_irq(true);
+    fixture.tick();
+    assert_eq!(true, fixture.irq_asserted());
+    assert_eq!(
+        true,
+        fixture.irq_line.is_asserted_by(IRQ_SOURCE_FRAME_COUNTER)
+    );
+
+    fixture.apu.set_dmc_irq(true);
+    fixture.rom.irq = true;
+    fixture.tick();
+    assert_eq!(true, fixture.irq_line.is_asserted_by(IRQ_SOURCE_DMC));
+    assert_eq!(true, fixture.irq_line.is_asserted_by(IRQ_SOURCE_MAPPER));
+
+    // The line stays asserted until every source has released it
+    fixture.apu.set_frame_irq(false);
+    fixture.apu.set_dmc_irq(false);
+    fixture.tick();
+    assert_eq!(true, fixture.irq_asserted());
+
+    fixture.rom.irq = false;
+    fixture.tick();
+    assert_eq!(false, fixture.irq_asserted());
 }
 
 fn new_fixture() -> NesInterconnect<PpuMock, ApuMock, InputMock, CartMock> {
@@ -117,6 +276,35 @@ fn new_fixture() -> NesInterconnect<PpuMock, ApuMock, InputMock, CartMock> {
         ppu: PpuMock::default(),
         apu: ApuMock::default(),
         input: InputMock::default(),
+        irq_line: IrqLine::default(),
         elapsed_cycles: 0,
+        dma: Dma::default(),
+        dma_cycles: 0,
+        records_bus_accesses: false,
+        bus_accesses: RefCell::new(Vec::new()),
+    }
+}
+
+// Writes on a CPU cycle, which only ends once the cycle has ticked
+fn write_cycle(
+    fixture: &mut NesInterconnect<PpuMock, ApuMock, InputMock, CartMock>,
+    addr: u16,
+    value: u8,
+) {
+    fixture.write(addr, value);
+    fixture.tick();
+}
+
+// Runs halted cycles like the CPU would while trying to read `addr`, returning how many there were
+fn run_halted(
+    fixture: &mut NesInterconnect<PpuMock, ApuMock, InputMock, CartMock>,
+    addr: u16,
+) -> usize {
+    let mut cycles = 0;
+    while fixture.halt_requested() {
+        fixture.halted_cycle(addr);
+        fixture.tick();
+        cycles += 1;
     }
+    cycles
 }
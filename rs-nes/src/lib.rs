@@ -10,20 +10,38 @@ extern crate byteorder;
 extern crate cpu6502;
 extern crate rs_nes_macros;
 
+#[cfg(test)]
+mod functional_tests;
+
 mod apu;
 mod cart;
+mod code_data_logger;
+mod debugger;
+mod frame_buffer;
 mod input;
 mod interconnect;
+mod palette;
 mod ppu;
+mod profiler;
 mod rom;
+mod tracer;
 
 pub use crate::{
     apu::Apu,
     cart::{Cart, Nrom128, Nrom256, Uxrom},
+    code_data_logger::{
+        CodeDataLogger, CHR_READ, CHR_RENDERED, PRG_BANK_MASK, PRG_CODE, PRG_DATA,
+        PRG_INDIRECT_CODE, PRG_INDIRECT_DATA, PRG_PCM_DATA,
+    },
+    debugger::{Break, Debugger},
+    frame_buffer::FrameBuffer,
     input::{Button, IInput, Input},
     interconnect::NesInterconnect,
-    ppu::{IPpu, Ppu, SpriteRenderer, Vram},
+    palette::{Palette, PpuModel, EMPHASIS_PALETTE_SIZE, PALETTE_SIZE},
+    ppu::{IPpu, Ppu, SpriteRenderer, Vram, SCREEN_HEIGHT, SCREEN_WIDTH},
+    profiler::{Profiler, SubroutineProfile},
     rom::NesRom,
+    tracer::{trace_line, Tracer},
 };
 use cpu6502::cpu::Cpu;
 
@@ -42,6 +60,6 @@ mod mocks {
 pub fn load_cart<C: Cart>(cart: C) -> Result<Box<Nes<C>>, &'static str> {
     let interconnect = NesInterconnect::new(cart);
     let mut cpu = box Cpu::new(interconnect, 0x00);
-    cpu.reset();
+    cpu.power_on();
     Ok(cpu)
 }
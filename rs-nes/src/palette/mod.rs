@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod spec_tests;
+
+use std::io::Read;
+
+/// The number of colors the PPU can output, not counting emphasis
+pub const PALETTE_SIZE: usize = 64;
+
+/// The number of colors with every combination of emphasis bits
+pub const EMPHASIS_PALETTE_SIZE: usize = PALETTE_SIZE * 8;
+
+// Emphasis bits of a palette index, which are PPUMASK bits 5 to 7 above the 6-bit color
+const EMPHASIZE_RED: usize = 0b001;
+const EMPHASIZE_GREEN: usize = 0b010;
+const EMPHASIZE_BLUE: usize = 0b100;
+
+// How much a color channel is dimmed when another channel is emphasized, as measured on a 2C02
+const ATTENUATION: f32 = 0.816_328;
+
+// The NES color palette, in RGB format
+#[rustfmt::skip]
+static DEFAULT_COLORS: [[u8; 3]; PALETTE_SIZE] = [
+    [0x7C, 0x7C, 0x7C], [0x00, 0x00, 0xFC], [0x00, 0x00, 0xBC], [0x44, 0x28, 0xBC],
+    [0x94, 0x00, 0x84], [0xA8, 0x00, 0x20], [0xA8, 0x10, 0x00], [0x88, 0x14, 0x00],
+    [0x50, 0x30, 0x00], [0x00, 0x78, 0x00], [0x00, 0x68, 0x00], [0x00, 0x58, 0x00],
+    [0x00, 0x40, 0x58], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+    [0xBC, 0xBC, 0xBC], [0x00, 0x78, 0xF8], [0x00, 0x58, 0xF8], [0x68, 0x44, 0xFC],
+    [0xD8, 0x00, 0xCC], [0xE4, 0x00, 0x58], [0xF8, 0x38, 0x00], [0xE4, 0x5C, 0x10],
+    [0xAC, 0x7C, 0x00], [0x00, 0xB8, 0x00], [0x00, 0xA8, 0x00], [0x00, 0xA8, 0x44],
+    [0x00, 0x88, 0x88], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+    [0xF8, 0xF8, 0xF8], [0x3C, 0xBC, 0xFC], [0x68, 0x88, 0xFC], [0x98, 0x78, 0xF8],
+    [0xF8, 0x78, 0xF8], [0xF8, 0x58, 0x98], [0xF8, 0x78, 0x58], [0xFC, 0xA0, 0x44],
+    [0xF8, 0xB8, 0x00], [0xB8, 0xF8, 0x18], [0x58, 0xD8, 0x54], [0x58, 0xF8, 0x98],
+    [0x00, 0xE8, 0xD8], [0x78, 0x78, 0x78], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+    [0xFC, 0xFC, 0xFC], [0xA4, 0xE4, 0xFC], [0xB8, 0xB8, 0xF8], [0xD8, 0xB8, 0xF8],
+    [0xF8, 0xB8, 0xF8], [0xF8, 0xA4, 0xC0], [0xF0, 0xD0, 0xB0], [0xFC, 0xE0, 0xA8],
+    [0xF8, 0xD8, 0x78], [0xD8, 0xF8, 0x78], [0xB8, 0xF8, 0xB8], [0xB8, 0xF8, 0xD8],
+    [0x00, 0xFC, 0xFC], [0xF8, 0xD8, 0xF8], [0x00, 0x00, 0x00], [0x00, 0x00, 0x00],
+];
+
+/// The PPU a palette is for, which determines how the emphasis bits of PPUMASK are interpreted
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PpuModel {
+    /// The NTSC PPU, where PPUMASK bits 5, 6 and 7 emphasize red, green and blue
+    Rp2C02,
+
+    /// The PAL PPU, where PPUMASK bits 5 and 6 are swapped, emphasizing green and red
+    Rp2C07,
+}
+
+/// Converts palette indexes to RGB
+///
+/// A palette index is 9 bits, with the emphasis bits of PPUMASK above the 6-bit color palette
+/// index, which is the layout of 512 color `.pal` files.
+pub struct Palette {
+    colors: Box<[[u8; 3]; EMPHASIS_PALETTE_SIZE]>,
+    model: PpuModel,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new(PpuModel::Rp2C02)
+    }
+}
+
+impl Palette {
+    pub fn new(model: PpuModel) -> Self {
+        Palette::with_emphasis(&DEFAULT_COLORS, model)
+    }
+
+    /// Loads a `.pal` file of 64 colors, or of 512 colors that include every combination of
+    /// emphasis bits, in RGB format. The emphasized colors of a 64 color palette are generated
+    /// for `model`.
+    pub fn load<R: Read>(input: &mut R, model: PpuModel) -> Result<Palette, &'static str> {
+        let mut bytes = Vec::new();
+        input
+            .read_to_end(&mut bytes)
+            .map_err(|_| "Unable to read palette")?;
+
+        if bytes.len() != PALETTE_SIZE * 3 && bytes.len() != EMPHASIS_PALETTE_SIZE * 3 {
+            return Err("Palette must have 64 or 512 colors");
+        }
+
+        let mut colors = [[0; 3]; EMPHASIS_PALETTE_SIZE];
+        for (color, rgb) in colors.iter_mut().zip(bytes.chunks(3)) {
+            color.copy_from_slice(rgb);
+        }
+
+        if bytes.len() == PALETTE_SIZE * 3 {
+            let mut base_colors = [[0; 3]; PALETTE_SIZE];
+            base_colors.copy_from_slice(&colors[..PALETTE_SIZE]);
+            Ok(Palette::with_emphasis(&base_colors, model))
+        } else {
+            Ok(Palette {
+                colors: Box::new(colors),
+                model,
+            })
+        }
+    }
+
+    pub fn model(&self) -> PpuModel {
+        self.model
+    }
+
+    /// Returns the RGB color for a palette index. Bits above the 9th are ignored.
+    pub fn rgb(&self, index: u16) -> [u8; 3] {
+        self.colors[index as usize % EMPHASIS_PALETTE_SIZE]
+    }
+
+    // Generates the emphasized colors for each combination of emphasis bits.
+    //
+    // Emphasizing a color dims the other two. A color is dimmed by the same amount however many of
+    // the others are emphasized, so emphasizing all three dims the whole picture evenly. The blacks
+    // in columns $xE and $xF are already at the lowest level, so emphasis doesn't affect them.
+    fn with_emphasis(base_colors: &[[u8; 3]; PALETTE_SIZE], model: PpuModel) -> Self {
+        let mut colors = [[0; 3]; EMPHASIS_PALETTE_SIZE];
+        for (index, color) in colors.iter_mut().enumerate() {
+            let base_color = index % PALETTE_SIZE;
+            *color = base_colors[base_color];
+            if base_color & 0xf >= 0xe {
+                continue;
+            }
+
+            let emphasis = index / PALETTE_SIZE;
+            let emphasis = match model {
+                PpuModel::Rp2C02 => emphasis,
+                // Swap the red and green bits
+                PpuModel::Rp2C07 => {
+                    ((emphasis & EMPHASIZE_RED) << 1)
+                        | ((emphasis & EMPHASIZE_GREEN) >> 1)
+                        | (emphasis & EMPHASIZE_BLUE)
+                }
+            };
+
+            for (channel, bit) in [EMPHASIZE_RED, EMPHASIZE_GREEN, EMPHASIZE_BLUE]
+                .iter()
+                .enumerate()
+            {
+                if emphasis & !bit > 0 {
+                    color[channel] = (f32::from(color[channel]) * ATTENUATION) as u8;
+                }
+            }
+        }
+
+        Palette {
+            colors: Box::new(colors),
+            model,
+        }
+    }
+}
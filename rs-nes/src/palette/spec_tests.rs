@@ -0,0 +1,83 @@
+use crate::palette::{Palette, PpuModel, DEFAULT_COLORS, EMPHASIS_PALETTE_SIZE};
+use std::io::Cursor;
+
+const RED: u16 = 0x40;
+const GREEN: u16 = 0x80;
+const BLUE: u16 = 0x100;
+
+#[test]
+fn rgb() {
+    let palette = Palette::default();
+    assert_eq!(PpuModel::Rp2C02, palette.model());
+    assert_eq!([0x7c, 0x7c, 0x7c], palette.rgb(0x00));
+    assert_eq!([0xb8, 0xf8, 0x18], palette.rgb(0x29));
+    assert_eq!([0xf8, 0xd8, 0xf8], palette.rgb(0x3d));
+
+    // Bits above the emphasis bits are ignored
+    assert_eq!([0xb8, 0xf8, 0x18], palette.rgb(0x29 | 0x200));
+}
+
+#[test]
+fn emphasis() {
+    let palette = Palette::new(PpuModel::Rp2C02);
+
+    // Emphasizing a color dims the other two
+    assert_eq!([0xf8, 0xca, 0xca], palette.rgb(0x20 | RED));
+    assert_eq!([0xca, 0xf8, 0xca], palette.rgb(0x20 | GREEN));
+    assert_eq!([0xca, 0xca, 0xf8], palette.rgb(0x20 | BLUE));
+
+    // Dimming doesn't compound when several colors are emphasized
+    assert_eq!([0xca, 0xca, 0xca], palette.rgb(0x20 | RED | GREEN));
+    assert_eq!([0xca, 0xca, 0xca], palette.rgb(0x20 | RED | GREEN | BLUE));
+}
+
+#[test]
+fn emphasis_doesnt_affect_blacks() {
+    let palette = Palette::default();
+    assert_eq!([0x00, 0x00, 0x00], palette.rgb(0x0f | RED | GREEN | BLUE));
+    assert_eq!([0x61, 0x61, 0x61], palette.rgb(0x2d | RED | GREEN | BLUE));
+    assert_eq!([0x00, 0x00, 0x00], palette.rgb(0x2e | RED | GREEN | BLUE));
+}
+
+#[test]
+fn emphasis_2c07() {
+    // The red and green emphasis bits are swapped on the PAL PPU
+    let palette = Palette::new(PpuModel::Rp2C07);
+    assert_eq!(PpuModel::Rp2C07, palette.model());
+    assert_eq!([0xca, 0xf8, 0xca], palette.rgb(0x20 | RED));
+    assert_eq!([0xf8, 0xca, 0xca], palette.rgb(0x20 | GREEN));
+    assert_eq!([0xca, 0xca, 0xf8], palette.rgb(0x20 | BLUE));
+}
+
+#[test]
+fn load_64_colors() {
+    // The emphasized colors are generated the same way as for the built in palette
+    let bytes: Vec<u8> = DEFAULT_COLORS.iter().flat_map(|rgb| rgb.to_vec()).collect();
+    for model in &[PpuModel::Rp2C02, PpuModel::Rp2C07] {
+        let loaded = Palette::load(&mut Cursor::new(&bytes), *model).unwrap();
+        let built_in = Palette::new(*model);
+        assert_eq!(*model, loaded.model());
+        for index in 0..EMPHASIS_PALETTE_SIZE as u16 {
+            assert_eq!(built_in.rgb(index), loaded.rgb(index), "index = {}", index);
+        }
+    }
+}
+
+#[test]
+fn load_512_colors() {
+    // The emphasized colors are used as they are
+    let bytes: Vec<u8> = (0..EMPHASIS_PALETTE_SIZE * 3).map(|i| i as u8).collect();
+    let palette = Palette::load(&mut Cursor::new(&bytes), PpuModel::Rp2C02).unwrap();
+    assert_eq!([0x00, 0x01, 0x02], palette.rgb(0x00));
+    assert_eq!([0x03, 0x04, 0x05], palette.rgb(0x01));
+    assert_eq!([0xc0, 0xc1, 0xc2], palette.rgb(RED));
+    assert_eq!([0xfd, 0xfe, 0xff], palette.rgb(0x3f | RED | GREEN | BLUE));
+}
+
+#[test]
+fn load_invalid_size() {
+    for len in &[0, 63 * 3, 64 * 3 + 1, 512 * 3 - 1, 512 * 3 + 3] {
+        let bytes = vec![0; *len];
+        assert!(Palette::load(&mut Cursor::new(&bytes), PpuModel::Rp2C02).is_err());
+    }
+}
@@ -41,6 +41,12 @@ impl BackgroundRenderer {
         ];
     }
 
+    /// The universal background color, which is shown wherever the background is transparent or
+    /// hidden
+    pub fn backdrop_color(&self) -> u8 {
+        self.palettes[0]
+    }
+
     pub fn current_pixel(&self, fine_x: u8) -> (u8, u8) {
         let shift = 15 - fine_x;
         let pattern_low = (self.shift_registers[0] >> shift) as u8 & 1;
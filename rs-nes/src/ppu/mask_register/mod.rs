@@ -21,6 +21,11 @@ impl Deref for MaskRegister {
 }
 
 impl MaskRegister {
+    /// Greyscale (0: normal color; 1: produce a greyscale display)
+    pub fn greyscale(&self) -> bool {
+        self.reg & 0b0000_0001 > 0
+    }
+
     pub fn background_render_leftmost_8_px(&self) -> bool {
         self.reg & 0b0000_0010 > 0
     }
@@ -37,15 +42,15 @@ impl MaskRegister {
         self.reg & 0b0001_0000 > 0
     }
 
-    fn emphasize_red(&self) -> bool {
+    pub fn emphasize_red(&self) -> bool {
         self.reg & 0b0010_0000 > 0
     }
 
-    fn emphasize_green(&self) -> bool {
+    pub fn emphasize_green(&self) -> bool {
         self.reg & 0b0100_0000 > 0
     }
 
-    fn emphasize_blue(&self) -> bool {
+    pub fn emphasize_blue(&self) -> bool {
         self.reg & 0b1000_0000 > 0
     }
 
@@ -56,4 +61,26 @@ impl MaskRegister {
     pub fn rendering_enabled(&self) -> bool {
         self.show_background() || self.show_sprites()
     }
+
+    /// Whether the background is shown at the pixel in `column`, which is hidden in the leftmost 8
+    /// pixels unless they're enabled
+    pub fn background_visible(&self, column: u16) -> bool {
+        self.show_background() && (column > 7 || self.background_render_leftmost_8_px())
+    }
+
+    /// Whether sprites are shown at the pixel in `column`, which is hidden in the leftmost 8 pixels
+    /// unless they're enabled
+    pub fn sprites_visible(&self, column: u16) -> bool {
+        self.show_sprites() && (column > 7 || self.sprites_render_leftmost_8_px())
+    }
+
+    /// Applies greyscale to a color palette index. The hardware does this by masking off the hue,
+    /// leaving the column of greys for the color's brightness.
+    pub fn apply_greyscale(&self, color: u8) -> u8 {
+        if self.greyscale() {
+            color & 0x30
+        } else {
+            color
+        }
+    }
 }
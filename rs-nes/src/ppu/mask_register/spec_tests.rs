@@ -1,5 +1,14 @@
 use crate::ppu::mask_register::MaskRegister;
 
+#[test]
+fn greyscale() {
+    let reg = new_mask_register(0b00000000);
+    assert_eq!(false, reg.greyscale());
+
+    let reg = new_mask_register(0b00000001);
+    assert_eq!(true, reg.greyscale());
+}
+
 #[test]
 fn background_render_leftmost_8_px() {
     let reg = new_mask_register(0b00000000);
@@ -63,6 +72,53 @@ fn emphasize_blue() {
     assert_eq!(true, reg.emphasize_blue());
 }
 
+#[test]
+fn background_visible() {
+    let reg = new_mask_register(0b00000000);
+    assert_eq!(false, reg.background_visible(0));
+    assert_eq!(false, reg.background_visible(8));
+
+    // Leftmost 8 pixels clipped
+    let reg = new_mask_register(0b00011100);
+    assert_eq!(false, reg.background_visible(0));
+    assert_eq!(false, reg.background_visible(7));
+    assert_eq!(true, reg.background_visible(8));
+    assert_eq!(true, reg.background_visible(255));
+
+    let reg = new_mask_register(0b00001010);
+    assert_eq!(true, reg.background_visible(0));
+    assert_eq!(true, reg.background_visible(255));
+}
+
+#[test]
+fn sprites_visible() {
+    let reg = new_mask_register(0b00000000);
+    assert_eq!(false, reg.sprites_visible(0));
+    assert_eq!(false, reg.sprites_visible(8));
+
+    // Leftmost 8 pixels clipped
+    let reg = new_mask_register(0b00011010);
+    assert_eq!(false, reg.sprites_visible(0));
+    assert_eq!(false, reg.sprites_visible(7));
+    assert_eq!(true, reg.sprites_visible(8));
+    assert_eq!(true, reg.sprites_visible(255));
+
+    let reg = new_mask_register(0b00010100);
+    assert_eq!(true, reg.sprites_visible(0));
+    assert_eq!(true, reg.sprites_visible(255));
+}
+
+#[test]
+fn apply_greyscale() {
+    let reg = new_mask_register(0b00000000);
+    assert_eq!(0x2a, reg.apply_greyscale(0x2a));
+
+    let reg = new_mask_register(0b00000001);
+    assert_eq!(0x20, reg.apply_greyscale(0x2a));
+    assert_eq!(0x30, reg.apply_greyscale(0x3f));
+    assert_eq!(0x00, reg.apply_greyscale(0x0d));
+}
+
 fn new_mask_register(val: u8) -> MaskRegister {
     MaskRegister { reg: val }
 }
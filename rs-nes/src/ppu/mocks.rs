@@ -8,6 +8,9 @@ use cpu6502::cpu::Interrupt;
 pub struct PpuMock {
     addr: u16,
     value: u8,
+    scanline: u16,
+    dot: u16,
+    reset_called: bool,
     screen: [u8; SCREEN_WIDTH * SCREEN_HEIGHT * 3],
 }
 
@@ -16,6 +19,9 @@ impl Default for PpuMock {
         PpuMock {
             addr: 0,
             value: 0,
+            scanline: 0,
+            dot: 0,
+            reset_called: false,
             screen: [0; SCREEN_WIDTH * SCREEN_HEIGHT * 3],
         }
     }
@@ -33,6 +39,15 @@ impl PpuMock {
     pub fn set_value(&mut self, value: u8) {
         self.value = value;
     }
+
+    pub fn set_position(&mut self, scanline: u16, dot: u16) {
+        self.scanline = scanline;
+        self.dot = dot;
+    }
+
+    pub fn reset_called(&self) -> bool {
+        self.reset_called
+    }
 }
 
 impl IPpu for PpuMock {
@@ -45,6 +60,10 @@ impl IPpu for PpuMock {
         self.value
     }
 
+    fn peek<C: Cart>(&self, _: u16, _: &C) -> u8 {
+        self.value
+    }
+
     fn step<C: Cart>(&mut self, _: &C) -> Interrupt {
         Interrupt::None
     }
@@ -52,4 +71,20 @@ impl IPpu for PpuMock {
     fn screen(&self) -> &[u8; SCREEN_WIDTH * SCREEN_HEIGHT * 3] {
         &self.screen
     }
+
+    fn scanline(&self) -> u16 {
+        self.scanline
+    }
+
+    fn dot(&self) -> u16 {
+        self.dot
+    }
+
+    fn vram_addr(&self) -> u16 {
+        0
+    }
+
+    fn reset(&mut self) {
+        self.reset_called = true;
+    }
 }
@@ -37,8 +37,24 @@ pub const SCREEN_HEIGHT: usize = 240;
 pub trait IPpu: Default {
     fn write<C: Cart>(&mut self, addr: u16, val: u8, cart: &mut C);
     fn read<C: Cart>(&self, addr: u16, cart: &C) -> u8;
+
+    /// Returns the value a read of the memory mapped `addr` would, without any side effects
+    fn peek<C: Cart>(&self, addr: u16, cart: &C) -> u8;
     fn step<C: Cart>(&mut self, cart: &C) -> Interrupt;
     fn screen(&self) -> &[u8; SCREEN_WIDTH * SCREEN_HEIGHT * 3];
+
+    /// The scanline of the next dot to be rendered, where 261 is the pre-render scanline
+    fn scanline(&self) -> u16;
+
+    /// The next dot to be rendered within the current scanline
+    fn dot(&self) -> u16;
+
+    /// The VRAM address that the next PPUDATA access will use
+    fn vram_addr(&self) -> u16;
+
+    /// Called when the console is reset. PPUCTRL, PPUMASK, the scroll position, the write latch
+    /// and the PPUDATA read buffer are cleared, while the VRAM address, OAM and memory are kept.
+    fn reset(&mut self);
 }
 
 #[derive(Debug, PartialEq)]
@@ -98,9 +114,9 @@ impl<V: IVram, S: ISpriteRenderer> Ppu<V, S> {
     ///
     /// **Byte 3 (pixel properties)**: `xxxx rgbp`
     ///
-    /// - `r`: Emphasize red (not yet implemented)
-    /// - `g`: Emphasize green (not yet implemented)
-    /// - `b`: Emphasize blue (not yet implemented)
+    /// - `r`: Emphasize red (PPUMASK bit 5)
+    /// - `g`: Emphasize green (PPUMASK bit 6)
+    /// - `b`: Emphasize blue (PPUMASK bit 7)
     /// - `p`: Sprite pixel priority
     ///
     /// The color palette index is 6-bit value that represents one of the 64 colors that the nes is
@@ -108,6 +124,13 @@ impl<V: IVram, S: ISpriteRenderer> Ppu<V, S> {
     /// corresponding to the palette index for the block of 16x16 pixels that the current pixel
     /// belongs to.
     ///
+    /// A layer that PPUMASK hides, either entirely or in the leftmost 8 pixels, is output with a
+    /// pixel value of 0, and a hidden background takes the backdrop color. Greyscale is applied to
+    /// both color palette indexes.
+    ///
+    /// The emphasis bits are named for the 2C02, and the 2C07 swaps the meaning of the red and
+    /// green bits. `FrameBuffer` decodes this format and converts it to RGB with a `Palette`.
+    ///
     /// The reason for encoding pixels this way, as opposed to encoding the color itself, is to
     /// offload the logic in determining which pixel displays (background or sprite) to the
     /// renderer, which effectively batches the process. This makes it more cache friendly and
@@ -116,12 +139,24 @@ impl<V: IVram, S: ISpriteRenderer> Ppu<V, S> {
     /// It also encodes point-in-time information that can be used for debugging, that would
     /// otherwise be lost if we simply output a color.
     fn draw_pixel(&mut self, x: u16, scanline: u16) {
-        let fine_x = self.vram.fine_x();
-        let (bg_pixel, bg_color) = self.background_renderer.current_pixel(fine_x);
-        let sprite_pixel = self.sprite_renderer.current_pixel();
-        let background_byte = (bg_color << 2) | bg_pixel;
-        let sprite_byte = (sprite_pixel.color_index << 2) | sprite_pixel.value;
-        let property_byte = sprite_pixel.has_priority as u8; // TODO: Add emphasis bits
+        let column = x - 2;
+        let (bg_pixel, bg_color) = if self.mask.background_visible(column) {
+            let fine_x = self.vram.fine_x();
+            self.background_renderer.current_pixel(fine_x)
+        } else {
+            (0, self.background_renderer.backdrop_color())
+        };
+        let mut sprite_pixel = self.sprite_renderer.current_pixel();
+        if !self.mask.sprites_visible(column) {
+            sprite_pixel.value = 0;
+        }
+        let background_byte = (self.mask.apply_greyscale(bg_color) << 2) | bg_pixel;
+        let sprite_byte =
+            (self.mask.apply_greyscale(sprite_pixel.color_index) << 2) | sprite_pixel.value;
+        let property_byte = (self.mask.emphasize_red() as u8) << 3
+            | (self.mask.emphasize_green() as u8) << 2
+            | (self.mask.emphasize_blue() as u8) << 1
+            | sprite_pixel.has_priority as u8;
 
         // TODO: Is it appropriate to evaluate sprite zero hit here considering the cycles
         // draw_pixel() is called on?
@@ -129,7 +164,7 @@ impl<V: IVram, S: ISpriteRenderer> Ppu<V, S> {
             self.status.set_sprite_zero_hit()
         }
 
-        let i = ((scanline as usize) * SCREEN_WIDTH + ((x - 2) as usize)) * 3;
+        let i = ((scanline as usize) * SCREEN_WIDTH + (column as usize)) * 3;
         self.screen[i] = background_byte;
         self.screen[i + 1] = sprite_byte;
         self.screen[i + 2] = property_byte;
@@ -211,6 +246,20 @@ impl<V: IVram, S: ISpriteRenderer> IPpu for Ppu<V, S> {
         }
     }
 
+    fn peek<C: Cart>(&self, addr: u16, cart: &C) -> u8 {
+        debug_assert!(
+            addr >= 0x2000 && addr < 0x4000,
+            "Invalid memory mapped ppu address"
+        );
+
+        match addr & 7 {
+            0x2 => self.status.read(),
+            0x4 => self.sprite_renderer.read_data(),
+            0x7 => self.vram.peek_ppu_data(cart),
+            _ => 0,
+        }
+    }
+
     #[ppu_loop]
     fn step<C: Cart>(&mut self, cart: &C) -> Interrupt {
         Interrupt::None
@@ -219,4 +268,24 @@ impl<V: IVram, S: ISpriteRenderer> IPpu for Ppu<V, S> {
     fn screen(&self) -> &[u8; SCREEN_WIDTH * SCREEN_HEIGHT * 3] {
         &self.screen
     }
+
+    fn scanline(&self) -> u16 {
+        (self.cycles % CYCLES_PER_FRAME / CYCLES_PER_SCANLINE) as u16
+    }
+
+    fn dot(&self) -> u16 {
+        (self.cycles % CYCLES_PER_SCANLINE) as u16
+    }
+
+    fn vram_addr(&self) -> u16 {
+        self.vram.addr()
+    }
+
+    fn reset(&mut self) {
+        self.control.write(0);
+        self.mask.write(0);
+        self.write_latch.clear();
+        self.vram.reset();
+        self.odd_frame = false;
+    }
 }
@@ -1,8 +1,12 @@
 use crate::{
     mocks::{CartMock, MockSpriteRenderer, MockVram},
     ppu::{
-        background_renderer::BackgroundRenderer, control_register::ControlRegister,
-        mask_register::MaskRegister, status_register::StatusRegister, write_latch::WriteLatch,
+        background_renderer::BackgroundRenderer,
+        control_register::ControlRegister,
+        mask_register::MaskRegister,
+        sprite_renderer::SpriteRenderer,
+        status_register::StatusRegister,
+        write_latch::{LatchState, WriteLatch},
         IPpu, Ppu, CYCLES_PER_SCANLINE, SCREEN_HEIGHT, SCREEN_WIDTH,
     },
 };
@@ -326,6 +330,163 @@ fn vblank_set_and_clear_cycles() {
     }
 }
 
+#[test]
+fn sprite_overflow_set_and_clear_cycles() {
+    // Sprite evaluation runs from dot 65 of each visible scanline, and the flag stays set until
+    // dot 1 of the pre-render line
+    const EVALUATION_START: usize = 65;
+    const CLEAR_CYCLE: usize = CYCLES_PER_SCANLINE * 261 + 1;
+
+    let mut ppu = ppu_fixture();
+    let mock_cart = CartMock::default();
+    ppu.mask.write(0b00010000); // Enable sprite rendering
+    ppu.sprite_renderer.mock_sprite_overflow.set(true);
+
+    while ppu.cycles < super::CYCLES_PER_FRAME * 2 {
+        match ppu.cycles % super::CYCLES_PER_FRAME {
+            0...EVALUATION_START => assert_eq!(false, ppu.status.sprite_overflow()),
+            frame_cycle if frame_cycle <= CLEAR_CYCLE => {
+                assert_eq!(true, ppu.status.sprite_overflow())
+            }
+            _ => assert_eq!(false, ppu.status.sprite_overflow()),
+        }
+        ppu.step(&mock_cart);
+    }
+}
+
+#[test]
+fn sprite_overflow_not_set_when_rendering_disabled() {
+    let mut ppu = ppu_fixture();
+    let mock_cart = CartMock::default();
+    ppu.sprite_renderer.mock_sprite_overflow.set(true);
+
+    while ppu.cycles < super::CYCLES_PER_FRAME {
+        ppu.step(&mock_cart);
+        assert_eq!(false, ppu.status.sprite_overflow());
+    }
+}
+
+#[test]
+fn sprite_overflow_from_evaluation() {
+    // Nine sprites on scanline 10 overflow on the write cycle after the ninth sprite's y is read,
+    // which is dot 130
+    let mut ppu = Ppu {
+        cycles: 0,
+        control: ControlRegister::default(),
+        mask: MaskRegister::default(),
+        status: StatusRegister::default(),
+        vram: Box::new(MockVram::default()),
+        sprite_renderer: SpriteRenderer::default(),
+        write_latch: WriteLatch::default(),
+        background_renderer: BackgroundRenderer::default(),
+        screen: Box::new([0; SCREEN_WIDTH * SCREEN_HEIGHT * 3]),
+        odd_frame: false,
+    };
+    let mut mock_cart = CartMock::default();
+    ppu.write(0x2003, 0, &mut mock_cart);
+    for i in 0..0x100 {
+        let val = if i < 9 * 4 && i % 4 == 0 { 10 } else { 0xff };
+        ppu.write(0x2004, val, &mut mock_cart);
+    }
+    ppu.write(0x2001, 0b00010000, &mut mock_cart);
+
+    let overflow_cycle = CYCLES_PER_SCANLINE * 10 + 130;
+    while ppu.cycles < overflow_cycle {
+        ppu.step(&mock_cart);
+        assert_eq!(false, ppu.status.sprite_overflow());
+    }
+    ppu.step(&mock_cart);
+    assert_eq!(true, ppu.status.sprite_overflow());
+    assert_eq!(true, ppu.read(0x2002, &mock_cart) & 0b00100000 > 0);
+}
+
+#[test]
+fn draw_pixel_sprite_clipping() {
+    let mut ppu = ppu_fixture();
+    ppu.sprite_renderer.mock_pixel_value.set(1);
+    ppu.sprite_renderer.mock_color_index.set(0x16);
+
+    // Sprites are shown, except in the leftmost 8 pixels
+    ppu.mask.write(0b00010000);
+    ppu.draw_pixel(9, 0);
+    ppu.draw_pixel(10, 0);
+    assert_eq!(0x16 << 2, ppu.screen[7 * 3 + 1]);
+    assert_eq!(0x16 << 2 | 1, ppu.screen[8 * 3 + 1]);
+
+    ppu.mask.write(0b00010100);
+    ppu.draw_pixel(2, 0);
+    assert_eq!(0x16 << 2 | 1, ppu.screen[1]);
+
+    // Sprites are hidden
+    ppu.mask.write(0b00001110);
+    ppu.draw_pixel(2, 0);
+    ppu.draw_pixel(10, 0);
+    assert_eq!(0x16 << 2, ppu.screen[1]);
+    assert_eq!(0x16 << 2, ppu.screen[8 * 3 + 1]);
+}
+
+#[test]
+fn draw_pixel_greyscale() {
+    let mut ppu = ppu_fixture();
+    ppu.sprite_renderer.mock_pixel_value.set(1);
+    ppu.sprite_renderer.mock_color_index.set(0x16);
+
+    ppu.mask.write(0b00010111);
+    ppu.draw_pixel(2, 0);
+    assert_eq!(0x10 << 2 | 1, ppu.screen[1]);
+}
+
+#[test]
+fn draw_pixel_emphasis() {
+    let mut ppu = ppu_fixture();
+    ppu.mask.write(0b00111000);
+    ppu.draw_pixel(2, 0);
+    assert_eq!(0b1001, ppu.screen[2]);
+
+    ppu.mask.write(0b11011000);
+    ppu.draw_pixel(2, 0);
+    assert_eq!(0b0111, ppu.screen[2]);
+}
+
+#[test]
+fn background_left_column_clipping() {
+    // Every fetched pattern is opaque, so only the clipped columns show the backdrop
+    let mut ppu = ppu_fixture();
+    let mock_cart = CartMock::default();
+    ppu.vram.mock_read.set(0xff);
+    ppu.vram.mock_palette.set(0x16);
+    ppu.mask.write(0b00001000);
+
+    // Palettes and the first tiles are loaded on the pre-render line, so check the second frame
+    while ppu.cycles < super::CYCLES_PER_FRAME * 2 {
+        ppu.step(&mock_cart);
+    }
+
+    for column in 0..SCREEN_WIDTH {
+        let expected = if column < 8 { 0x16 << 2 } else { 0x16 << 2 | 3 };
+        assert_eq!(expected, ppu.screen[column * 3], "column = {}", column);
+    }
+}
+
+#[test]
+fn rendering_disabled_outputs_backdrop() {
+    let mut ppu = ppu_fixture();
+    let mock_cart = CartMock::default();
+    ppu.vram.mock_read.set(0xff);
+    ppu.vram.mock_palette.set(0x16);
+    ppu.sprite_renderer.mock_pixel_value.set(1);
+    ppu.sprite_renderer.mock_color_index.set(0x2a);
+
+    while ppu.cycles < super::CYCLES_PER_FRAME * 2 {
+        ppu.step(&mock_cart);
+    }
+
+    for pixel in ppu.screen.chunks(3) {
+        assert_eq!(0x16 << 2, pixel[0]);
+        assert_eq!(0, pixel[1] & 0b11);
+    }
+}
+
 #[test]
 fn vblank_clear_after_status_read() {
     let ppu = ppu_fixture();
@@ -378,6 +539,62 @@ fn oam_read_forced_blanking_doesnt_increments_addr() {
     assert_eq!(true, ppu.sprite_renderer.read_data_called.get());
 }
 
+#[test]
+fn peek_has_no_side_effects() {
+    let mut ppu = ppu_fixture();
+    let mock_cart = CartMock::default();
+    ppu.status.set_in_vblank();
+    ppu.mask.write(0xff); // Enable rendering
+
+    let status = ppu.peek(0x2002, &mock_cart);
+    assert_eq!(true, status & 0b10000000 > 0);
+    assert_eq!(true, ppu.status.in_vblank());
+
+    ppu.status.clear_in_vblank();
+    ppu.peek(0x2004, &mock_cart);
+    assert_eq!(
+        false,
+        ppu.sprite_renderer.read_data_increment_addr_called.get()
+    );
+    assert_eq!(true, ppu.sprite_renderer.read_data_called.get());
+}
+
+#[test]
+fn reset() {
+    let mut ppu = ppu_fixture();
+    let mut mock_cart = CartMock::default();
+    ppu.write(0x2000, 0xff, &mut mock_cart);
+    ppu.write(0x2001, 0xff, &mut mock_cart);
+    ppu.write(0x2005, 0xff, &mut mock_cart);
+    ppu.status.set_in_vblank();
+    ppu.odd_frame = true;
+
+    ppu.reset();
+    assert_eq!(0, *ppu.control);
+    assert_eq!(0, *ppu.mask);
+    assert_eq!(true, ppu.vram.reset_called.get());
+    assert_eq!(true, ppu.status.in_vblank());
+    assert_eq!(false, ppu.odd_frame);
+
+    // The write latch is cleared, so the next write is a first write again
+    assert_eq!(LatchState::FirstWrite(0xff), ppu.write_latch.write(0xff));
+}
+
+#[test]
+fn scanline_and_dot() {
+    let mut ppu = ppu_fixture();
+    assert_eq!(0, ppu.scanline());
+    assert_eq!(0, ppu.dot());
+
+    ppu.cycles = CYCLES_PER_SCANLINE * 241 + 21;
+    assert_eq!(241, ppu.scanline());
+    assert_eq!(21, ppu.dot());
+
+    ppu.cycles += super::CYCLES_PER_FRAME;
+    assert_eq!(241, ppu.scanline());
+    assert_eq!(21, ppu.dot());
+}
+
 #[test]
 fn odd_frame_cycle_skip() {
     let mut ppu = ppu_fixture();
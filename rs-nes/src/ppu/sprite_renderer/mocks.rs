@@ -14,6 +14,9 @@ pub struct MockSpriteRenderer {
     pub read_data_increment_addr_called: Cell<bool>,
     pub mock_addr: Cell<u8>,
     pub mock_data: Cell<u8>,
+    pub mock_sprite_overflow: Cell<bool>,
+    pub mock_pixel_value: Cell<u8>,
+    pub mock_color_index: Cell<u8>,
 }
 
 impl ISpriteRenderer for MockSpriteRenderer {
@@ -41,14 +44,16 @@ impl ISpriteRenderer for MockSpriteRenderer {
 
     fn start_sprite_evaluation(&mut self, _: u16, _: ControlRegister) {}
 
-    fn tick_sprite_evaluation(&mut self) {}
+    fn tick_sprite_evaluation(&mut self) -> bool {
+        self.mock_sprite_overflow.get()
+    }
 
     fn fill_registers<V: IVram, C: Cart>(&mut self, _: &V, _: ControlRegister, _: &C) {}
 
     fn current_pixel(&self) -> SpritePixel {
         SpritePixel {
-            value: 0,
-            color_index: 0,
+            value: self.mock_pixel_value.get(),
+            color_index: self.mock_color_index.get(),
             has_priority: true,
             is_sprite_zero: false,
         }
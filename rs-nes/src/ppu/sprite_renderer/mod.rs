@@ -82,7 +82,10 @@ pub trait ISpriteRenderer: Default {
     fn update_palettes<V: IVram>(&mut self, vram: &V);
     fn dec_x_counters(&mut self);
     fn start_sprite_evaluation(&mut self, scanline: u16, control: ControlRegister);
-    fn tick_sprite_evaluation(&mut self);
+
+    /// Runs a dot of sprite evaluation, returning whether a sprite overflow has been found on the
+    /// scanline being evaluated
+    fn tick_sprite_evaluation(&mut self) -> bool;
     fn fill_registers<V: IVram, C: Cart>(&mut self, vram: &V, control: ControlRegister, cart: &C);
     fn current_pixel(&self) -> SpritePixel;
 }
@@ -181,8 +184,9 @@ impl ISpriteRenderer for SpriteRenderer {
         self.sprite_evaluation = SpriteEvaluation::new(scanline as u8, control.sprite_size());
     }
 
-    fn tick_sprite_evaluation(&mut self) {
-        self.sprite_evaluation.tick(&self.primary_oam)
+    fn tick_sprite_evaluation(&mut self) -> bool {
+        self.sprite_evaluation.tick(&self.primary_oam);
+        self.sprite_evaluation.sprite_overflow()
     }
 
     fn fill_registers<V: IVram, C: Cart>(&mut self, vram: &V, control: ControlRegister, cart: &C) {
@@ -199,24 +203,35 @@ impl ISpriteRenderer for SpriteRenderer {
                 (0, 0)
             } else {
                 let tile_index = self.sprite_evaluation.read_secondary_oam(sprite_base + 1);
-
-                let fine_y = if attribute.flip_vertically() {
-                    7 - (self.sprite_evaluation.scanline() - tile_y)
-                } else {
-                    self.sprite_evaluation.scanline() - tile_y
-                };
-                debug_assert!(fine_y < 8);
+                let row = self.sprite_evaluation.scanline() - tile_y;
 
                 let tile_offset = match control.sprite_size() {
                     SpriteSize::X8 => {
-                        control.sprite_pattern_table_base() | (u16::from(tile_index) << 4)
+                        // Only the low bits of the row are used, in case the sprite was found
+                        // before the sprite size was changed to 8x8
+                        let fine_y = if attribute.flip_vertically() {
+                            7 - (row & 7)
+                        } else {
+                            row & 7
+                        };
+                        control.sprite_pattern_table_base()
+                            | (u16::from(tile_index) << 4)
+                            | u16::from(fine_y)
                     }
                     SpriteSize::X16 => {
-                        let actual_tile_index = tile_index & !1;
+                        // Bit 0 of the tile index selects the pattern table, and the sprite is made
+                        // of that tile with the bit cleared on top and the next tile below. Flipping
+                        // vertically swaps the two tiles as well as flipping each of them.
+                        let row = if attribute.flip_vertically() {
+                            15 - row
+                        } else {
+                            row
+                        };
                         let sprite_table_select = (u16::from(tile_index) & 1) << 12;
-                        sprite_table_select | u16::from(actual_tile_index)
+                        let tile = u16::from(tile_index & !1) + u16::from(row >> 3);
+                        sprite_table_select | (tile << 4) | u16::from(row & 7)
                     }
-                } + u16::from(fine_y);
+                };
 
                 let pattern_low = vram.read(tile_offset, cart);
                 let pattern_high = vram.read(tile_offset + 8, cart);
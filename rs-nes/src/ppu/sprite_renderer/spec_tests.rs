@@ -1,4 +1,5 @@
 use super::*;
+use crate::{mocks::CartMock, ppu::Vram};
 
 #[test]
 fn write_and_read() {
@@ -27,6 +28,134 @@ fn write_and_read_increment() {
     assert_eq!(0x1, oam.address.get())
 }
 
+#[test]
+fn fill_registers_8x8() {
+    // Tile 3 from the pattern table at $1000
+    let control = control_fixture(0b0000_1000);
+    let cart = cart_fixture();
+    for row in 0..8 {
+        let mut renderer = sprite_fixture(10, 0x03, 0);
+        renderer.start_sprite_evaluation(10 + row, control);
+        fill_registers(&mut renderer, control, &cart);
+        assert_eq!(pattern(0x103, row), renderer.pattern_low_shift_registers[0]);
+        assert_eq!(
+            pattern(0x103, row),
+            renderer.pattern_high_shift_registers[0]
+        );
+    }
+}
+
+#[test]
+fn fill_registers_8x8_flip_vertically() {
+    let control = control_fixture(0);
+    let cart = cart_fixture();
+    for row in 0..8 {
+        let mut renderer = sprite_fixture(10, 0x03, 0b1000_0000);
+        renderer.start_sprite_evaluation(10 + row, control);
+        fill_registers(&mut renderer, control, &cart);
+        assert_eq!(
+            pattern(0x03, 7 - row),
+            renderer.pattern_low_shift_registers[0]
+        );
+    }
+}
+
+#[test]
+fn fill_registers_8x16() {
+    // Bit 0 of the tile index selects the pattern table at $1000 regardless of PPUCTRL, and the
+    // top half is tile 2 with tile 3 below it
+    let control = control_fixture(0b0010_0000);
+    let cart = cart_fixture();
+    for row in 0..16 {
+        let mut renderer = sprite_fixture(10, 0x03, 0);
+        renderer.start_sprite_evaluation(10 + row, control);
+        fill_registers(&mut renderer, control, &cart);
+        let expected = if row < 8 {
+            pattern(0x102, row)
+        } else {
+            pattern(0x103, row - 8)
+        };
+        assert_eq!(
+            expected, renderer.pattern_low_shift_registers[0],
+            "row = {}",
+            row
+        );
+        assert_eq!(
+            expected, renderer.pattern_high_shift_registers[0],
+            "row = {}",
+            row
+        );
+    }
+}
+
+#[test]
+fn fill_registers_8x16_flip_vertically() {
+    // Flipping vertically puts the bottom tile on top, and flips each of the tiles
+    let control = control_fixture(0b0010_1000);
+    let cart = cart_fixture();
+    for row in 0..16 {
+        let mut renderer = sprite_fixture(10, 0x02, 0b1000_0000);
+        renderer.start_sprite_evaluation(10 + row, control);
+        fill_registers(&mut renderer, control, &cart);
+        let expected = if row < 8 {
+            pattern(0x03, 7 - row)
+        } else {
+            pattern(0x02, 15 - row)
+        };
+        assert_eq!(
+            expected, renderer.pattern_low_shift_registers[0],
+            "row = {}",
+            row
+        );
+    }
+}
+
+#[test]
+fn fill_registers_8x16_not_on_scanline() {
+    let control = control_fixture(0b0010_0000);
+    let cart = cart_fixture();
+    let mut renderer = sprite_fixture(10, 0x03, 0);
+    renderer.start_sprite_evaluation(26, control);
+    fill_registers(&mut renderer, control, &cart);
+    assert_eq!(0, renderer.pattern_low_shift_registers[0]);
+    assert_eq!(0, renderer.pattern_high_shift_registers[0]);
+}
+
+// Runs sprite evaluation for the scanline it was started on, then fetches the sprites found
+fn fill_registers(renderer: &mut SpriteRenderer, control: ControlRegister, cart: &CartMock) {
+    for _ in 65..257 {
+        renderer.tick_sprite_evaluation();
+    }
+    renderer.fill_registers(&Vram::default(), control, cart);
+}
+
+// The value of each byte of CHR identifies the pattern table, the low bits of the tile index and
+// the row it belongs to
+fn pattern(tile: u16, row: u16) -> u8 {
+    ((tile >> 1 & 0x80) | (tile << 3 & 0x78) | row) as u8
+}
+
+fn cart_fixture() -> CartMock {
+    let mut cart = CartMock::default();
+    for (addr, byte) in cart.chr.iter_mut().enumerate() {
+        *byte = pattern(addr as u16 >> 4, addr as u16 & 7);
+    }
+    cart
+}
+
+fn control_fixture(val: u8) -> ControlRegister {
+    let mut control = ControlRegister::default();
+    control.write(val);
+    control
+}
+
+// The only sprite in OAM is sprite 0
+fn sprite_fixture(y: u8, tile_index: u8, attributes: u8) -> SpriteRenderer {
+    let mut mem = [0xff_u8; 0x100];
+    mem[..4].copy_from_slice(&[y, tile_index, attributes, 0]);
+    fixture(&mem)
+}
+
 fn fixture(initial_values: &[u8]) -> SpriteRenderer {
     let mut mem = [0_u8; 0x100];
     for (i, byte) in initial_values.iter().enumerate() {
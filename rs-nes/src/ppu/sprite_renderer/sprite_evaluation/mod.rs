@@ -47,6 +47,10 @@ impl SpriteEvaluation {
         self.scanline
     }
 
+    pub fn sprite_overflow(&self) -> bool {
+        self.sprite_overflow
+    }
+
     pub fn tick(&mut self, primary_oam: &[u8]) {
         debug_assert!(self.cycle <= 191);
         debug_assert!(self.m < 4);
@@ -8,24 +8,30 @@ use std::cell::Cell;
 pub struct MockVram {
     pub mock_addr: Cell<u8>,
     pub mock_data: Cell<u8>,
+    pub mock_read: Cell<u8>,
+    pub mock_palette: Cell<u8>,
     pub scroll_write_called: Cell<bool>,
     pub control_write_called: Cell<bool>,
     pub coarse_x_increment_called: Cell<bool>,
     pub fine_y_increment_called: Cell<bool>,
     pub copy_horizontal_pos_to_addr_called: Cell<bool>,
     pub copy_vertical_pos_to_addr_called: Cell<bool>,
+    pub reset_called: Cell<bool>,
 }
 
 impl MockVram {
     pub fn reset_mock(&self) {
         self.mock_addr.set(0);
         self.mock_data.set(0);
+        self.mock_read.set(0);
+        self.mock_palette.set(0);
         self.scroll_write_called.set(false);
         self.control_write_called.set(false);
         self.coarse_x_increment_called.set(false);
         self.fine_y_increment_called.set(false);
         self.copy_horizontal_pos_to_addr_called.set(false);
         self.copy_vertical_pos_to_addr_called.set(false);
+        self.reset_called.set(false);
     }
 }
 
@@ -51,12 +57,16 @@ impl IVram for MockVram {
         self.mock_data.get()
     }
 
+    fn peek_ppu_data<C: Cart>(&self, _: &C) -> u8 {
+        self.mock_data.get()
+    }
+
     fn read<C: Cart>(&self, _: u16, _: &C) -> u8 {
-        0
+        self.mock_read.get()
     }
 
     fn read_palette(&self, _: u16) -> u8 {
-        0
+        self.mock_palette.get()
     }
 
     fn addr(&self) -> u16 {
@@ -90,4 +100,8 @@ impl IVram for MockVram {
     fn fine_x(&self) -> u8 {
         0
     }
+
+    fn reset(&self) {
+        self.reset_called.set(true);
+    }
 }
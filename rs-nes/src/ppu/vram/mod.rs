@@ -7,14 +7,43 @@ mod spec_tests;
 use crate::{
     cart::Cart,
     ppu::{control_register::IncrementAmount, write_latch::LatchState},
+    rom::Mirroring,
 };
 use std::cell::Cell;
 
+// The PPU address bus is 14 bits wide, so higher addresses are mirrored down
+const VRAM_ADDR_MASK: u16 = 0x3fff;
+
+// The console has 2 KB of VRAM, which is enough for two nametables
+const CIRAM_SIZE: usize = 0x800;
+
+// Where a nametable byte is stored, as an offset into the console's VRAM or the cart's
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum NametableAddr {
+    Ciram(usize),
+    Cart(usize),
+}
+
+fn nametable_addr(addr: u16, mirroring: Mirroring) -> NametableAddr {
+    let table = (addr as usize >> 10) & 3;
+    let offset = addr as usize & 0x3ff;
+    let page = match mirroring {
+        Mirroring::Horizontal => table >> 1,
+        Mirroring::Vertical => table & 1,
+        Mirroring::SingleScreenLower => 0,
+        Mirroring::SingleScreenUpper => 1,
+        Mirroring::FourScreen if table < 2 => table,
+        Mirroring::FourScreen => return NametableAddr::Cart(((table - 2) << 10) | offset),
+    };
+    NametableAddr::Ciram((page << 10) | offset)
+}
+
 pub trait IVram: Default {
     fn write_ppu_addr(&self, latch_state: LatchState);
     fn write_ppu_data<C: Cart>(&mut self, val: u8, inc_amount: IncrementAmount, cart: &mut C);
     fn read_ppu_data<C: Cart>(&self, inc_amount: IncrementAmount, cart: &C) -> u8;
     fn ppu_data<C: Cart>(&self, cart: &C) -> u8;
+    fn peek_ppu_data<C: Cart>(&self, cart: &C) -> u8;
     fn read<C: Cart>(&self, addr: u16, cart: &C) -> u8;
     fn read_palette(&self, addr: u16) -> u8;
     fn addr(&self) -> u16;
@@ -25,11 +54,14 @@ pub trait IVram: Default {
     fn copy_horizontal_pos_to_addr(&self);
     fn copy_vertical_pos_to_addr(&self);
     fn fine_x(&self) -> u8;
+
+    /// Clears the scroll position and the PPUDATA read buffer, keeping the VRAM address
+    fn reset(&self);
 }
 
 pub struct Vram {
     address: Cell<u16>,
-    name_tables: [u8; 0x1000],
+    ciram: [u8; CIRAM_SIZE],
     palette: [u8; 0x20],
     ppu_data_buffer: Cell<u8>,
     t: Cell<u16>,
@@ -40,7 +72,7 @@ impl Default for Vram {
     fn default() -> Self {
         Vram {
             address: Cell::new(0),
-            name_tables: [0; 0x1000],
+            ciram: [0; CIRAM_SIZE],
             palette: [0; 0x20],
             ppu_data_buffer: Cell::new(0),
             t: Cell::new(0),
@@ -49,6 +81,33 @@ impl Default for Vram {
     }
 }
 
+impl Vram {
+    fn increment_address(&self, inc_amount: IncrementAmount) {
+        // v is a 15 bit register, so the increment wraps around instead of overflowing
+        let inc = match inc_amount {
+            IncrementAmount::One => 1,
+            IncrementAmount::ThirtyTwo => 32,
+        };
+        self.address
+            .set(self.address.get().wrapping_add(inc) & 0x7fff);
+    }
+
+    // The mirroring is looked up on every access, since mappers can change it at any time
+    fn read_nametable<C: Cart>(&self, addr: u16, cart: &C) -> u8 {
+        match nametable_addr(addr, cart.mirroring()) {
+            NametableAddr::Ciram(offset) => self.ciram[offset],
+            NametableAddr::Cart(offset) => cart.read_vram(offset),
+        }
+    }
+
+    fn write_nametable<C: Cart>(&mut self, addr: u16, val: u8, cart: &mut C) {
+        match nametable_addr(addr, cart.mirroring()) {
+            NametableAddr::Ciram(offset) => self.ciram[offset] = val,
+            NametableAddr::Cart(offset) => cart.write_vram(offset, val),
+        }
+    }
+}
+
 impl IVram for Vram {
     fn write_ppu_addr(&self, latch_state: LatchState) {
         // Addresses greater than 0x3fff are mirrored down
@@ -70,13 +129,13 @@ impl IVram for Vram {
     }
 
     fn write_ppu_data<C: Cart>(&mut self, val: u8, inc_amount: IncrementAmount, cart: &mut C) {
-        let addr = self.address.get();
+        let addr = self.address.get() & VRAM_ADDR_MASK;
 
         if addr < 0x2000 {
             cart.write_chr(addr, val);
         } else if addr < 0x3f00 {
-            self.name_tables[addr as usize & 0x0fff] = val;
-        } else if addr < 0x4000 {
+            self.write_nametable(addr, val, cart);
+        } else {
             let addr = addr as usize & 0x1f;
             // Certain sprite addresses are mirrored back into background addresses
             let addr = match addr & 0xf {
@@ -89,23 +148,17 @@ impl IVram for Vram {
             self.palette[addr] = val;
         }
 
-        match inc_amount {
-            IncrementAmount::One => self.address.set(self.address.get() + 1),
-            IncrementAmount::ThirtyTwo => self.address.set(self.address.get() + 32),
-        }
+        self.increment_address(inc_amount);
     }
 
     fn read_ppu_data<C: Cart>(&self, inc_amount: IncrementAmount, cart: &C) -> u8 {
         let val = self.ppu_data(cart);
-        match inc_amount {
-            IncrementAmount::One => self.address.set(self.address.get() + 1),
-            IncrementAmount::ThirtyTwo => self.address.set(self.address.get() + 32),
-        }
+        self.increment_address(inc_amount);
         val
     }
 
     fn ppu_data<C: Cart>(&self, cart: &C) -> u8 {
-        let addr = self.address.get();
+        let addr = self.address.get() & VRAM_ADDR_MASK;
         let val = self.read(addr, cart);
 
         // When reading while the VRAM address is in the range 0-$3EFF (i.e., before the palettes),
@@ -122,16 +175,25 @@ impl IVram for Vram {
         }
     }
 
+    /// The value `ppu_data` would return, without updating the internal read buffer
+    fn peek_ppu_data<C: Cart>(&self, cart: &C) -> u8 {
+        let addr = self.address.get() & VRAM_ADDR_MASK;
+        if addr < 0x3f00 {
+            self.ppu_data_buffer.get()
+        } else {
+            self.read(addr, cart)
+        }
+    }
+
     fn read<C: Cart>(&self, addr: u16, cart: &C) -> u8 {
+        let addr = addr & VRAM_ADDR_MASK;
         if addr < 0x2000 {
             cart.read_chr(addr)
         } else if addr < 0x3f00 {
-            self.name_tables[addr as usize & 0x0fff]
-        } else if addr < 0x4000 {
+            self.read_nametable(addr, cart)
+        } else {
             let addr = addr & 0x1f;
             self.read_palette(addr)
-        } else {
-            panic!("Invalid vram read")
         }
     }
 
@@ -251,4 +313,10 @@ impl IVram for Vram {
     fn fine_x(&self) -> u8 {
         self.fine_x.get()
     }
+
+    fn reset(&self) {
+        self.t.set(0);
+        self.fine_x.set(0);
+        self.ppu_data_buffer.set(0);
+    }
 }
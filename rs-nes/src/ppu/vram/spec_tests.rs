@@ -5,6 +5,7 @@ use crate::{
         vram::{IVram, Vram},
         write_latch::LatchState,
     },
+    rom::Mirroring,
 };
 
 #[test]
@@ -23,21 +24,21 @@ fn write_address() {
 }
 
 #[test]
-#[ignore] // TODO: Fix once we have a mapping mechanism in place
 fn internal_memory_mapping_read() {
     let mut vram = Vram::default();
-    let cart = mock_cart();
-    mock_cart_with_chr(vec![1; 0x2000]);
-    vram.name_tables = [2; 0x1000];
+    let mut cart = mock_cart_with_chr(vec![1; 0x2000]);
+    cart.vram = [3; 0x800];
+    vram.ciram = [2; 0x800];
 
-    vram.ppu_data_buffer.set(1);
-    for _ in 0..0x2000 {
-        assert_eq!(1, vram.read_ppu_data(IncrementAmount::One, &cart))
+    for addr in 0..0x2000 {
+        assert_eq!(1, vram.read(addr, &cart));
     }
 
-    vram.ppu_data_buffer.set(2);
-    for _ in 0x2000..0x3f00 {
-        assert_eq!(2, vram.read_ppu_data(IncrementAmount::One, &cart))
+    // With four-screen mirroring, the last two nametables are on the cart, and $3000-$3EFF
+    // mirrors $2000-$2EFF
+    for addr in 0x2000..0x3f00 {
+        let expected = if addr & 0x0800 == 0 { 2 } else { 3 };
+        assert_eq!(expected, vram.read(addr, &cart));
     }
 }
 
@@ -70,7 +71,44 @@ fn vram_read_buffering_behavior() {
 }
 
 #[test]
-#[ignore] // TODO: Fix once we have a mapping mechanism in place
+fn peek_ppu_data() {
+    let mut vram = Vram::default();
+    let cart = mock_cart();
+    vram.ciram = [0xaa; 0x800];
+    vram.palette = [0xcc; 0x20];
+
+    // Peeking returns what a read would without updating the buffer or the address
+    vram.address.set(0x2000);
+    vram.ppu_data_buffer.set(0x11);
+    assert_eq!(0x11, vram.peek_ppu_data(&cart));
+    assert_eq!(0x11, vram.ppu_data_buffer.get());
+    assert_eq!(0x2000, vram.address.get());
+
+    vram.address.set(0x3f00);
+    assert_eq!(0xcc, vram.peek_ppu_data(&cart));
+}
+
+#[test]
+fn ppu_data_address_mirroring() {
+    let mut vram = Vram::default();
+    let mut cart = mock_cart_with_chr(vec![0xaa; 0x2000]);
+    vram.palette = [0xcc; 0x20];
+
+    // Incrementing past 0x3fff mirrors back down to the pattern tables
+    vram.address.set(0x3fff);
+    assert_eq!(0xcc, vram.read_ppu_data(IncrementAmount::One, &cart));
+    assert_eq!(0x4000, vram.address.get());
+    vram.read_ppu_data(IncrementAmount::One, &cart);
+    assert_eq!(0xaa, vram.read_ppu_data(IncrementAmount::One, &cart));
+
+    // The address register is 15 bits wide
+    vram.address.set(0x7fe0);
+    vram.write_ppu_data(0x11, IncrementAmount::ThirtyTwo, &mut cart);
+    assert_eq!(0x11, vram.palette[0]);
+    assert_eq!(0, vram.address.get());
+}
+
+#[test]
 fn write_mapping() {
     // Tests pattern and nametable write mappings, palette mapping tested separately
 
@@ -85,8 +123,65 @@ fn write_mapping() {
         vram.write_ppu_data(2, IncrementAmount::One, &mut cart)
     }
 
-    //assert_eq!(true, vram.rom.chr.into_iter().all(|val| val == 1));
-    assert_eq!(true, vram.name_tables.into_iter().all(|val| *val == 2));
+    assert_eq!(true, cart.chr.iter().all(|val| *val == 1));
+    assert_eq!(true, vram.ciram.iter().all(|val| *val == 2));
+    assert_eq!(true, cart.vram.iter().all(|val| *val == 2));
+}
+
+#[test]
+fn nametable_mirroring() {
+    let mut vram = Vram::default();
+    let mut cart = mock_cart();
+    for i in 0..0x800 {
+        vram.ciram[i] = (i >> 10) as u8 + 1;
+    }
+    cart.vram = [3; 0x800];
+
+    // The nametable at each of $2000, $2400, $2800 and $2C00 and its mirror at $3000 and up
+    let read_nametables = |cart: &CartMock| {
+        [
+            0x2000, 0x2400, 0x2800, 0x2c00, 0x3000, 0x3400, 0x3800, 0x3c00,
+        ]
+        .iter()
+        .map(|addr| vram.read(addr + 0x123, cart))
+        .collect::<Vec<_>>()
+    };
+
+    cart.mirroring = Mirroring::Horizontal;
+    assert_eq!(vec![1, 1, 2, 2, 1, 1, 2, 2], read_nametables(&cart));
+
+    // Mappers can change the mirroring at any time
+    cart.mirroring = Mirroring::Vertical;
+    assert_eq!(vec![1, 2, 1, 2, 1, 2, 1, 2], read_nametables(&cart));
+
+    cart.mirroring = Mirroring::SingleScreenLower;
+    assert_eq!(vec![1; 8], read_nametables(&cart));
+
+    cart.mirroring = Mirroring::SingleScreenUpper;
+    assert_eq!(vec![2; 8], read_nametables(&cart));
+
+    cart.mirroring = Mirroring::FourScreen;
+    assert_eq!(vec![1, 2, 3, 3, 1, 2, 3, 3], read_nametables(&cart));
+}
+
+#[test]
+fn nametable_mirroring_write() {
+    let mut vram = Vram::default();
+    let mut cart = mock_cart();
+    cart.mirroring = Mirroring::Horizontal;
+
+    // Writing $2C05 writes the second nametable, which $2805 mirrors
+    vram.address.set(0x2c05);
+    vram.write_ppu_data(0x11, IncrementAmount::One, &mut cart);
+    assert_eq!(0x11, vram.ciram[0x405]);
+    assert_eq!(0x11, vram.read(0x2805, &cart));
+    assert_eq!(0, vram.read(0x2405, &cart));
+
+    cart.mirroring = Mirroring::FourScreen;
+    vram.address.set(0x2c05);
+    vram.write_ppu_data(0x22, IncrementAmount::One, &mut cart);
+    assert_eq!(0x22, cart.vram[0x405]);
+    assert_eq!(0x11, vram.ciram[0x405]);
 }
 
 #[test]
@@ -278,6 +373,21 @@ fn control_write() {
     assert_eq!(0b0111_0111_1111_1111, vram.t.get());
 }
 
+#[test]
+fn reset() {
+    let vram = Vram::default();
+    vram.address.set(0x2345);
+    vram.t.set(0x1234);
+    vram.fine_x.set(0x5);
+    vram.ppu_data_buffer.set(0xff);
+
+    vram.reset();
+    assert_eq!(0x2345, vram.address.get());
+    assert_eq!(0, vram.t.get());
+    assert_eq!(0, vram.fine_x.get());
+    assert_eq!(0, vram.ppu_data_buffer.get());
+}
+
 #[test]
 fn palette_write_mapping() {
     // Verifying the following:
@@ -0,0 +1,257 @@
+#[cfg(test)]
+mod spec_tests;
+
+use crate::{apu::IApu, cart::Cart, input::IInput, interconnect::NesInterconnect, ppu::IPpu};
+use cpu6502::cpu::{Cpu, FrameKind, Interconnect, Interrupt};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+// Cycles taken to push the return address and status and fetch the vector of an interrupt
+const INTERRUPT_CYCLES: usize = 7;
+
+// A frame of the CPU's call stack, as it's shown in reports
+type Frame = (FrameKind, u16);
+
+/// The cycles attributed to a subroutine or interrupt handler
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct SubroutineProfile {
+    pub calls: usize,
+    /// Cycles spent in the subroutine and everything it called, including DMA
+    pub inclusive_cycles: usize,
+    /// Cycles spent executing the instructions of the subroutine itself
+    pub exclusive_cycles: usize,
+}
+
+/// Attributes CPU cycles to the instructions and subroutines that spent them
+///
+/// Like the `Tracer`, profiling is opt-in and only happens for instructions stepped through the
+/// `Profiler`. Cycles are attributed to the frames of the CPU's call stack, which follows `JSR`,
+/// `BRK` and interrupts and stays consistent when code discards return addresses or uses `RTS` as
+/// a jump table.
+///
+/// Cycles the CPU spends halted by OAM or DMC DMA aren't attributed to the instruction that was
+/// halted, but are counted separately and included in the inclusive cycles of every frame on the
+/// stack.
+pub struct Profiler {
+    address_cycles: Vec<usize>,
+    subroutines: HashMap<u16, SubroutineProfile>,
+    stacks: HashMap<Vec<Frame>, usize>,
+    dma_stacks: HashMap<Vec<Frame>, usize>,
+    // The call stack the cycles being recorded were spent in, kept to avoid allocating every step
+    stack: Vec<Frame>,
+    total_cycles: usize,
+    dma_cycles: usize,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Profiler {
+            address_cycles: vec![0; 0x10000],
+            subroutines: HashMap::new(),
+            stacks: HashMap::new(),
+            dma_stacks: HashMap::new(),
+            stack: Vec::new(),
+            total_cycles: 0,
+            dma_cycles: 0,
+        }
+    }
+}
+
+impl Profiler {
+    /// Executes the instruction at the program counter, attributing the cycles it takes
+    pub fn step<P: IPpu, A: IApu, I: IInput, C: Cart>(
+        &mut self,
+        cpu: &mut Cpu<NesInterconnect<P, A, I, C>>,
+    ) -> Interrupt {
+        let pc = cpu.state().pc();
+        let start_cycles = cpu.interconnect.elapsed_cycles();
+        let start_dma_cycles = cpu.interconnect.dma_cycles();
+
+        // The cycles of a call belong to the caller, and those of a return to the callee
+        self.load_stack(cpu);
+
+        let interrupt = cpu.step();
+
+        let end_state = cpu.state();
+        let dma_cycles = cpu.interconnect.dma_cycles() - start_dma_cycles;
+        let mut cycles = cpu.interconnect.elapsed_cycles() - start_cycles - dma_cycles;
+
+        // A jammed CPU reports interrupts without servicing them
+        let interrupted = interrupt != Interrupt::None && !end_state.jammed();
+        if interrupted {
+            cycles -= INTERRUPT_CYCLES;
+        }
+        self.record(pc, cycles, dma_cycles);
+
+        // Frames pushed by the instruction or an interrupt started during this step
+        for frame in cpu.call_stack().frames() {
+            if frame.cycle >= start_cycles {
+                self.subroutines.entry(frame.target).or_default().calls += 1;
+            }
+        }
+
+        if interrupted {
+            self.load_stack(cpu);
+            self.record(end_state.pc(), INTERRUPT_CYCLES, 0);
+        }
+
+        interrupt
+    }
+
+    /// Clears everything recorded so far
+    pub fn reset(&mut self) {
+        *self = Profiler::default();
+    }
+
+    /// The cycles spent executing the instruction at `addr`, excluding DMA. The cycles taken
+    /// to enter an interrupt handler are attributed to its first instruction.
+    pub fn address_cycles(&self, addr: u16) -> usize {
+        self.address_cycles[addr as usize]
+    }
+
+    /// The cycles attributed to the subroutine or interrupt handler starting at `target`
+    pub fn subroutine(&self, target: u16) -> Option<SubroutineProfile> {
+        self.subroutines.get(&target).cloned()
+    }
+
+    /// The cycles stepped through the profiler, including DMA
+    pub fn total_cycles(&self) -> usize {
+        self.total_cycles
+    }
+
+    pub fn dma_cycles(&self) -> usize {
+        self.dma_cycles
+    }
+
+    /// Writes the subroutines sorted by inclusive cycles, followed by the addresses sorted by
+    /// cycles
+    pub fn write_report<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(
+            out,
+            "Total cycles: {} (DMA: {})",
+            self.total_cycles, self.dma_cycles
+        )?;
+        writeln!(out)?;
+
+        let mut subroutines = self.subroutines.iter().collect::<Vec<_>>();
+        subroutines.sort_by(|(a_target, a), (b_target, b)| {
+            b.inclusive_cycles
+                .cmp(&a.inclusive_cycles)
+                .then(a_target.cmp(b_target))
+        });
+        writeln!(
+            out,
+            "{:<8}{:>10}{:>12}{:>12}",
+            "Target", "Calls", "Inclusive", "Exclusive"
+        )?;
+        for (target, profile) in subroutines {
+            writeln!(
+                out,
+                "${:04X}   {:>10}{:>12}{:>12}",
+                target, profile.calls, profile.inclusive_cycles, profile.exclusive_cycles
+            )?;
+        }
+        writeln!(out)?;
+
+        let mut addresses = self
+            .address_cycles
+            .iter()
+            .enumerate()
+            .filter(|&(_, &cycles)| cycles > 0)
+            .collect::<Vec<_>>();
+        addresses.sort_by(|(a_addr, a), (b_addr, b)| b.cmp(a).then(a_addr.cmp(b_addr)));
+        writeln!(out, "{:<8}{:>10}{:>8}", "Address", "Cycles", "%")?;
+        for (addr, &cycles) in addresses {
+            writeln!(
+                out,
+                "${:04X}   {:>10}{:>8.2}",
+                addr,
+                cycles,
+                cycles as f64 * 100.0 / self.total_cycles as f64
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the cycles spent in each call stack in the folded format read by flamegraph.pl and
+    /// compatible tools, e.g. `main;$C123;$C456 1024`. DMA is shown as a call made by the
+    /// instruction that was halted.
+    pub fn write_folded<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let mut lines = self
+            .stacks
+            .iter()
+            .map(|(stack, cycles)| (folded_stack(stack), *cycles))
+            .chain(
+                self.dma_stacks
+                    .iter()
+                    .map(|(stack, cycles)| (folded_stack(stack) + ";DMA", *cycles)),
+            )
+            .collect::<Vec<_>>();
+        lines.sort();
+        for (stack, cycles) in lines {
+            writeln!(out, "{} {}", stack, cycles)?;
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, pc: u16, cycles: usize, dma_cycles: usize) {
+        self.address_cycles[pc as usize] += cycles;
+        self.total_cycles += cycles + dma_cycles;
+        self.dma_cycles += dma_cycles;
+
+        for (i, &(_, target)) in self.stack.iter().enumerate() {
+            // Recursive calls are only counted once
+            if self.stack[..i].iter().any(|&(_, t)| t == target) {
+                continue;
+            }
+            let profile = self.subroutines.entry(target).or_default();
+            profile.inclusive_cycles += cycles + dma_cycles;
+        }
+        if let Some(&(_, target)) = self.stack.last() {
+            self.subroutines.entry(target).or_default().exclusive_cycles += cycles;
+        }
+
+        if cycles > 0 {
+            add_stack_cycles(&mut self.stacks, &self.stack, cycles);
+        }
+        if dma_cycles > 0 {
+            add_stack_cycles(&mut self.dma_stacks, &self.stack, dma_cycles);
+        }
+    }
+
+    fn load_stack<P: IPpu, A: IApu, I: IInput, C: Cart>(
+        &mut self,
+        cpu: &Cpu<NesInterconnect<P, A, I, C>>,
+    ) {
+        let frames = cpu.call_stack().frames().iter();
+        self.stack.clear();
+        self.stack
+            .extend(frames.map(|frame| (frame.kind, frame.target)));
+    }
+}
+
+fn add_stack_cycles(stacks: &mut HashMap<Vec<Frame>, usize>, stack: &[Frame], cycles: usize) {
+    // Avoids copying the stack for every instruction once it has been seen
+    match stacks.get_mut(stack) {
+        Some(total) => *total += cycles,
+        None => {
+            stacks.insert(stack.to_vec(), cycles);
+        }
+    }
+}
+
+fn folded_stack(stack: &[Frame]) -> String {
+    let mut folded = "main".to_owned();
+    for &(kind, target) in stack {
+        folded.push(';');
+        folded.push_str(&match kind {
+            FrameKind::Subroutine => format!("${:04X}", target),
+            FrameKind::Break => format!("BRK ${:04X}", target),
+            FrameKind::Nmi => format!("NMI ${:04X}", target),
+            FrameKind::Irq => format!("IRQ ${:04X}", target),
+        });
+    }
+    folded
+}
@@ -0,0 +1,224 @@
+use crate::{
+    interconnect::NesInterconnect,
+    mocks::{ApuMock, CartMock, InputMock, PpuMock},
+    profiler::{Profiler, SubroutineProfile},
+};
+use cpu6502::cpu::{Cpu, FrameKind, Interconnect, Interrupt};
+
+type ProfileCpu = Cpu<NesInterconnect<PpuMock, ApuMock, InputMock, CartMock>>;
+
+fn new_fixture(program: &[(u16, &[u8])]) -> ProfileCpu {
+    let mut cpu = Cpu::new(NesInterconnect::new(CartMock::default()), 0x0400);
+    for &(addr, bytes) in program {
+        for (i, byte) in bytes.iter().enumerate() {
+            cpu.interconnect.write(addr + i as u16, *byte);
+        }
+    }
+    let mut state = cpu.state();
+    state.set_sp(0xfd);
+    state.set_status(0x24);
+    cpu.set_state(state);
+    cpu
+}
+
+fn run(profiler: &mut Profiler, cpu: &mut ProfileCpu, steps: usize) {
+    for _ in 0..steps {
+        profiler.step(cpu);
+    }
+}
+
+fn call_stack(cpu: &ProfileCpu) -> Vec<(FrameKind, u16)> {
+    let frames = cpu.call_stack().frames().iter();
+    frames.map(|frame| (frame.kind, frame.target)).collect()
+}
+
+fn folded(profiler: &Profiler) -> String {
+    let mut folded = Vec::new();
+    profiler.write_folded(&mut folded).unwrap();
+    String::from_utf8(folded).unwrap()
+}
+
+// JSR $0410, JSR $0420, NOP, where $0410 calls $0420 as well
+fn nested_calls() -> ProfileCpu {
+    new_fixture(&[
+        (0x0400, &[0x20, 0x10, 0x04, 0x20, 0x20, 0x04, 0xea]),
+        (0x0410, &[0x20, 0x20, 0x04, 0x60]),
+        (0x0420, &[0xea, 0x60]),
+    ])
+}
+
+#[test]
+fn subroutine_cycles() {
+    let mut cpu = nested_calls();
+    let mut profiler = Profiler::default();
+    run(&mut profiler, &mut cpu, 9);
+
+    assert_eq!(
+        Some(SubroutineProfile {
+            calls: 1,
+            inclusive_cycles: 20,
+            exclusive_cycles: 12,
+        }),
+        profiler.subroutine(0x0410)
+    );
+    assert_eq!(
+        Some(SubroutineProfile {
+            calls: 2,
+            inclusive_cycles: 16,
+            exclusive_cycles: 16,
+        }),
+        profiler.subroutine(0x0420)
+    );
+    assert_eq!(None, profiler.subroutine(0x0400));
+    assert_eq!(42, profiler.total_cycles());
+    assert_eq!(cpu.interconnect.elapsed_cycles(), profiler.total_cycles());
+    assert_eq!(true, call_stack(&cpu).is_empty());
+}
+
+#[test]
+fn address_cycles() {
+    let mut cpu = nested_calls();
+    let mut profiler = Profiler::default();
+    run(&mut profiler, &mut cpu, 9);
+
+    assert_eq!(6, profiler.address_cycles(0x0400));
+    assert_eq!(4, profiler.address_cycles(0x0420));
+    assert_eq!(12, profiler.address_cycles(0x0421));
+    assert_eq!(2, profiler.address_cycles(0x0406));
+    assert_eq!(0, profiler.address_cycles(0x0407));
+}
+
+#[test]
+fn folded_stacks() {
+    let mut cpu = nested_calls();
+    let mut profiler = Profiler::default();
+    run(&mut profiler, &mut cpu, 9);
+
+    assert_eq!(
+        "main 14\nmain;$0410 12\nmain;$0410;$0420 8\nmain;$0420 8\n",
+        folded(&profiler)
+    );
+}
+
+#[test]
+fn report() {
+    let mut cpu = nested_calls();
+    let mut profiler = Profiler::default();
+    run(&mut profiler, &mut cpu, 9);
+
+    let mut report = Vec::new();
+    profiler.write_report(&mut report).unwrap();
+    let report = String::from_utf8(report).unwrap();
+    let lines = report.lines().collect::<Vec<_>>();
+
+    assert_eq!("Total cycles: 42 (DMA: 0)", lines[0]);
+    assert_eq!("Target       Calls   Inclusive   Exclusive", lines[2]);
+    assert_eq!("$0410            1          20          12", lines[3]);
+    assert_eq!("$0420            2          16          16", lines[4]);
+    assert_eq!("Address     Cycles       %", lines[6]);
+    assert_eq!("$0421           12   28.57", lines[7]);
+}
+
+#[test]
+fn oam_dma() {
+    // JSR $0410, where $0410 does LDA #$02, STA $4014, NOP
+    let mut cpu = new_fixture(&[
+        (0x0400, &[0x20, 0x10, 0x04]),
+        (0x0410, &[0xa9, 0x02, 0x8d, 0x14, 0x40, 0xea]),
+    ]);
+    let mut profiler = Profiler::default();
+    run(&mut profiler, &mut cpu, 3);
+    assert_eq!(0, cpu.interconnect.dma_cycles());
+
+    // The CPU is halted when it fetches the NOP
+    run(&mut profiler, &mut cpu, 1);
+    let dma_cycles = cpu.interconnect.dma_cycles();
+    assert_eq!(true, dma_cycles > 0);
+    assert_eq!(dma_cycles, profiler.dma_cycles());
+    assert_eq!(4, profiler.address_cycles(0x0412));
+    assert_eq!(2, profiler.address_cycles(0x0415));
+    assert_eq!(cpu.interconnect.elapsed_cycles(), profiler.total_cycles());
+
+    let profile = profiler.subroutine(0x0410).unwrap();
+    assert_eq!(8, profile.exclusive_cycles);
+    assert_eq!(8 + dma_cycles, profile.inclusive_cycles);
+
+    assert_eq!(
+        format!("main 6\nmain;$0410 8\nmain;$0410;DMA {}\n", dma_cycles),
+        folded(&profiler)
+    );
+}
+
+#[test]
+fn rts_jump_table() {
+    // $0410 jumps to $0420 by pushing $041F and returning, then $0420 returns to the caller
+    let mut cpu = new_fixture(&[
+        (0x0400, &[0x20, 0x10, 0x04, 0xea]),
+        (0x0410, &[0xa9, 0x04, 0x48, 0xa9, 0x1f, 0x48, 0x60]),
+        (0x0420, &[0xea, 0x60]),
+    ]);
+    let mut profiler = Profiler::default();
+
+    run(&mut profiler, &mut cpu, 6);
+    assert_eq!(0x0420, cpu.state().pc());
+    assert_eq!(vec![(FrameKind::Subroutine, 0x0410)], call_stack(&cpu));
+
+    // $0420 wasn't called, so its cycles belong to $0410
+    run(&mut profiler, &mut cpu, 2);
+    assert_eq!(0x0403, cpu.state().pc());
+    assert_eq!(true, call_stack(&cpu).is_empty());
+    assert_eq!(None, profiler.subroutine(0x0420));
+    assert_eq!("main 6\nmain;$0410 24\n", folded(&profiler));
+}
+
+#[test]
+fn interrupts() {
+    // The IRQ handler at $0420 does NOP, RTI
+    let mut cpu = new_fixture(&[
+        (0x0400, &[0xea, 0xea, 0xea]),
+        (0x0420, &[0xea, 0x40]),
+        (0xfffe, &[0x20, 0x04]),
+    ]);
+    let mut state = cpu.state();
+    state.set_status(0x20);
+    cpu.set_state(state);
+    cpu.interconnect.apu.set_frame_irq(true);
+
+    let mut profiler = Profiler::default();
+    while profiler.step(&mut cpu) != Interrupt::Irq {}
+    cpu.interconnect.apu.set_frame_irq(false);
+
+    assert_eq!(vec![(FrameKind::Irq, 0x0420)], call_stack(&cpu));
+    assert_eq!(7, profiler.address_cycles(0x0420));
+
+    run(&mut profiler, &mut cpu, 2);
+    assert_eq!(true, call_stack(&cpu).is_empty());
+    assert_eq!(
+        Some(SubroutineProfile {
+            calls: 1,
+            inclusive_cycles: 15,
+            exclusive_cycles: 15,
+        }),
+        profiler.subroutine(0x0420)
+    );
+    assert_eq!(cpu.interconnect.elapsed_cycles(), profiler.total_cycles());
+}
+
+#[test]
+fn brk() {
+    // BRK enters the handler at $0420, which does NOP, RTI
+    let mut cpu = new_fixture(&[
+        (0x0400, &[0x00, 0x00, 0xea]),
+        (0x0420, &[0xea, 0x40]),
+        (0xfffe, &[0x20, 0x04]),
+    ]);
+    let mut profiler = Profiler::default();
+
+    run(&mut profiler, &mut cpu, 1);
+    assert_eq!(vec![(FrameKind::Break, 0x0420)], call_stack(&cpu));
+
+    run(&mut profiler, &mut cpu, 2);
+    assert_eq!(0x0402, cpu.state().pc());
+    assert_eq!(1, profiler.subroutine(0x0420).unwrap().calls);
+    assert_eq!("main 7\nmain;BRK $0420 8\n", folded(&profiler));
+}
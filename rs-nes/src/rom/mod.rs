@@ -15,10 +15,18 @@ pub enum VideoStandard {
     Indeterminite,
 }
 
-#[derive(Copy, Clone, Debug)]
+/// How the four nametables are mapped onto VRAM
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Mirroring {
+    /// $2000 and $2400 share the first 1 KB of VRAM, and $2800 and $2C00 the second
     Horizontal,
+    /// $2000 and $2800 share the first 1 KB of VRAM, and $2400 and $2C00 the second
     Vertical,
+    /// Every nametable is the first 1 KB of VRAM
+    SingleScreenLower,
+    /// Every nametable is the second 1 KB of VRAM
+    SingleScreenUpper,
+    /// $2000 and $2400 are VRAM, and $2800 and $2C00 are 2 KB of extra VRAM on the cart
     FourScreen,
 }
 
@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod spec_tests;
+
+use crate::{apu::IApu, cart::Cart, input::IInput, interconnect::NesInterconnect, ppu::IPpu};
+use cpu6502::cpu::{
+    disassembler::{disassemble, AddressingMode, Instruction, Mnemonic},
+    Cpu, CpuState, Interconnect, Interrupt,
+};
+use std::io::{self, Write};
+
+/// Writes a line in the format of nestest.log for every instruction it steps
+///
+/// Tracing is opt-in: stepping the CPU through a `Tracer` instead of calling `Cpu::step` directly
+/// is the only thing that enables it, so an untraced `Nes` pays nothing for its existence.
+pub struct Tracer<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Tracer<W> {
+    pub fn new(out: W) -> Self {
+        Tracer { out }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Traces the instruction at the program counter, then executes it
+    pub fn step<P: IPpu, A: IApu, I: IInput, C: Cart>(
+        &mut self,
+        cpu: &mut Cpu<NesInterconnect<P, A, I, C>>,
+    ) -> io::Result<Interrupt> {
+        writeln!(self.out, "{}", trace_line(cpu))?;
+        Ok(cpu.step())
+    }
+}
+
+/// Formats the state of the CPU before executing the instruction at the program counter, e.g.
+///
+/// `C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:7`
+///
+/// Unofficial opcodes are marked with a `*`, and the values at any memory operands are shown as
+/// they were before the instruction executes.
+pub fn trace_line<P: IPpu, A: IApu, I: IInput, C: Cart>(
+    cpu: &Cpu<NesInterconnect<P, A, I, C>>,
+) -> String {
+    let bus = &cpu.interconnect;
+    let state = cpu.state();
+    let instruction = disassemble(bus, state.pc());
+
+    let bytes = instruction
+        .bytes()
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let disassembly = format!(
+        "{} {}",
+        mnemonic(&instruction),
+        operand(bus, &state, &instruction)
+    );
+
+    format!(
+        "{:04X}  {:<9}{}{:<32}A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} PPU:{:>3},{:>3} CYC:{}",
+        state.pc(),
+        bytes,
+        if instruction.is_official() { ' ' } else { '*' },
+        disassembly.trim_end(),
+        state.acc(),
+        state.x(),
+        state.y(),
+        state.status(),
+        state.sp(),
+        bus.ppu.scanline(),
+        bus.ppu.dot(),
+        bus.elapsed_cycles()
+    )
+}
+
+fn mnemonic(instruction: &Instruction) -> &'static str {
+    // nestest.log predates the ISC name
+    match instruction.mnemonic() {
+        Mnemonic::Isc => "ISB",
+        mnemonic => mnemonic.as_str(),
+    }
+}
+
+fn operand<B: Interconnect>(bus: &B, state: &CpuState, instruction: &Instruction) -> String {
+    let operand = instruction.operand();
+    let op8 = operand.first().cloned().unwrap_or(0);
+    let op16 = u16::from(op8) | u16::from(operand.get(1).cloned().unwrap_or(0)) << 8;
+    let read16_zp = |addr: u8| {
+        u16::from(bus.peek(u16::from(addr)))
+            | u16::from(bus.peek(u16::from(addr.wrapping_add(1)))) << 8
+    };
+
+    match instruction.mode() {
+        AddressingMode::Implied => String::new(),
+        AddressingMode::Accumulator => "A".to_owned(),
+        AddressingMode::Immediate => format!("#${:02X}", op8),
+        AddressingMode::ZeroPage => format!("${:02X} = {:02X}", op8, bus.peek(u16::from(op8))),
+        AddressingMode::ZeroPageX => {
+            let addr = op8.wrapping_add(state.x());
+            format!(
+                "${:02X},X @ {:02X} = {:02X}",
+                op8,
+                addr,
+                bus.peek(u16::from(addr))
+            )
+        }
+        AddressingMode::ZeroPageY => {
+            let addr = op8.wrapping_add(state.y());
+            format!(
+                "${:02X},Y @ {:02X} = {:02X}",
+                op8,
+                addr,
+                bus.peek(u16::from(addr))
+            )
+        }
+        AddressingMode::Absolute => match instruction.mnemonic() {
+            Mnemonic::Jmp | Mnemonic::Jsr => format!("${:04X}", op16),
+            _ => format!("${:04X} = {:02X}", op16, bus.peek(op16)),
+        },
+        AddressingMode::AbsoluteX => {
+            let addr = op16.wrapping_add(u16::from(state.x()));
+            format!("${:04X},X @ {:04X} = {:02X}", op16, addr, bus.peek(addr))
+        }
+        AddressingMode::AbsoluteY => {
+            let addr = op16.wrapping_add(u16::from(state.y()));
+            format!("${:04X},Y @ {:04X} = {:02X}", op16, addr, bus.peek(addr))
+        }
+        AddressingMode::Indirect => {
+            // The high byte of the target is fetched without carrying into the high byte of the
+            // pointer, as the hardware does
+            let hi_addr = (op16 & 0xff00) | (op16.wrapping_add(1) & 0x00ff);
+            let target = u16::from(bus.peek(op16)) | u16::from(bus.peek(hi_addr)) << 8;
+            format!("(${:04X}) = {:04X}", op16, target)
+        }
+        AddressingMode::IndexedIndirect => {
+            let ptr = op8.wrapping_add(state.x());
+            let addr = read16_zp(ptr);
+            format!(
+                "(${:02X},X) @ {:02X} = {:04X} = {:02X}",
+                op8,
+                ptr,
+                addr,
+                bus.peek(addr)
+            )
+        }
+        AddressingMode::IndirectIndexed => {
+            let base = read16_zp(op8);
+            let addr = base.wrapping_add(u16::from(state.y()));
+            format!(
+                "(${:02X}),Y = {:04X} @ {:04X} = {:02X}",
+                op8,
+                base,
+                addr,
+                bus.peek(addr)
+            )
+        }
+        AddressingMode::Relative => {
+            let target = state.pc().wrapping_add(2).wrapping_add(op8 as i8 as u16);
+            format!("${:04X}", target)
+        }
+    }
+}
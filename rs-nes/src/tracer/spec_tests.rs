@@ -0,0 +1,124 @@
+use crate::{
+    interconnect::NesInterconnect,
+    mocks::{ApuMock, CartMock, InputMock, PpuMock},
+    tracer::{trace_line, Tracer},
+};
+use cpu6502::cpu::{Cpu, Interconnect};
+
+type TraceCpu = Cpu<NesInterconnect<PpuMock, ApuMock, InputMock, CartMock>>;
+
+fn new_fixture(program: &[u8]) -> TraceCpu {
+    let mut cpu = Cpu::new(NesInterconnect::new(CartMock::default()), 0x0400);
+    for (i, byte) in program.iter().enumerate() {
+        cpu.interconnect.write(0x0400 + i as u16, *byte);
+    }
+    let mut state = cpu.state();
+    state.set_sp(0xfd);
+    state.set_status(0x24);
+    cpu.set_state(state);
+    cpu
+}
+
+fn trace(program: &[u8]) -> String {
+    trace_line(&new_fixture(program))
+}
+
+#[test]
+fn line_format() {
+    let mut cpu = new_fixture(&[0x4c, 0xf5, 0xc5]);
+    cpu.interconnect.ppu.set_position(0, 21);
+    assert_eq!(
+        "0400  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:0",
+        trace_line(&cpu)
+    );
+}
+
+#[test]
+fn unofficial_opcodes_are_marked() {
+    assert_eq!(
+        "0400  04 A9    *NOP $A9 = 00                    A:00 X:00 Y:00 P:24 SP:FD PPU:  0,  0 CYC:0",
+        trace(&[0x04, 0xa9])
+    );
+    assert!(trace(&[0xe7, 0x10]).starts_with("0400  E7 10    *ISB $10 = 00 "));
+}
+
+#[test]
+fn operands() {
+    let operand = |program: &[u8], setup: &dyn Fn(&mut TraceCpu)| {
+        let mut cpu = new_fixture(program);
+        setup(&mut cpu);
+        trace_line(&cpu)[16..48].trim_end().to_owned()
+    };
+    let no_setup = |_: &mut TraceCpu| ();
+
+    assert_eq!("NOP", operand(&[0xea], &no_setup));
+    assert_eq!("LSR A", operand(&[0x4a], &no_setup));
+    assert_eq!("LDA #$44", operand(&[0xa9, 0x44], &no_setup));
+    assert_eq!("BCS $0412", operand(&[0xb0, 0x10], &no_setup));
+    assert_eq!("JSR $0600", operand(&[0x20, 0x00, 0x06], &no_setup));
+
+    let setup = |cpu: &mut TraceCpu| {
+        let mut state = cpu.state();
+        state.set_x(0x02);
+        state.set_y(0x03);
+        cpu.set_state(state);
+
+        cpu.interconnect.write(0x0010, 0x80);
+        cpu.interconnect.write(0x0011, 0x81);
+        cpu.interconnect.write(0x0012, 0x00);
+        cpu.interconnect.write(0x0013, 0x03);
+        cpu.interconnect.write(0x00ff, 0x10);
+        cpu.interconnect.write(0x0000, 0x02);
+        cpu.interconnect.write(0x0300, 0x5a);
+        cpu.interconnect.write(0x0303, 0x89);
+        cpu.interconnect.write(0x0213, 0x22);
+        cpu.interconnect.write(0x0200, 0x7e);
+        cpu.interconnect.write(0x0100, 0xdb);
+    };
+
+    assert_eq!("LDA $10 = 80", operand(&[0xa5, 0x10], &setup));
+    assert_eq!("LDA $10,X @ 12 = 00", operand(&[0xb5, 0x10], &setup));
+    assert_eq!("LDX $10,Y @ 13 = 03", operand(&[0xb6, 0x10], &setup));
+    assert_eq!("STA $0300 = 5A", operand(&[0x8d, 0x00, 0x03], &setup));
+    assert_eq!(
+        "LDA $0300,X @ 0302 = 00",
+        operand(&[0xbd, 0x00, 0x03], &setup)
+    );
+    assert_eq!(
+        "LDA $0300,Y @ 0303 = 89",
+        operand(&[0xb9, 0x00, 0x03], &setup)
+    );
+    assert_eq!(
+        "LDA ($10,X) @ 12 = 0300 = 5A",
+        operand(&[0xa1, 0x10], &setup)
+    );
+    assert_eq!(
+        "LDA ($12),Y = 0300 @ 0303 = 89",
+        operand(&[0xb1, 0x12], &setup)
+    );
+
+    // Zero page pointers wrap within the zero page
+    assert_eq!(
+        "LDA ($FF),Y = 0210 @ 0213 = 22",
+        operand(&[0xb1, 0xff], &setup)
+    );
+
+    // The target of an indirect jump never crosses a page
+    assert_eq!("JMP ($01FF) = DB00", operand(&[0x6c, 0xff, 0x01], &setup));
+}
+
+#[test]
+fn step_writes_line_and_executes() {
+    let mut cpu = new_fixture(&[0xa9, 0x44, 0xea]);
+    let mut tracer = Tracer::new(Vec::new());
+    tracer.step(&mut cpu).unwrap();
+    tracer.step(&mut cpu).unwrap();
+
+    let log = String::from_utf8(tracer.into_inner()).unwrap();
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(2, lines.len());
+    assert!(lines[0].starts_with("0400  A9 44     LDA #$44 "));
+    assert!(lines[1].starts_with("0402  EA        NOP "));
+    assert!(lines[1].contains("A:44 X:00 Y:00 P:24"));
+    assert!(lines[1].ends_with("CYC:2"));
+}
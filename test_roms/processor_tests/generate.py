@@ -0,0 +1,528 @@
+#!/usr/bin/env python3
+#
+# Generates nmos6502.json, a few randomized vectors for every NMOS 6502 opcode in the format of
+# the SingleStepTests/ProcessorTests suites.
+#
+# These are regression fixtures made in this repository, not reference data: they only share the
+# format of the suites, and nothing in them was checked against real hardware. The vectors come
+# from the model below, which is written from the cycle-by-cycle descriptions in 64doc.txt and
+# the decimal mode notes at http://www.6502.org/tutorials/ rather than from the emulator, so the
+# two are checked against each other. The JAM opcodes are left out since the suites don't model
+# the lock up.
+#
+#   python3 generate.py > nmos6502.json
+
+import json
+import random
+import sys
+
+TESTS_PER_OPCODE = 4
+SEED = 6502
+
+C, Z, I, D, B, U, V, N = 0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80
+
+# The unstable ANE and LXA opcodes use the most commonly observed magic constant
+UNSTABLE_MAGIC = 0xEE
+
+OPCODES = [
+    # 0x00
+    ("BRK", "brk"), ("ORA", "izx"), ("JAM", None), ("SLO", "izx"),
+    ("NOP", "zp"), ("ORA", "zp"), ("ASL", "zp"), ("SLO", "zp"),
+    ("PHP", "php"), ("ORA", "imm"), ("ASL", "acc"), ("ANC", "imm"),
+    ("NOP", "abs"), ("ORA", "abs"), ("ASL", "abs"), ("SLO", "abs"),
+    # 0x10
+    ("BPL", "rel"), ("ORA", "izy"), ("JAM", None), ("SLO", "izy"),
+    ("NOP", "zpx"), ("ORA", "zpx"), ("ASL", "zpx"), ("SLO", "zpx"),
+    ("CLC", "imp"), ("ORA", "aby"), ("NOP", "imp"), ("SLO", "aby"),
+    ("NOP", "abx"), ("ORA", "abx"), ("ASL", "abx"), ("SLO", "abx"),
+    # 0x20
+    ("JSR", "jsr"), ("AND", "izx"), ("JAM", None), ("RLA", "izx"),
+    ("BIT", "zp"), ("AND", "zp"), ("ROL", "zp"), ("RLA", "zp"),
+    ("PLP", "plp"), ("AND", "imm"), ("ROL", "acc"), ("ANC", "imm"),
+    ("BIT", "abs"), ("AND", "abs"), ("ROL", "abs"), ("RLA", "abs"),
+    # 0x30
+    ("BMI", "rel"), ("AND", "izy"), ("JAM", None), ("RLA", "izy"),
+    ("NOP", "zpx"), ("AND", "zpx"), ("ROL", "zpx"), ("RLA", "zpx"),
+    ("SEC", "imp"), ("AND", "aby"), ("NOP", "imp"), ("RLA", "aby"),
+    ("NOP", "abx"), ("AND", "abx"), ("ROL", "abx"), ("RLA", "abx"),
+    # 0x40
+    ("RTI", "rti"), ("EOR", "izx"), ("JAM", None), ("SRE", "izx"),
+    ("NOP", "zp"), ("EOR", "zp"), ("LSR", "zp"), ("SRE", "zp"),
+    ("PHA", "pha"), ("EOR", "imm"), ("LSR", "acc"), ("ALR", "imm"),
+    ("JMP", "jmp"), ("EOR", "abs"), ("LSR", "abs"), ("SRE", "abs"),
+    # 0x50
+    ("BVC", "rel"), ("EOR", "izy"), ("JAM", None), ("SRE", "izy"),
+    ("NOP", "zpx"), ("EOR", "zpx"), ("LSR", "zpx"), ("SRE", "zpx"),
+    ("CLI", "imp"), ("EOR", "aby"), ("NOP", "imp"), ("SRE", "aby"),
+    ("NOP", "abx"), ("EOR", "abx"), ("LSR", "abx"), ("SRE", "abx"),
+    # 0x60
+    ("RTS", "rts"), ("ADC", "izx"), ("JAM", None), ("RRA", "izx"),
+    ("NOP", "zp"), ("ADC", "zp"), ("ROR", "zp"), ("RRA", "zp"),
+    ("PLA", "pla"), ("ADC", "imm"), ("ROR", "acc"), ("ARR", "imm"),
+    ("JMP", "ind"), ("ADC", "abs"), ("ROR", "abs"), ("RRA", "abs"),
+    # 0x70
+    ("BVS", "rel"), ("ADC", "izy"), ("JAM", None), ("RRA", "izy"),
+    ("NOP", "zpx"), ("ADC", "zpx"), ("ROR", "zpx"), ("RRA", "zpx"),
+    ("SEI", "imp"), ("ADC", "aby"), ("NOP", "imp"), ("RRA", "aby"),
+    ("NOP", "abx"), ("ADC", "abx"), ("ROR", "abx"), ("RRA", "abx"),
+    # 0x80
+    ("NOP", "imm"), ("STA", "izx"), ("NOP", "imm"), ("SAX", "izx"),
+    ("STY", "zp"), ("STA", "zp"), ("STX", "zp"), ("SAX", "zp"),
+    ("DEY", "imp"), ("NOP", "imm"), ("TXA", "imp"), ("ANE", "imm"),
+    ("STY", "abs"), ("STA", "abs"), ("STX", "abs"), ("SAX", "abs"),
+    # 0x90
+    ("BCC", "rel"), ("STA", "izy"), ("JAM", None), ("SHA", "izy"),
+    ("STY", "zpx"), ("STA", "zpx"), ("STX", "zpy"), ("SAX", "zpy"),
+    ("TYA", "imp"), ("STA", "aby"), ("TXS", "imp"), ("TAS", "aby"),
+    ("SHY", "abx"), ("STA", "abx"), ("SHX", "aby"), ("SHA", "aby"),
+    # 0xa0
+    ("LDY", "imm"), ("LDA", "izx"), ("LDX", "imm"), ("LAX", "izx"),
+    ("LDY", "zp"), ("LDA", "zp"), ("LDX", "zp"), ("LAX", "zp"),
+    ("TAY", "imp"), ("LDA", "imm"), ("TAX", "imp"), ("LXA", "imm"),
+    ("LDY", "abs"), ("LDA", "abs"), ("LDX", "abs"), ("LAX", "abs"),
+    # 0xb0
+    ("BCS", "rel"), ("LDA", "izy"), ("JAM", None), ("LAX", "izy"),
+    ("LDY", "zpx"), ("LDA", "zpx"), ("LDX", "zpy"), ("LAX", "zpy"),
+    ("CLV", "imp"), ("LDA", "aby"), ("TSX", "imp"), ("LAS", "aby"),
+    ("LDY", "abx"), ("LDA", "abx"), ("LDX", "aby"), ("LAX", "aby"),
+    # 0xc0
+    ("CPY", "imm"), ("CMP", "izx"), ("NOP", "imm"), ("DCP", "izx"),
+    ("CPY", "zp"), ("CMP", "zp"), ("DEC", "zp"), ("DCP", "zp"),
+    ("INY", "imp"), ("CMP", "imm"), ("DEX", "imp"), ("SBX", "imm"),
+    ("CPY", "abs"), ("CMP", "abs"), ("DEC", "abs"), ("DCP", "abs"),
+    # 0xd0
+    ("BNE", "rel"), ("CMP", "izy"), ("JAM", None), ("DCP", "izy"),
+    ("NOP", "zpx"), ("CMP", "zpx"), ("DEC", "zpx"), ("DCP", "zpx"),
+    ("CLD", "imp"), ("CMP", "aby"), ("NOP", "imp"), ("DCP", "aby"),
+    ("NOP", "abx"), ("CMP", "abx"), ("DEC", "abx"), ("DCP", "abx"),
+    # 0xe0
+    ("CPX", "imm"), ("SBC", "izx"), ("NOP", "imm"), ("ISC", "izx"),
+    ("CPX", "zp"), ("SBC", "zp"), ("INC", "zp"), ("ISC", "zp"),
+    ("INX", "imp"), ("SBC", "imm"), ("NOP", "imp"), ("SBC", "imm"),
+    ("CPX", "abs"), ("SBC", "abs"), ("INC", "abs"), ("ISC", "abs"),
+    # 0xf0
+    ("BEQ", "rel"), ("SBC", "izy"), ("JAM", None), ("ISC", "izy"),
+    ("NOP", "zpx"), ("SBC", "zpx"), ("INC", "zpx"), ("ISC", "zpx"),
+    ("SED", "imp"), ("SBC", "aby"), ("NOP", "imp"), ("ISC", "aby"),
+    ("NOP", "abx"), ("SBC", "abx"), ("INC", "abx"), ("ISC", "abx"),
+]
+
+STORES = {"STA", "STX", "STY", "SAX", "SHA", "SHX", "SHY", "TAS"}
+READ_MODIFY_WRITES = {"ASL", "ROL", "LSR", "ROR", "INC", "DEC", "SLO", "RLA", "SRE", "RRA", "DCP",
+                      "ISC"}
+UNSTABLE_STORES = {"SHA", "SHX", "SHY", "TAS"}
+
+BRANCHES = {
+    "BPL": (N, False), "BMI": (N, True), "BVC": (V, False), "BVS": (V, True),
+    "BCC": (C, False), "BCS": (C, True), "BNE": (Z, False), "BEQ": (Z, True),
+}
+
+INSTRUCTION_LENGTHS = {
+    "imp": 1, "acc": 1, "imm": 2, "zp": 2, "zpx": 2, "zpy": 2, "abs": 3, "abx": 3, "aby": 3,
+    "izx": 2, "izy": 2, "rel": 2, "jmp": 3, "ind": 3, "jsr": 3, "brk": 1, "rti": 1, "rts": 1,
+    "pha": 1, "php": 1, "pla": 1, "plp": 1,
+}
+
+
+class Cpu:
+    def __init__(self, rng, opcode):
+        self.rng = rng
+        self.memory = {}
+        self.initial_memory = {}
+        self.cycles = []
+
+        self.pc = rng.randrange(0x10000)
+        self.s = rng.randrange(0x100)
+        self.a = rng.randrange(0x100)
+        self.x = rng.randrange(0x100)
+        self.y = rng.randrange(0x100)
+        self.p = rng.randrange(0x100) | B | U
+        self.poke(self.pc, opcode)
+
+    # Memory that hasn't been accessed yet holds random values, like the suites' initial state
+    def poke(self, addr, value):
+        self.memory[addr] = value
+        self.initial_memory[addr] = value
+
+    def peek(self, addr):
+        if addr not in self.memory:
+            self.poke(addr, self.rng.randrange(0x100))
+        return self.memory[addr]
+
+    def read(self, addr):
+        value = self.peek(addr)
+        self.cycles.append([addr, value, "read"])
+        return value
+
+    def write(self, addr, value):
+        self.peek(addr)
+        self.memory[addr] = value
+        self.cycles.append([addr, value, "write"])
+
+    def fetch(self):
+        value = self.read(self.pc)
+        self.pc = (self.pc + 1) & 0xFFFF
+        return value
+
+    def push(self, value):
+        self.write(0x100 | self.s, value)
+        self.s = (self.s - 1) & 0xFF
+
+    def pull(self):
+        self.s = (self.s + 1) & 0xFF
+        return self.read(0x100 | self.s)
+
+    def flag(self, flag):
+        return self.p & flag != 0
+
+    def set_flag(self, flag, on):
+        self.p = self.p | flag if on else self.p & ~flag
+
+    def set_nz(self, value):
+        self.set_flag(N, value & 0x80)
+        self.set_flag(Z, value == 0)
+        return value
+
+    # Addressing modes return the effective address, and the address used before the page was
+    # fixed up by an index, for the unstable stores
+
+    def indexed(self, base, index, always_fix):
+        unfixed = (base & 0xFF00) | ((base + index) & 0xFF)
+        addr = (base + index) & 0xFFFF
+        if always_fix or unfixed != addr:
+            self.read(unfixed)
+        return addr, base
+
+    def address(self, mode, name):
+        always_fix = name in STORES or name in READ_MODIFY_WRITES
+        if mode == "zp":
+            return self.fetch(), None
+        if mode in ("zpx", "zpy"):
+            base = self.fetch()
+            self.read(base)
+            return (base + (self.x if mode == "zpx" else self.y)) & 0xFF, None
+        if mode == "abs":
+            lo = self.fetch()
+            return self.fetch() << 8 | lo, None
+        if mode in ("abx", "aby"):
+            lo = self.fetch()
+            base = self.fetch() << 8 | lo
+            return self.indexed(base, self.x if mode == "abx" else self.y, always_fix)
+        if mode == "izx":
+            pointer = self.fetch()
+            self.read(pointer)
+            pointer = (pointer + self.x) & 0xFF
+            lo = self.read(pointer)
+            return self.read((pointer + 1) & 0xFF) << 8 | lo, None
+        if mode == "izy":
+            pointer = self.fetch()
+            lo = self.read(pointer)
+            base = self.read((pointer + 1) & 0xFF) << 8 | lo
+            return self.indexed(base, self.y, always_fix)
+        raise ValueError(mode)
+
+    def adc(self, value):
+        carry = 1 if self.flag(C) else 0
+        binary = self.a + value + carry
+        if not self.flag(D):
+            self.set_flag(C, binary > 0xFF)
+            self.set_flag(V, ~(self.a ^ value) & (self.a ^ binary) & 0x80)
+            self.a = self.set_nz(binary & 0xFF)
+            return
+
+        lo = (self.a & 0x0F) + (value & 0x0F) + carry
+        if lo >= 0x0A:
+            lo = ((lo + 0x06) & 0x0F) + 0x10
+        result = (self.a & 0xF0) + (value & 0xF0) + lo
+        signed = signed_byte(self.a & 0xF0) + signed_byte(value & 0xF0) + lo
+        self.set_flag(V, signed < -128 or signed > 127)
+        self.set_flag(N, result & 0x80)
+        self.set_flag(Z, binary & 0xFF == 0)
+        if result >= 0xA0:
+            result += 0x60
+        self.set_flag(C, result >= 0x100)
+        self.a = result & 0xFF
+
+    def sbc(self, value):
+        borrow = 0 if self.flag(C) else 1
+        decimal = self.flag(D)
+        lo = (self.a & 0x0F) - (value & 0x0F) - borrow
+        if lo < 0:
+            lo = ((lo - 0x06) & 0x0F) - 0x10
+        result = (self.a & 0xF0) - (value & 0xF0) + lo
+        if result < 0:
+            result -= 0x60
+
+        # The flags always come from the binary subtraction
+        self.set_flag(D, False)
+        self.adc(value ^ 0xFF)
+        self.set_flag(D, decimal)
+        if decimal:
+            self.a = result & 0xFF
+
+    def compare(self, register, value):
+        self.set_flag(C, register >= value)
+        self.set_nz((register - value) & 0xFF)
+
+    def modify(self, name, value):
+        carry = 1 if self.flag(C) else 0
+        if name in ("ASL", "SLO"):
+            self.set_flag(C, value & 0x80)
+            value = (value << 1) & 0xFF
+        elif name in ("ROL", "RLA"):
+            self.set_flag(C, value & 0x80)
+            value = ((value << 1) | carry) & 0xFF
+        elif name in ("LSR", "SRE"):
+            self.set_flag(C, value & 0x01)
+            value >>= 1
+        elif name in ("ROR", "RRA"):
+            self.set_flag(C, value & 0x01)
+            value = (value >> 1) | (carry << 7)
+        elif name in ("INC", "ISC"):
+            value = (value + 1) & 0xFF
+        elif name in ("DEC", "DCP"):
+            value = (value - 1) & 0xFF
+        self.set_nz(value)
+
+        if name == "SLO":
+            self.a = self.set_nz(self.a | value)
+        elif name == "RLA":
+            self.a = self.set_nz(self.a & value)
+        elif name == "SRE":
+            self.a = self.set_nz(self.a ^ value)
+        elif name == "RRA":
+            self.adc(value)
+        elif name == "DCP":
+            self.compare(self.a, value)
+        elif name == "ISC":
+            self.sbc(value)
+        return value
+
+    def operate(self, name, value):
+        if name == "ORA":
+            self.a = self.set_nz(self.a | value)
+        elif name == "AND":
+            self.a = self.set_nz(self.a & value)
+        elif name == "EOR":
+            self.a = self.set_nz(self.a ^ value)
+        elif name == "ADC":
+            self.adc(value)
+        elif name == "SBC":
+            self.sbc(value)
+        elif name == "CMP":
+            self.compare(self.a, value)
+        elif name == "CPX":
+            self.compare(self.x, value)
+        elif name == "CPY":
+            self.compare(self.y, value)
+        elif name == "BIT":
+            self.set_flag(Z, self.a & value == 0)
+            self.set_flag(N, value & 0x80)
+            self.set_flag(V, value & 0x40)
+        elif name == "LDA":
+            self.a = self.set_nz(value)
+        elif name == "LDX":
+            self.x = self.set_nz(value)
+        elif name == "LDY":
+            self.y = self.set_nz(value)
+        elif name == "LAX":
+            self.a = self.x = self.set_nz(value)
+        elif name == "LAS":
+            self.a = self.x = self.s = self.set_nz(value & self.s)
+        elif name == "ANC":
+            self.a = self.set_nz(self.a & value)
+            self.set_flag(C, self.a & 0x80)
+        elif name == "ALR":
+            value &= self.a
+            self.set_flag(C, value & 0x01)
+            self.a = self.set_nz(value >> 1)
+        elif name == "ARR":
+            value &= self.a
+            result = self.set_nz((value >> 1) | (0x80 if self.flag(C) else 0))
+            if self.flag(D):
+                # N and Z come from the rotation, then each nibble is fixed up based on the ANDed
+                # value
+                self.set_flag(V, (result ^ value) & 0x40)
+                if (value & 0x0F) + (value & 0x01) > 0x05:
+                    result = (result & 0xF0) | ((result + 0x06) & 0x0F)
+                self.set_flag(C, (value & 0xF0) + (value & 0x10) > 0x50)
+                if self.flag(C):
+                    result = (result + 0x60) & 0xFF
+            else:
+                self.set_flag(C, result & 0x40)
+                self.set_flag(V, ((result >> 6) ^ (result >> 5)) & 0x01)
+            self.a = result
+        elif name == "ANE":
+            self.a = self.set_nz((self.a | UNSTABLE_MAGIC) & self.x & value)
+        elif name == "LXA":
+            self.a = self.x = self.set_nz((self.a | UNSTABLE_MAGIC) & value)
+        elif name == "SBX":
+            lhs = self.a & self.x
+            self.set_flag(C, lhs >= value)
+            self.x = self.set_nz((lhs - value) & 0xFF)
+        elif name != "NOP":
+            raise ValueError(name)
+
+    def store(self, name, addr, base):
+        if name in UNSTABLE_STORES:
+            value = {"SHA": self.a & self.x, "SHX": self.x, "SHY": self.y, "TAS": self.a & self.x}
+            value = value[name]
+            if name == "TAS":
+                self.s = value
+            value &= ((base >> 8) + 1) & 0xFF
+            if base & 0xFF00 != addr & 0xFF00:
+                addr = (value << 8) | (addr & 0xFF)
+            self.write(addr, value)
+            return
+
+        value = {"STA": self.a, "STX": self.x, "STY": self.y, "SAX": self.a & self.x}[name]
+        self.write(addr, value)
+
+    def implied(self, name):
+        if name in ("CLC", "SEC", "CLI", "SEI", "CLV", "CLD", "SED"):
+            flag = {"C": C, "I": I, "V": V, "D": D}[name[2]]
+            self.set_flag(flag, name[0] == "S")
+        elif name in ("TAX", "TAY", "TSX"):
+            value = self.a if name != "TSX" else self.s
+            if name == "TAY":
+                self.y = self.set_nz(value)
+            else:
+                self.x = self.set_nz(value)
+        elif name == "TXA":
+            self.a = self.set_nz(self.x)
+        elif name == "TYA":
+            self.a = self.set_nz(self.y)
+        elif name == "TXS":
+            self.s = self.x
+        elif name in ("INX", "DEX"):
+            self.x = self.set_nz((self.x + (1 if name == "INX" else -1)) & 0xFF)
+        elif name in ("INY", "DEY"):
+            self.y = self.set_nz((self.y + (1 if name == "INY" else -1)) & 0xFF)
+        elif name != "NOP":
+            raise ValueError(name)
+
+    def step(self, name, mode):
+        self.fetch()
+        if mode == "imp":
+            self.read(self.pc)
+            self.implied(name)
+        elif mode == "acc":
+            self.read(self.pc)
+            self.a = self.modify(name, self.a)
+        elif mode == "imm":
+            self.operate(name, self.fetch())
+        elif mode == "rel":
+            offset = self.fetch()
+            flag, set_ = BRANCHES[name]
+            if self.flag(flag) == set_:
+                self.read(self.pc)
+                target = (self.pc + signed_byte(offset)) & 0xFFFF
+                if target & 0xFF00 != self.pc & 0xFF00:
+                    self.read((self.pc & 0xFF00) | (target & 0xFF))
+                self.pc = target
+        elif mode == "jmp":
+            lo = self.fetch()
+            self.pc = self.read(self.pc) << 8 | lo
+        elif mode == "ind":
+            lo = self.fetch()
+            pointer = self.fetch() << 8 | lo
+            lo = self.read(pointer)
+            self.pc = self.read((pointer & 0xFF00) | ((pointer + 1) & 0xFF)) << 8 | lo
+        elif mode == "jsr":
+            lo = self.fetch()
+            self.read(0x100 | self.s)
+            self.push(self.pc >> 8)
+            self.push(self.pc & 0xFF)
+            self.pc = self.read(self.pc) << 8 | lo
+        elif mode == "rts":
+            self.read(self.pc)
+            self.read(0x100 | self.s)
+            lo = self.pull()
+            self.pc = self.pull() << 8 | lo
+            self.fetch()
+        elif mode == "rti":
+            self.read(self.pc)
+            self.read(0x100 | self.s)
+            self.p = self.pull() | B | U
+            lo = self.pull()
+            self.pc = self.pull() << 8 | lo
+        elif mode == "brk":
+            self.fetch()
+            self.push(self.pc >> 8)
+            self.push(self.pc & 0xFF)
+            self.push(self.p | B | U)
+            self.set_flag(I, True)
+            lo = self.read(0xFFFE)
+            self.pc = self.read(0xFFFF) << 8 | lo
+        elif mode in ("pha", "php"):
+            self.read(self.pc)
+            self.push(self.a if mode == "pha" else self.p | B | U)
+        elif mode in ("pla", "plp"):
+            self.read(self.pc)
+            self.read(0x100 | self.s)
+            if mode == "pla":
+                self.a = self.set_nz(self.pull())
+            else:
+                self.p = self.pull() | B | U
+        else:
+            addr, base = self.address(mode, name)
+            if name in STORES:
+                self.store(name, addr, base)
+            elif name in READ_MODIFY_WRITES:
+                value = self.read(addr)
+                self.write(addr, value)
+                self.write(addr, self.modify(name, value))
+            else:
+                self.operate(name, self.read(addr))
+
+    def snapshot(self, memory):
+        return {
+            "pc": self.pc,
+            "s": self.s,
+            "a": self.a,
+            "x": self.x,
+            "y": self.y,
+            "p": self.p,
+            "ram": [[addr, memory[addr]] for addr in sorted(memory)],
+        }
+
+
+def signed_byte(value):
+    return value - 0x100 if value & 0x80 else value
+
+
+def generate(rng, opcode, name, mode):
+    cpu = Cpu(rng, opcode)
+    for i in range(1, INSTRUCTION_LENGTHS[mode]):
+        cpu.peek((cpu.pc + i) & 0xFFFF)
+    instruction = [cpu.memory[(cpu.pc + i) & 0xFFFF] for i in range(INSTRUCTION_LENGTHS[mode])]
+
+    initial = cpu.snapshot(cpu.memory)
+    cpu.step(name, mode)
+
+    # Memory first accessed during the step still needs its initial value
+    initial["ram"] = [[addr, cpu.initial_memory[addr]] for addr in sorted(cpu.initial_memory)]
+    return {
+        "name": " ".join("%02x" % byte for byte in instruction),
+        "initial": initial,
+        "final": cpu.snapshot(cpu.memory),
+        "cycles": cpu.cycles,
+    }
+
+
+def main():
+    rng = random.Random(SEED)
+    tests = []
+    for opcode, (name, mode) in enumerate(OPCODES):
+        if name == "JAM":
+            continue
+        for _ in range(TESTS_PER_OPCODE):
+            tests.append(generate(rng, opcode, name, mode))
+
+    sys.stdout.write("[\n")
+    sys.stdout.write(",\n".join(json.dumps(test, separators=(",", ":")) for test in tests))
+    sys.stdout.write("\n]\n")
+
+
+if __name__ == "__main__":
+    main()